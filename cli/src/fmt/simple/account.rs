@@ -7,6 +7,15 @@ impl Render<Account> for Simple {
         let mut buf = String::new();
         append!(buf, "ID:       {}", account.id());
         append!(buf, "Sequence: {}", account.sequence());
+        append!(buf, "Balances:");
+        for balance in account.balances() {
+            append!(
+                buf,
+                "  {}: {}",
+                self.render(balance.asset_identifier()).unwrap(),
+                balance.balance()
+            );
+        }
         Some(buf)
     }
 }
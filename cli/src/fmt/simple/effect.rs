@@ -19,6 +19,10 @@ impl Render<Effect> for Simple {
             Kind::Trustline(ref kind) => trustline::render(buf, kind),
             Kind::Trade(ref kind) => trade::render(buf, kind),
             Kind::Data(ref kind) => data::render(buf, kind),
+            Kind::Unknown { ref raw, .. } => {
+                append!(buf, "  {}", raw);
+                buf
+            }
         })
     }
 }
@@ -19,6 +19,7 @@ impl Render<Operation> for Simple {
             Kind::AccountMerge(kind) => self.render(kind),
             Kind::Inflation => None,
             Kind::ManageData(kind) => self.render(kind),
+            Kind::Unknown { raw, .. } => Some(raw.to_string()),
         };
         if let Some(value) = kind_details {
             append!(buf, "{}", value);
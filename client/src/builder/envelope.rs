@@ -0,0 +1,605 @@
+use super::{Error, Result, MAX_SIGNATURES};
+use keypair::{self, Keypair};
+use network::{self, Network};
+use resources::Account;
+use xdr::{
+    self, AccountEntry, AccountId, DecoratedSignature, Hash, OperationBody, PublicKey, Signer,
+    SignerKey, Thresholds, Transaction, TransactionEnvelope,
+};
+
+/// The threshold an operation must meet, which depends on how much control
+/// the operation has over the account.
+///
+/// <https://www.stellar.org/developers/guides/concepts/multi-sig.html#thresholds>
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ThresholdCategory {
+    /// Allow trust, bump sequence and inflation, as well as the fee and
+    /// sequence number of a transaction.
+    Low,
+    /// All other operations.
+    Medium,
+    /// Account merge, and set options that change signers or thresholds.
+    High,
+}
+
+impl ThresholdCategory {
+    /// The category of the operation.
+    pub fn of(body: &OperationBody) -> ThresholdCategory {
+        match *body {
+            OperationBody::AllowTrust(_)
+            | OperationBody::BumpSequence(_)
+            | OperationBody::Inflation => ThresholdCategory::Low,
+            OperationBody::AccountMerge(_) => ThresholdCategory::High,
+            OperationBody::SetOptions(ref options)
+                if options.master_weight.is_some()
+                    || options.low_threshold.is_some()
+                    || options.med_threshold.is_some()
+                    || options.high_threshold.is_some()
+                    || options.signer.is_some() =>
+            {
+                ThresholdCategory::High
+            }
+            _ => ThresholdCategory::Medium,
+        }
+    }
+}
+
+/// The signers of an account and the thresholds their summed weights must
+/// meet. These are usually taken from the account's ledger entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountSigners {
+    account_id: AccountId,
+    thresholds: Thresholds,
+    signers: Vec<Signer>,
+}
+
+impl AccountSigners {
+    /// The account with its thresholds, given in the same order as the ledger:
+    /// master weight, low, medium and high.
+    pub fn new(account_id: AccountId, thresholds: Thresholds) -> Self {
+        AccountSigners {
+            account_id,
+            thresholds,
+            signers: Vec::new(),
+        }
+    }
+
+    /// The signers and thresholds from an account's ledger entry.
+    pub fn from_entry(entry: &AccountEntry) -> Self {
+        AccountSigners {
+            account_id: entry.account_id,
+            thresholds: entry.thresholds,
+            signers: entry.signers.clone(),
+        }
+    }
+
+    /// The signers and thresholds of an account fetched from horizon. Horizon
+    /// lists the master key among the signers, so it is picked out by its key.
+    pub fn from_account(account: &Account) -> Result<Self> {
+        let account_id = account
+            .account_id()
+            .parse::<AccountId>()
+            .map_err(Error::InvalidAccountId)?;
+        let thresholds = account.thresholds();
+        let mut master_weight = 0;
+        let mut signers = Vec::new();
+        for signer in account.signers() {
+            if signer.key() == account.account_id() {
+                master_weight = signer.weight();
+                continue;
+            }
+            signers.push(Signer {
+                key: signer.key().parse().map_err(Error::InvalidSigner)?,
+                weight: u32::from(signer.weight()),
+            });
+        }
+        Ok(AccountSigners {
+            account_id,
+            thresholds: [
+                master_weight,
+                thresholds.low(),
+                thresholds.med(),
+                thresholds.high(),
+            ],
+            signers,
+        })
+    }
+
+    /// Adds a signer in addition to the master key.
+    pub fn with_signer(mut self, signer: Signer) -> Self {
+        self.signers.push(signer);
+        self
+    }
+
+    /// The id of the account.
+    pub fn account_id(&self) -> &AccountId {
+        &self.account_id
+    }
+
+    /// The weight of the account's own key.
+    pub fn master_weight(&self) -> u8 {
+        self.thresholds[0]
+    }
+
+    /// The weight needed to authorize operations of the category.
+    pub fn threshold(&self, category: ThresholdCategory) -> u8 {
+        match category {
+            ThresholdCategory::Low => self.thresholds[1],
+            ThresholdCategory::Medium => self.thresholds[2],
+            ThresholdCategory::High => self.thresholds[3],
+        }
+    }
+
+    /// The signers in addition to the master key.
+    pub fn signers(&self) -> &[Signer] {
+        &self.signers
+    }
+
+    fn master_signer(&self) -> Signer {
+        let PublicKey::Ed25519(key) = self.account_id;
+        Signer {
+            key: SignerKey::Ed25519(key),
+            weight: u32::from(self.master_weight()),
+        }
+    }
+}
+
+/// A transaction that is collecting signatures. Signatures can be made with
+/// keypairs held locally, or made elsewhere and attached, until the weights of
+/// the signers meet the thresholds of every account the transaction uses.
+///
+/// ## Example
+/// ```
+/// use stellar_client::{Keypair, Network, TransactionBuilder};
+/// use stellar_client::builder::AccountSigners;
+/// use stellar_client::builder::operation::Inflation;
+/// use stellar_client::xdr::{Signer, SignerKey};
+///
+/// let treasury = Keypair::random().unwrap();
+/// let alice = Keypair::random().unwrap();
+/// let bob = Keypair::random().unwrap();
+///
+/// // The master key is disabled so both signers must sign.
+/// let signers = AccountSigners::new(treasury.public_key(), [0, 2, 2, 2])
+///     .with_signer(Signer {
+///         key: SignerKey::Ed25519(*alice.public_key_bytes()),
+///         weight: 1,
+///     })
+///     .with_signer(Signer {
+///         key: SignerKey::Ed25519(*bob.public_key_bytes()),
+///         weight: 1,
+///     });
+///
+/// let envelope = TransactionBuilder::new(&treasury.account_id(), 1)
+///     .with_operation(Inflation::new())
+///     .envelope(&Network::test())
+///     .unwrap()
+///     .sign(&alice);
+/// assert!(envelope.verify_thresholds(&[signers.clone()]).is_err());
+///
+/// // Bob signs elsewhere and sends back his signature.
+/// let signature = bob.sign_decorated(envelope.hash());
+/// let envelope = envelope.with_signature(signature);
+/// assert!(envelope.verify_thresholds(&[signers]).is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Envelope {
+    tx: Transaction,
+    hash: Hash,
+    signatures: Vec<DecoratedSignature>,
+}
+
+impl Envelope {
+    /// Starts collecting signatures for the transaction on the network.
+    pub fn new(tx: Transaction, network: &Network) -> Self {
+        Envelope {
+            hash: network.hash_transaction(&tx),
+            tx,
+            signatures: Vec::new(),
+        }
+    }
+
+    /// Continues collecting signatures on an envelope that was encoded as
+    /// base64, such as one passed along by another signer.
+    pub fn from_base64(envelope_xdr: &str, network: &Network) -> Result<Self> {
+        let envelope: TransactionEnvelope =
+            xdr::from_base64(envelope_xdr).map_err(|_| Error::InvalidEnvelope)?;
+        Ok(Envelope::new(envelope.tx, network).with_signatures(envelope.signatures))
+    }
+
+    /// The transaction being signed.
+    pub fn transaction(&self) -> &Transaction {
+        &self.tx
+    }
+
+    /// The network scoped hash of the transaction, which is what signers sign.
+    pub fn hash(&self) -> &Hash {
+        &self.hash
+    }
+
+    /// The signatures collected so far.
+    pub fn signatures(&self) -> &[DecoratedSignature] {
+        &self.signatures
+    }
+
+    /// Signs the transaction with the keypair.
+    pub fn sign(self, keypair: &Keypair) -> Self {
+        let signature = keypair.sign_decorated(&self.hash);
+        self.with_signature(signature)
+    }
+
+    /// Attaches a signature made elsewhere. Signatures already attached are
+    /// not added twice.
+    pub fn with_signature(mut self, signature: DecoratedSignature) -> Self {
+        if !self.signatures.contains(&signature) {
+            self.signatures.push(signature);
+        }
+        self
+    }
+
+    /// Attaches several signatures made elsewhere.
+    pub fn with_signatures<I>(self, signatures: I) -> Self
+    where
+        I: IntoIterator<Item = DecoratedSignature>,
+    {
+        signatures.into_iter().fold(self, |envelope, signature| {
+            envelope.with_signature(signature)
+        })
+    }
+
+    /// The summed weight of the account's signers that have signed. Each
+    /// signer counts once, and signatures that don't verify count for nothing.
+    pub fn signature_weight(&self, account: &AccountSigners) -> u32 {
+        Some(account.master_signer())
+            .into_iter()
+            .chain(account.signers().iter().cloned())
+            .filter(|signer| signer.weight > 0 && self.is_signed_by(&signer.key))
+            .map(|signer| signer.weight.min(u32::from(::std::u8::MAX)))
+            .sum()
+    }
+
+    /// Checks that the signatures meet the thresholds of each account the
+    /// transaction uses. The source account must meet the threshold of its
+    /// most demanding operation, and at least the low threshold for the fee
+    /// and sequence number. Operations with their own source account need the
+    /// signers of that account as well.
+    ///
+    /// Accounts are checked in the order the transaction first uses them,
+    /// starting with its source account, and the first to fall short is the
+    /// one the error names.
+    pub fn verify_thresholds(&self, accounts: &[AccountSigners]) -> Result<()> {
+        if self.signatures.len() > MAX_SIGNATURES {
+            return Err(Error::TooManySignatures);
+        }
+        for (account_id, category) in self.threshold_categories() {
+            let account = accounts
+                .iter()
+                .find(|account| account.account_id == account_id)
+                .ok_or_else(|| Error::MissingSigners(account_id.to_string()))?;
+            let weight = self.signature_weight(account);
+            // A threshold of zero still needs a signature from someone.
+            let threshold = account.threshold(category);
+            if weight == 0 || weight < u32::from(threshold) {
+                return Err(Error::InsufficientWeight {
+                    account_id: account_id.to_string(),
+                    weight,
+                    threshold,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The most demanding threshold category of each account the transaction
+    /// uses, in the order it first uses them.
+    fn threshold_categories(&self) -> Vec<(AccountId, ThresholdCategory)> {
+        let mut categories = vec![(self.tx.source_account, ThresholdCategory::Low)];
+        for operation in &self.tx.operations {
+            let source = operation.source_account.unwrap_or(self.tx.source_account);
+            let category = ThresholdCategory::of(&operation.body);
+            match categories
+                .iter_mut()
+                .find(|&&mut (account_id, _)| account_id == source)
+            {
+                Some(&mut (_, ref mut current)) => *current = ::std::cmp::max(*current, category),
+                None => categories.push((source, category)),
+            }
+        }
+        categories
+    }
+
+    /// The envelope encoded as base64, ready to be submitted.
+    pub fn to_base64(&self) -> Result<String> {
+        if self.signatures.len() > MAX_SIGNATURES {
+            return Err(Error::TooManySignatures);
+        }
+        Ok(xdr::to_base64(&TransactionEnvelope {
+            tx: self.tx.clone(),
+            signatures: self.signatures.clone(),
+        }))
+    }
+
+    fn is_signed_by(&self, key: &SignerKey) -> bool {
+        match *key {
+            SignerKey::Ed25519(ref key) => self.signatures.iter().any(|signature| {
+                signature.hint == key[28..]
+                    && keypair::verify(&PublicKey::Ed25519(*key), &self.hash, &signature.signature)
+            }),
+            SignerKey::PreAuthTx(ref hash) => *hash == self.hash,
+            SignerKey::HashX(ref hash) => self.signatures.iter().any(|signature| {
+                signature.hint == hash[28..] && network::sha256(&signature.signature) == *hash
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod envelope_tests {
+    use super::*;
+    use builder::operation::{AccountMerge, BuildOperation, Inflation, Payment, SetOptions};
+    use builder::TransactionBuilder;
+    use resources::{Amount, AssetIdentifier};
+    use serde_json;
+    use xdr::Operation;
+
+    fn keypair(seed: u8) -> Keypair {
+        Keypair::from_raw_seed([seed; 32])
+    }
+
+    fn signer(keypair: &Keypair, weight: u32) -> Signer {
+        Signer {
+            key: SignerKey::Ed25519(*keypair.public_key_bytes()),
+            weight,
+        }
+    }
+
+    fn payment(destination: &Keypair) -> Operation {
+        Payment::new(
+            &destination.account_id(),
+            AssetIdentifier::native(),
+            Amount::new(1),
+        )
+        .build()
+        .unwrap()
+    }
+
+    fn envelope(source: &Keypair, operations: Vec<Operation>) -> Envelope {
+        TransactionBuilder::new(&source.account_id(), 1)
+            .with_operations(operations)
+            .envelope(&Network::test())
+            .unwrap()
+    }
+
+    #[test]
+    fn it_categorizes_operations() {
+        let category = |op: Operation| ThresholdCategory::of(&op.body);
+        assert_eq!(
+            category(Inflation::new().build().unwrap()),
+            ThresholdCategory::Low
+        );
+        assert_eq!(category(payment(&keypair(1))), ThresholdCategory::Medium);
+        assert_eq!(
+            category(SetOptions::new().with_home_domain("a.com").build().unwrap()),
+            ThresholdCategory::Medium
+        );
+        assert_eq!(
+            category(SetOptions::new().with_thresholds(1, 2, 3).build().unwrap()),
+            ThresholdCategory::High
+        );
+        let merge = AccountMerge::new(&keypair(1).account_id()).build().unwrap();
+        assert_eq!(category(merge), ThresholdCategory::High);
+    }
+
+    #[test]
+    fn it_takes_the_signers_from_an_account() {
+        let account: Account =
+            serde_json::from_str(include_str!("../../fixtures/account.json")).unwrap();
+        let signers = AccountSigners::from_account(&account).unwrap();
+        assert_eq!(&signers.account_id().to_string(), account.account_id());
+        assert_eq!(signers.master_weight(), 1);
+        assert_eq!(signers.threshold(ThresholdCategory::High), 3);
+        assert_eq!(signers.signers().len(), 2);
+        assert_eq!(
+            signers.signers()[0],
+            Signer {
+                key: SignerKey::Ed25519([2; 32]),
+                weight: 2,
+            }
+        );
+        assert_eq!(signers.signers()[1].key, SignerKey::PreAuthTx([4; 32]));
+    }
+
+    #[test]
+    fn it_sums_the_weights_of_the_signers() {
+        let source = keypair(1);
+        let (alice, bob, eve) = (keypair(2), keypair(3), keypair(4));
+        let signers = AccountSigners::new(source.public_key(), [1, 0, 0, 0])
+            .with_signer(signer(&alice, 2))
+            .with_signer(signer(&bob, 4));
+        let envelope = envelope(&source, vec![payment(&alice)]);
+        assert_eq!(envelope.signature_weight(&signers), 0);
+
+        let envelope = envelope.sign(&source).sign(&bob).sign(&eve);
+        assert_eq!(envelope.signature_weight(&signers), 5);
+        // Signing twice does not count twice.
+        let envelope = envelope.sign(&bob);
+        assert_eq!(envelope.signatures().len(), 3);
+        assert_eq!(envelope.signature_weight(&signers), 5);
+    }
+
+    #[test]
+    fn it_ignores_signatures_that_do_not_verify() {
+        let source = keypair(1);
+        let signers = AccountSigners::new(source.public_key(), [1, 0, 0, 0]);
+        let envelope = envelope(&source, vec![payment(&source)]);
+        let forged = DecoratedSignature {
+            hint: source.signature_hint(),
+            signature: source.sign(b"something else"),
+        };
+        assert_eq!(
+            envelope.with_signature(forged).signature_weight(&signers),
+            0
+        );
+    }
+
+    #[test]
+    fn it_counts_pre_authorized_and_hash_x_signers() {
+        let source = keypair(1);
+        let preimage = b"a secret preimage".to_vec();
+        let hash = network::sha256(&preimage);
+        let envelope = envelope(&source, vec![payment(&source)]);
+        let signers = AccountSigners::new(source.public_key(), [0, 0, 0, 0])
+            .with_signer(Signer {
+                key: SignerKey::PreAuthTx(*envelope.hash()),
+                weight: 1,
+            })
+            .with_signer(Signer {
+                key: SignerKey::HashX(hash),
+                weight: 2,
+            });
+        assert_eq!(envelope.signature_weight(&signers), 1);
+
+        let mut hint = [0; 4];
+        hint.copy_from_slice(&hash[28..]);
+        let envelope = envelope.with_signature(DecoratedSignature {
+            hint,
+            signature: preimage,
+        });
+        assert_eq!(envelope.signature_weight(&signers), 3);
+    }
+
+    #[test]
+    fn it_verifies_thresholds_by_category() {
+        let source = keypair(1);
+        let (alice, bob) = (keypair(2), keypair(3));
+        let signers = AccountSigners::new(source.public_key(), [0, 1, 2, 3])
+            .with_signer(signer(&alice, 1))
+            .with_signer(signer(&bob, 2));
+
+        let low = envelope(&source, vec![Inflation::new().build().unwrap()]).sign(&alice);
+        assert_eq!(low.verify_thresholds(&[signers.clone()]), Ok(()));
+
+        let medium = envelope(&source, vec![payment(&alice)]).sign(&alice);
+        assert_eq!(
+            medium.verify_thresholds(&[signers.clone()]),
+            Err(Error::InsufficientWeight {
+                account_id: source.account_id(),
+                weight: 1,
+                threshold: 2,
+            })
+        );
+        let medium = medium.sign(&bob);
+        assert_eq!(medium.verify_thresholds(&[signers.clone()]), Ok(()));
+
+        let merge = AccountMerge::new(&alice.account_id()).build().unwrap();
+        let high = envelope(&source, vec![payment(&alice), merge]).sign(&bob);
+        assert!(high.verify_thresholds(&[signers.clone()]).is_err());
+        assert_eq!(high.sign(&alice).verify_thresholds(&[signers]), Ok(()));
+    }
+
+    #[test]
+    fn it_needs_a_signature_even_with_zero_thresholds() {
+        let source = keypair(1);
+        let signers = AccountSigners::new(source.public_key(), [1, 0, 0, 0]);
+        let envelope = envelope(&source, vec![payment(&source)]);
+        assert!(envelope.verify_thresholds(&[signers.clone()]).is_err());
+        assert!(envelope.sign(&source).verify_thresholds(&[signers]).is_ok());
+    }
+
+    #[test]
+    fn it_verifies_the_source_accounts_of_operations() {
+        let (source, other) = (keypair(1), keypair(2));
+        let op = Payment::new(
+            &source.account_id(),
+            AssetIdentifier::native(),
+            Amount::new(1),
+        )
+        .with_source_account(&other.account_id())
+        .build()
+        .unwrap();
+        let envelope = envelope(&source, vec![op]).sign(&source);
+        let source_signers = AccountSigners::new(source.public_key(), [1, 1, 1, 1]);
+        let other_signers = AccountSigners::new(other.public_key(), [1, 1, 1, 1]);
+
+        assert_eq!(
+            envelope.verify_thresholds(&[source_signers.clone()]),
+            Err(Error::MissingSigners(other.account_id()))
+        );
+        assert!(envelope
+            .verify_thresholds(&[source_signers.clone(), other_signers.clone()])
+            .is_err());
+        let envelope = envelope.sign(&other);
+        assert!(envelope
+            .verify_thresholds(&[source_signers, other_signers])
+            .is_ok());
+    }
+
+    #[test]
+    fn it_reports_the_first_account_that_falls_short() {
+        let (source, alice, bob) = (keypair(1), keypair(2), keypair(3));
+        let operation = |account: &Keypair| {
+            Payment::new(
+                &source.account_id(),
+                AssetIdentifier::native(),
+                Amount::new(1),
+            )
+            .with_source_account(&account.account_id())
+            .build()
+            .unwrap()
+        };
+        let envelope = envelope(&source, vec![operation(&bob), operation(&alice)]);
+        let signers = |account: &Keypair| AccountSigners::new(account.public_key(), [1, 1, 1, 1]);
+
+        assert_eq!(
+            envelope.verify_thresholds(&[]),
+            Err(Error::MissingSigners(source.account_id()))
+        );
+        assert_eq!(
+            envelope.verify_thresholds(&[signers(&source)]),
+            Err(Error::InsufficientWeight {
+                account_id: source.account_id(),
+                weight: 0,
+                threshold: 1,
+            })
+        );
+        let envelope = envelope.sign(&source);
+        assert_eq!(
+            envelope.verify_thresholds(&[signers(&source)]),
+            Err(Error::MissingSigners(bob.account_id()))
+        );
+        assert_eq!(
+            envelope.verify_thresholds(&[signers(&source), signers(&alice), signers(&bob)]),
+            Err(Error::InsufficientWeight {
+                account_id: bob.account_id(),
+                weight: 0,
+                threshold: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn it_round_trips_through_base64() {
+        let (source, other) = (keypair(1), keypair(2));
+        let envelope = envelope(&source, vec![payment(&other)]).sign(&source);
+        let encoded = envelope.to_base64().unwrap();
+
+        let decoded = Envelope::from_base64(&encoded, &Network::test())
+            .unwrap()
+            .sign(&other);
+        assert_eq!(decoded.hash(), envelope.hash());
+        assert_eq!(decoded.signatures().len(), 2);
+        assert_eq!(
+            Envelope::from_base64("not xdr", &Network::test()).map(|_| ()),
+            Err(Error::InvalidEnvelope)
+        );
+    }
+
+    #[test]
+    fn it_limits_the_number_of_signatures() {
+        let source = keypair(1);
+        let envelope = (0..21).fold(envelope(&source, vec![payment(&source)]), |envelope, i| {
+            envelope.sign(&keypair(i))
+        });
+        assert_eq!(envelope.to_base64(), Err(Error::TooManySignatures));
+    }
+}
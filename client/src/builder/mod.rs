@@ -0,0 +1,437 @@
+//! Builds transactions and signs them so they can be submitted to horizon.
+use keypair::Keypair;
+use network::Network;
+use resources::{Account, Ledger, Memo};
+use std::error::Error as StdError;
+use std::fmt;
+use strkey;
+use xdr::{Operation, PublicKey, TimeBounds, Transaction};
+
+mod envelope;
+pub mod operation;
+
+pub use self::envelope::{AccountSigners, Envelope, ThresholdCategory};
+pub use self::operation::BuildOperation;
+
+/// The most operations a single transaction may contain.
+pub const MAX_OPERATIONS: usize = 100;
+
+/// The base fee used when one isn't given. This is the minimum the network accepts.
+pub const DEFAULT_BASE_FEE: u32 = 100;
+
+/// The most signatures a single transaction envelope may carry.
+pub const MAX_SIGNATURES: usize = 20;
+
+/// Errors that occur when building a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The source account was not a valid account id.
+    InvalidSourceAccount(strkey::Error),
+    /// The transaction contained no operations.
+    NoOperations,
+    /// The transaction contained more than 100 operations.
+    TooManyOperations,
+    /// The base fee was negative, or too large once multiplied by the number of
+    /// operations.
+    InvalidFee,
+    /// The sequence number of the source account was too large for another
+    /// transaction to follow it.
+    InvalidSequence,
+    /// The memo was text over 28 bytes.
+    InvalidMemo,
+    /// An account referenced by an operation was not a valid account id.
+    InvalidAccountId(strkey::Error),
+    /// An amount was zero where it must be positive.
+    InvalidAmount,
+    /// An asset code was not one to twelve alphanumeric characters, or did not
+    /// fit the type of the asset.
+    InvalidAsset,
+    /// A price was not positive or did not fit in 32 bits.
+    InvalidPrice,
+    /// A path payment had more than five intermediate assets.
+    InvalidPath,
+    /// A data entry name was empty or over 64 bytes.
+    InvalidDataName,
+    /// A data entry value was over 64 bytes.
+    InvalidDataValue,
+    /// A home domain was over 32 bytes.
+    InvalidHomeDomain,
+    /// A signer was not a valid public key, pre-authorized transaction or hash.
+    InvalidSigner(strkey::Error),
+    /// An envelope could not be decoded from xdr.
+    InvalidEnvelope,
+    /// The envelope carried more than 20 signatures.
+    TooManySignatures,
+    /// The signers of an account used by the transaction were not given.
+    MissingSigners(String),
+    /// The signatures did not meet the threshold an account needs for the
+    /// transaction.
+    InsufficientWeight {
+        /// The account whose threshold was not met.
+        account_id: String,
+        /// The summed weight of the account's signers that signed.
+        weight: u32,
+        /// The threshold of the account for the transaction's operations.
+        threshold: u8,
+    },
+}
+
+/// A result including builder errors.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::InvalidSourceAccount(_) => "The source account is not a valid account id",
+            Error::NoOperations => "A transaction must contain at least one operation",
+            Error::TooManyOperations => "A transaction may contain at most 100 operations",
+            Error::InvalidFee => "The fee must not be negative and must fit in 32 bits",
+            Error::InvalidSequence => "The sequence number can not be incremented",
+            Error::InvalidMemo => "The memo text may be at most 28 bytes",
+            Error::InvalidAccountId(_) => "An account is not a valid account id",
+            Error::InvalidAmount => "The amount must be positive",
+            Error::InvalidAsset => "The asset code is not valid for its type",
+            Error::InvalidPrice => "The price must be positive and fit in 32 bits",
+            Error::InvalidPath => "A path may contain at most 5 assets",
+            Error::InvalidDataName => "The data name must be between 1 and 64 bytes",
+            Error::InvalidDataValue => "The data value may be at most 64 bytes",
+            Error::InvalidHomeDomain => "The home domain may be at most 32 bytes",
+            Error::InvalidSigner(_) => "The signer is not a valid signer key",
+            Error::InvalidEnvelope => "The envelope is not valid xdr",
+            Error::TooManySignatures => "An envelope may contain at most 20 signatures",
+            Error::MissingSigners(_) => "The signers of an account were not given",
+            Error::InsufficientWeight { .. } => {
+                "The signatures do not meet the threshold of an account"
+            }
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Builds a transaction from a source account and a list of operations, then
+/// signs it to produce an envelope that can be submitted with
+/// `endpoint::transaction::Submit`.
+///
+/// The fee is the base fee times the number of operations. The base fee
+/// defaults to 100 stroops, and should be taken from the most recent ledger
+/// when the network is busy.
+///
+/// ## Example
+/// ```
+/// use stellar_client::{Keypair, Network, TransactionBuilder};
+/// use stellar_client::builder::operation::Payment;
+/// use stellar_client::resources::{Amount, AssetIdentifier, Memo};
+///
+/// # fn main() -> Result<(), Box<::std::error::Error>> {
+/// let keypair = Keypair::random()?;
+/// let payment = Payment::new(
+///     &keypair.account_id(),
+///     AssetIdentifier::native(),
+///     Amount::new(10_000_000),
+/// );
+///
+/// let envelope_xdr = TransactionBuilder::new(&keypair.account_id(), 1234)
+///     .with_memo(Memo::text("hello")?)
+///     .with_operation(payment)
+///     .sign(&[&keypair], &Network::test())?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TransactionBuilder {
+    source_account: String,
+    sequence: u64,
+    base_fee: i64,
+    time_bounds: Option<TimeBounds>,
+    memo: Memo,
+    operations: Vec<Result<Operation>>,
+}
+
+impl TransactionBuilder {
+    /// Starts a transaction for the source account. The sequence is the
+    /// account's current sequence number, and the transaction will use the
+    /// one after it.
+    pub fn new(source_account: &str, sequence: u64) -> Self {
+        TransactionBuilder {
+            source_account: source_account.to_string(),
+            sequence,
+            base_fee: i64::from(DEFAULT_BASE_FEE),
+            time_bounds: None,
+            memo: Memo::None,
+            operations: Vec::new(),
+        }
+    }
+
+    /// Starts a transaction for an account fetched from horizon.
+    pub fn for_account(account: &Account) -> Self {
+        Self::new(account.account_id(), account.sequence())
+    }
+
+    /// Sets the fee, in stroops, charged for each operation.
+    pub fn with_base_fee(mut self, base_fee: i64) -> Self {
+        self.base_fee = base_fee;
+        self
+    }
+
+    /// Sets the fee charged for each operation to the base fee of the ledger.
+    pub fn with_ledger_base_fee(self, ledger: &Ledger) -> Self {
+        self.with_base_fee(ledger.base_fee_in_stroops())
+    }
+
+    /// Sets the window, in seconds since the epoch, within which the
+    /// transaction is valid. A max time of zero leaves the window open ended.
+    pub fn with_time_bounds(mut self, min_time: u64, max_time: u64) -> Self {
+        self.time_bounds = Some(TimeBounds { min_time, max_time });
+        self
+    }
+
+    /// Attaches a memo to the transaction.
+    pub fn with_memo(mut self, memo: Memo) -> Self {
+        self.memo = memo;
+        self
+    }
+
+    /// Adds an operation to the end of the transaction. The operation is built
+    /// as it is added, and if it isn't valid the transaction fails to build
+    /// with its error.
+    pub fn with_operation<O>(mut self, operation: O) -> Self
+    where
+        O: BuildOperation,
+    {
+        self.operations.push(operation.build());
+        self
+    }
+
+    /// Adds several operations to the end of the transaction.
+    pub fn with_operations<I>(mut self, operations: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: BuildOperation,
+    {
+        self.operations
+            .extend(operations.into_iter().map(|operation| operation.build()));
+        self
+    }
+
+    /// The fee, in stroops, that the transaction will pay.
+    pub fn fee(&self) -> Result<u32> {
+        if self.base_fee < 0 {
+            return Err(Error::InvalidFee);
+        }
+        let fee = self
+            .base_fee
+            .checked_mul(self.operations.len() as i64)
+            .ok_or(Error::InvalidFee)?;
+        if fee > i64::from(::std::u32::MAX) {
+            return Err(Error::InvalidFee);
+        }
+        Ok(fee as u32)
+    }
+
+    /// Validates the transaction and builds it without signing it.
+    pub fn build(&self) -> Result<Transaction> {
+        if self.operations.is_empty() {
+            return Err(Error::NoOperations);
+        }
+        if self.operations.len() > MAX_OPERATIONS {
+            return Err(Error::TooManyOperations);
+        }
+        let source_account = self
+            .source_account
+            .parse::<PublicKey>()
+            .map_err(Error::InvalidSourceAccount)?;
+        if self.sequence > ::std::i64::MAX as u64 {
+            return Err(Error::InvalidSequence);
+        }
+        let seq_num = (self.sequence as i64)
+            .checked_add(1)
+            .ok_or(Error::InvalidSequence)?;
+        let operations = self.operations.iter().cloned().collect::<Result<_>>()?;
+        Ok(Transaction {
+            source_account,
+            fee: self.fee()?,
+            seq_num,
+            time_bounds: self.time_bounds,
+            memo: self.memo.to_xdr().map_err(|_| Error::InvalidMemo)?,
+            operations,
+        })
+    }
+
+    /// Builds the transaction into an envelope for the network, which can
+    /// collect signatures from several signers.
+    pub fn envelope(&self, network: &Network) -> Result<Envelope> {
+        Ok(Envelope::new(self.build()?, network))
+    }
+
+    /// Builds the transaction and signs it with each of the keypairs for the
+    /// network. Returns the envelope encoded as base64.
+    pub fn sign(&self, keypairs: &[&Keypair], network: &Network) -> Result<String> {
+        keypairs
+            .iter()
+            .fold(self.envelope(network)?, |envelope, keypair| {
+                envelope.sign(keypair)
+            })
+            .to_base64()
+    }
+}
+
+#[cfg(test)]
+mod builder_tests {
+    use super::operation::Payment;
+    use super::*;
+    use resources::{Amount, AssetIdentifier};
+    use xdr::{self, Asset, OperationBody, PaymentOp, TransactionEnvelope};
+
+    fn payment(keypair: &Keypair) -> Operation {
+        Operation {
+            source_account: None,
+            body: OperationBody::Payment(PaymentOp {
+                destination: keypair.public_key(),
+                asset: Asset::Native,
+                amount: 10_000_000,
+            }),
+        }
+    }
+
+    #[test]
+    fn it_builds_a_transaction() {
+        let keypair = Keypair::random().unwrap();
+        let tx = TransactionBuilder::new(&keypair.account_id(), 1234)
+            .with_time_bounds(100, 200)
+            .with_memo(Memo::Id(42))
+            .with_operations(vec![payment(&keypair), payment(&keypair)])
+            .build()
+            .unwrap();
+        assert_eq!(tx.source_account, keypair.public_key());
+        assert_eq!(tx.seq_num, 1235);
+        assert_eq!(tx.fee, 200);
+        assert_eq!(
+            tx.time_bounds,
+            Some(TimeBounds {
+                min_time: 100,
+                max_time: 200,
+            })
+        );
+        assert_eq!(tx.memo, xdr::Memo::Id(42));
+        assert_eq!(tx.operations.len(), 2);
+    }
+
+    #[test]
+    fn it_charges_the_base_fee_for_each_operation() {
+        let keypair = Keypair::random().unwrap();
+        let builder = TransactionBuilder::new(&keypair.account_id(), 0)
+            .with_base_fee(250)
+            .with_operations(vec![payment(&keypair); 3]);
+        assert_eq!(builder.fee(), Ok(750));
+        assert_eq!(builder.with_base_fee(-1).fee(), Err(Error::InvalidFee));
+    }
+
+    #[test]
+    fn it_enforces_the_operation_limits() {
+        let keypair = Keypair::random().unwrap();
+        let builder = TransactionBuilder::new(&keypair.account_id(), 0);
+        assert_eq!(builder.build(), Err(Error::NoOperations));
+
+        let builder = builder.with_operations(vec![payment(&keypair); MAX_OPERATIONS]);
+        assert!(builder.build().is_ok());
+        assert_eq!(
+            builder.with_operation(payment(&keypair)).build(),
+            Err(Error::TooManyOperations)
+        );
+    }
+
+    #[test]
+    fn it_rejects_an_invalid_source_account() {
+        let keypair = Keypair::random().unwrap();
+        let result = TransactionBuilder::new("abc123", 0)
+            .with_operation(payment(&keypair))
+            .build();
+        assert_eq!(
+            result,
+            Err(Error::InvalidSourceAccount(strkey::Error::InvalidLength))
+        );
+    }
+
+    #[test]
+    fn it_rejects_sequence_numbers_that_can_not_be_incremented() {
+        let keypair = Keypair::random().unwrap();
+        let build = |sequence| {
+            TransactionBuilder::new(&keypair.account_id(), sequence)
+                .with_operation(payment(&keypair))
+                .build()
+                .map(|tx| tx.seq_num)
+        };
+        assert_eq!(build(::std::i64::MAX as u64 - 1), Ok(::std::i64::MAX));
+        assert_eq!(build(::std::i64::MAX as u64), Err(Error::InvalidSequence));
+        assert_eq!(build(::std::u64::MAX), Err(Error::InvalidSequence));
+    }
+
+    #[test]
+    fn it_builds_operations_as_they_are_added() {
+        let keypair = Keypair::random().unwrap();
+        let builder = TransactionBuilder::new(&keypair.account_id(), 0);
+        let amount = |stroops| {
+            Payment::new(
+                &keypair.account_id(),
+                AssetIdentifier::native(),
+                Amount::new(stroops),
+            )
+        };
+        assert_eq!(
+            builder
+                .clone()
+                .with_operation(amount(1))
+                .build()
+                .unwrap()
+                .operations,
+            vec![amount(1).build().unwrap()]
+        );
+        assert_eq!(
+            builder.with_operation(amount(0)).build(),
+            Err(Error::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn it_converts_memos() {
+        let keypair = Keypair::random().unwrap();
+        let builder = TransactionBuilder::new(&keypair.account_id(), 0)
+            .with_operation(payment(&keypair))
+            .with_memo(Memo::Id(::std::u64::MAX));
+        assert_eq!(
+            builder.build().unwrap().memo,
+            xdr::Memo::Id(::std::u64::MAX)
+        );
+        assert_eq!(
+            builder
+                .with_memo(Memo::Text("this memo is far too long to fit".to_string()))
+                .build(),
+            Err(Error::InvalidMemo)
+        );
+    }
+
+    #[test]
+    fn it_signs_the_transaction() {
+        let keypair = Keypair::random().unwrap();
+        let other = Keypair::random().unwrap();
+        let builder =
+            TransactionBuilder::new(&keypair.account_id(), 0).with_operation(payment(&other));
+        let encoded = builder.sign(&[&keypair, &other], &Network::test()).unwrap();
+
+        let envelope: TransactionEnvelope = xdr::from_base64(&encoded).unwrap();
+        let hash = Network::test().hash_transaction(&envelope.tx);
+        assert_eq!(envelope.signatures.len(), 2);
+        assert_eq!(envelope.signatures[0].hint, keypair.signature_hint());
+        assert!(keypair.verify(&hash, &envelope.signatures[0].signature));
+        assert!(other.verify(&hash, &envelope.signatures[1].signature));
+        assert!(!keypair.verify(
+            &Network::public().hash_transaction(&envelope.tx),
+            &envelope.signatures[0].signature,
+        ));
+    }
+}
@@ -0,0 +1,859 @@
+//! Operations that can be added to a transaction.
+//!
+//! Each operation is created from the same types that horizon returns, such as
+//! `Amount` and `AssetIdentifier`, and is validated when it is built into xdr
+//! with `BuildOperation`. A `TransactionBuilder` builds each operation as it is
+//! added. Operations apply to the source account of the transaction unless they
+//! are given a source account of their own.
+//!
+//! ## Example
+//! ```
+//! use stellar_client::builder::operation::{BuildOperation, ChangeTrust, Payment};
+//! use stellar_client::resources::{Amount, AssetIdentifier};
+//!
+//! let issuer = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+//! let usd = AssetIdentifier::alphanum4("USD", issuer);
+//!
+//! let trust = ChangeTrust::new(usd.clone(), Amount::new(1_000_000_000))
+//!     .build()
+//!     .unwrap();
+//! let payment = Payment::new(issuer, usd, Amount::new(10_000_000))
+//!     .with_source_account(issuer)
+//!     .build()
+//!     .unwrap();
+//! assert!(payment.source_account.is_some());
+//!
+//! let empty = Payment::new(issuer, AssetIdentifier::native(), Amount::new(0));
+//! assert!(empty.build().is_err());
+//! ```
+use super::{Error, Result};
+use resources::{Amount, AssetIdentifier, Flags, PriceRatio};
+use xdr::{
+    self, AccountId, AllowTrustAsset, AllowTrustOp, Asset, ChangeTrustOp, CreateAccountOp,
+    CreatePassiveOfferOp, ManageDataOp, ManageOfferOp, OperationBody, PathPaymentOp, PaymentOp,
+    Price, PublicKey, SetOptionsOp, Signer, SignerKey,
+};
+
+/// The most intermediate assets a path payment may convert through.
+pub const MAX_PATH_LENGTH: usize = 5;
+
+/// The longest a data entry name, or value, may be in bytes.
+pub const MAX_DATA_LENGTH: usize = 64;
+
+/// The longest a home domain may be in bytes.
+pub const MAX_HOME_DOMAIN_LENGTH: usize = 32;
+
+const AUTH_REQUIRED_FLAG: u32 = 0x1;
+const AUTH_REVOCABLE_FLAG: u32 = 0x2;
+
+/// An operation that can be added to a transaction.
+pub trait BuildOperation {
+    /// Validates the operation and builds it into xdr.
+    fn build(&self) -> Result<xdr::Operation>;
+}
+
+/// Operations that are already xdr, such as those decoded from an envelope,
+/// are added as they are.
+impl BuildOperation for xdr::Operation {
+    fn build(&self) -> Result<xdr::Operation> {
+        Ok(self.clone())
+    }
+}
+
+/// Implements the source account shared by every operation.
+macro_rules! source_account {
+    ($name:ident) => {
+        impl $name {
+            /// Sets the account the operation applies to, in place of the source
+            /// account of the transaction.
+            pub fn with_source_account(mut self, source_account: &str) -> Self {
+                self.source_account = Some(source_account.to_string());
+                self
+            }
+        }
+    };
+}
+
+/// Creates and funds a new account with lumens.
+#[derive(Debug, Clone)]
+pub struct CreateAccount {
+    source_account: Option<String>,
+    destination: String,
+    starting_balance: Amount,
+}
+
+source_account!(CreateAccount);
+
+impl CreateAccount {
+    /// Creates the destination account with a starting balance.
+    pub fn new(destination: &str, starting_balance: Amount) -> Self {
+        CreateAccount {
+            source_account: None,
+            destination: destination.to_string(),
+            starting_balance,
+        }
+    }
+}
+
+impl BuildOperation for CreateAccount {
+    fn build(&self) -> Result<xdr::Operation> {
+        operation(
+            &self.source_account,
+            OperationBody::CreateAccount(CreateAccountOp {
+                destination: account_id(&self.destination)?,
+                starting_balance: positive(self.starting_balance)?,
+            }),
+        )
+    }
+}
+
+/// Sends an amount of an asset to an account.
+#[derive(Debug, Clone)]
+pub struct Payment {
+    source_account: Option<String>,
+    destination: String,
+    asset: AssetIdentifier,
+    amount: Amount,
+}
+
+source_account!(Payment);
+
+impl Payment {
+    /// Sends the amount of the asset to the destination account.
+    pub fn new(destination: &str, asset: AssetIdentifier, amount: Amount) -> Self {
+        Payment {
+            source_account: None,
+            destination: destination.to_string(),
+            asset,
+            amount,
+        }
+    }
+}
+
+impl BuildOperation for Payment {
+    fn build(&self) -> Result<xdr::Operation> {
+        operation(
+            &self.source_account,
+            OperationBody::Payment(PaymentOp {
+                destination: account_id(&self.destination)?,
+                asset: asset(&self.asset)?,
+                amount: positive(self.amount)?,
+            }),
+        )
+    }
+}
+
+/// Sends an asset to an account, which receives a different asset after the
+/// payment is converted through the order books.
+#[derive(Debug, Clone)]
+pub struct PathPayment {
+    source_account: Option<String>,
+    destination: String,
+    send_asset: AssetIdentifier,
+    send_max: Amount,
+    destination_asset: AssetIdentifier,
+    destination_amount: Amount,
+    path: Vec<AssetIdentifier>,
+}
+
+source_account!(PathPayment);
+
+impl PathPayment {
+    /// Sends at most `send_max` of the send asset so that the destination
+    /// receives exactly the destination amount of the destination asset.
+    pub fn new(
+        destination: &str,
+        send_asset: AssetIdentifier,
+        send_max: Amount,
+        destination_asset: AssetIdentifier,
+        destination_amount: Amount,
+    ) -> Self {
+        PathPayment {
+            source_account: None,
+            destination: destination.to_string(),
+            send_asset,
+            send_max,
+            destination_asset,
+            destination_amount,
+            path: Vec::new(),
+        }
+    }
+
+    /// Sets the intermediate assets the payment converts through, such as a
+    /// path found with `endpoint::payment::FindPath`.
+    pub fn with_path(mut self, path: Vec<AssetIdentifier>) -> Self {
+        self.path = path;
+        self
+    }
+}
+
+impl BuildOperation for PathPayment {
+    fn build(&self) -> Result<xdr::Operation> {
+        if self.path.len() > MAX_PATH_LENGTH {
+            return Err(Error::InvalidPath);
+        }
+        operation(
+            &self.source_account,
+            OperationBody::PathPayment(PathPaymentOp {
+                send_asset: asset(&self.send_asset)?,
+                send_max: positive(self.send_max)?,
+                destination: account_id(&self.destination)?,
+                dest_asset: asset(&self.destination_asset)?,
+                dest_amount: positive(self.destination_amount)?,
+                path: self.path.iter().map(asset).collect::<Result<_>>()?,
+            }),
+        )
+    }
+}
+
+/// Creates, updates or deletes an offer to sell one asset for another.
+#[derive(Debug, Clone)]
+pub struct ManageOffer {
+    source_account: Option<String>,
+    selling: AssetIdentifier,
+    buying: AssetIdentifier,
+    amount: Amount,
+    price: PriceRatio,
+    offer_id: u64,
+}
+
+source_account!(ManageOffer);
+
+impl ManageOffer {
+    /// Creates an offer to sell the amount of the selling asset, with the
+    /// price being how many units of buying one unit of selling costs.
+    pub fn new(
+        selling: AssetIdentifier,
+        buying: AssetIdentifier,
+        amount: Amount,
+        price: PriceRatio,
+    ) -> Self {
+        ManageOffer {
+            source_account: None,
+            selling,
+            buying,
+            amount,
+            price,
+            offer_id: 0,
+        }
+    }
+
+    /// Updates an existing offer rather than creating a new one. An amount of
+    /// zero deletes the offer.
+    pub fn with_offer_id(mut self, offer_id: u64) -> Self {
+        self.offer_id = offer_id;
+        self
+    }
+}
+
+impl BuildOperation for ManageOffer {
+    fn build(&self) -> Result<xdr::Operation> {
+        operation(
+            &self.source_account,
+            OperationBody::ManageOffer(ManageOfferOp {
+                selling: asset(&self.selling)?,
+                buying: asset(&self.buying)?,
+                amount: self.amount.stroops(),
+                price: price(self.price)?,
+                offer_id: self.offer_id,
+            }),
+        )
+    }
+}
+
+/// Creates an offer that doesn't take offers at exactly the same price.
+#[derive(Debug, Clone)]
+pub struct CreatePassiveOffer {
+    source_account: Option<String>,
+    selling: AssetIdentifier,
+    buying: AssetIdentifier,
+    amount: Amount,
+    price: PriceRatio,
+}
+
+source_account!(CreatePassiveOffer);
+
+impl CreatePassiveOffer {
+    /// Creates a passive offer to sell the amount of the selling asset, with
+    /// the price being how many units of buying one unit of selling costs.
+    pub fn new(
+        selling: AssetIdentifier,
+        buying: AssetIdentifier,
+        amount: Amount,
+        price: PriceRatio,
+    ) -> Self {
+        CreatePassiveOffer {
+            source_account: None,
+            selling,
+            buying,
+            amount,
+            price,
+        }
+    }
+}
+
+impl BuildOperation for CreatePassiveOffer {
+    fn build(&self) -> Result<xdr::Operation> {
+        operation(
+            &self.source_account,
+            OperationBody::CreatePassiveOffer(CreatePassiveOfferOp {
+                selling: asset(&self.selling)?,
+                buying: asset(&self.buying)?,
+                amount: positive(self.amount)?,
+                price: price(self.price)?,
+            }),
+        )
+    }
+}
+
+/// Sets the options of an account. Only the options that are given change.
+#[derive(Debug, Clone, Default)]
+pub struct SetOptions {
+    source_account: Option<String>,
+    inflation_destination: Option<String>,
+    set_flags: Option<Flags>,
+    clear_flags: Option<Flags>,
+    master_weight: Option<u8>,
+    thresholds: Option<(u8, u8, u8)>,
+    home_domain: Option<String>,
+    signer: Option<(String, u8)>,
+}
+
+source_account!(SetOptions);
+
+impl SetOptions {
+    /// Creates an operation that changes nothing until options are added.
+    pub fn new() -> Self {
+        SetOptions::default()
+    }
+
+    /// Sets the account that the account's inflation votes go to.
+    pub fn with_inflation_destination(mut self, account_id: &str) -> Self {
+        self.inflation_destination = Some(account_id.to_string());
+        self
+    }
+
+    /// Sets the flags that are true.
+    pub fn with_set_flags(mut self, flags: Flags) -> Self {
+        self.set_flags = Some(flags);
+        self
+    }
+
+    /// Clears the flags that are true.
+    pub fn with_clear_flags(mut self, flags: Flags) -> Self {
+        self.clear_flags = Some(flags);
+        self
+    }
+
+    /// Sets the weight of the master key. A weight of zero disables it.
+    pub fn with_master_weight(mut self, weight: u8) -> Self {
+        self.master_weight = Some(weight);
+        self
+    }
+
+    /// Sets the low, medium and high thresholds.
+    pub fn with_thresholds(mut self, low: u8, med: u8, high: u8) -> Self {
+        self.thresholds = Some((low, med, high));
+        self
+    }
+
+    /// Sets the home domain of the account.
+    pub fn with_home_domain(mut self, home_domain: &str) -> Self {
+        self.home_domain = Some(home_domain.to_string());
+        self
+    }
+
+    /// Adds or updates a signer, given as a strkey. A weight of zero removes
+    /// the signer.
+    pub fn with_signer(mut self, key: &str, weight: u8) -> Self {
+        self.signer = Some((key.to_string(), weight));
+        self
+    }
+}
+
+impl BuildOperation for SetOptions {
+    fn build(&self) -> Result<xdr::Operation> {
+        if let Some(ref home_domain) = self.home_domain {
+            if home_domain.len() > MAX_HOME_DOMAIN_LENGTH {
+                return Err(Error::InvalidHomeDomain);
+            }
+        }
+        let inflation_dest = match self.inflation_destination {
+            Some(ref destination) => Some(account_id(destination)?),
+            None => None,
+        };
+        let signer = match self.signer {
+            Some((ref key, weight)) => Some(Signer {
+                key: key.parse::<SignerKey>().map_err(Error::InvalidSigner)?,
+                weight: u32::from(weight),
+            }),
+            None => None,
+        };
+        operation(
+            &self.source_account,
+            OperationBody::SetOptions(SetOptionsOp {
+                inflation_dest,
+                clear_flags: self.clear_flags.map(flag_bits),
+                set_flags: self.set_flags.map(flag_bits),
+                master_weight: self.master_weight.map(u32::from),
+                low_threshold: self.thresholds.map(|(low, _, _)| u32::from(low)),
+                med_threshold: self.thresholds.map(|(_, med, _)| u32::from(med)),
+                high_threshold: self.thresholds.map(|(_, _, high)| u32::from(high)),
+                home_domain: self
+                    .home_domain
+                    .as_ref()
+                    .map(|domain| domain.as_bytes().to_vec()),
+                signer,
+            }),
+        )
+    }
+}
+
+/// Creates, updates or deletes a trustline to an asset.
+#[derive(Debug, Clone)]
+pub struct ChangeTrust {
+    source_account: Option<String>,
+    asset: AssetIdentifier,
+    limit: Amount,
+}
+
+source_account!(ChangeTrust);
+
+impl ChangeTrust {
+    /// Trusts the asset up to the limit. A limit of zero deletes the
+    /// trustline.
+    pub fn new(asset: AssetIdentifier, limit: Amount) -> Self {
+        ChangeTrust {
+            source_account: None,
+            asset,
+            limit,
+        }
+    }
+}
+
+impl BuildOperation for ChangeTrust {
+    fn build(&self) -> Result<xdr::Operation> {
+        if self.asset.is_native() {
+            return Err(Error::InvalidAsset);
+        }
+        operation(
+            &self.source_account,
+            OperationBody::ChangeTrust(ChangeTrustOp {
+                line: asset(&self.asset)?,
+                limit: self.limit.stroops(),
+            }),
+        )
+    }
+}
+
+/// Authorizes, or revokes, another account's trustline to an asset issued by
+/// the source account.
+#[derive(Debug, Clone)]
+pub struct AllowTrust {
+    source_account: Option<String>,
+    trustor: String,
+    asset_code: String,
+    authorize: bool,
+}
+
+source_account!(AllowTrust);
+
+impl AllowTrust {
+    /// Authorizes, or revokes, the trustor's trustline to the asset with the
+    /// code.
+    pub fn new(trustor: &str, asset_code: &str, authorize: bool) -> Self {
+        AllowTrust {
+            source_account: None,
+            trustor: trustor.to_string(),
+            asset_code: asset_code.to_string(),
+            authorize,
+        }
+    }
+}
+
+impl BuildOperation for AllowTrust {
+    fn build(&self) -> Result<xdr::Operation> {
+        let asset = if self.asset_code.len() <= 4 {
+            AllowTrustAsset::AlphaNum4(asset_code(&self.asset_code, 1, 4)?)
+        } else {
+            AllowTrustAsset::AlphaNum12(asset_code(&self.asset_code, 5, 12)?)
+        };
+        operation(
+            &self.source_account,
+            OperationBody::AllowTrust(AllowTrustOp {
+                trustor: account_id(&self.trustor)?,
+                asset,
+                authorize: self.authorize,
+            }),
+        )
+    }
+}
+
+/// Merges the source account into another, sending it all of its lumens.
+#[derive(Debug, Clone)]
+pub struct AccountMerge {
+    source_account: Option<String>,
+    destination: String,
+}
+
+source_account!(AccountMerge);
+
+impl AccountMerge {
+    /// Merges the source account into the destination account.
+    pub fn new(destination: &str) -> Self {
+        AccountMerge {
+            source_account: None,
+            destination: destination.to_string(),
+        }
+    }
+}
+
+impl BuildOperation for AccountMerge {
+    fn build(&self) -> Result<xdr::Operation> {
+        operation(
+            &self.source_account,
+            OperationBody::AccountMerge(account_id(&self.destination)?),
+        )
+    }
+}
+
+/// Runs inflation, paying out to the accounts that received enough votes.
+#[derive(Debug, Clone, Default)]
+pub struct Inflation {
+    source_account: Option<String>,
+}
+
+source_account!(Inflation);
+
+impl Inflation {
+    /// Creates an inflation operation.
+    pub fn new() -> Self {
+        Inflation::default()
+    }
+}
+
+impl BuildOperation for Inflation {
+    fn build(&self) -> Result<xdr::Operation> {
+        operation(&self.source_account, OperationBody::Inflation)
+    }
+}
+
+/// Sets, or deletes, a key value pair on an account.
+#[derive(Debug, Clone)]
+pub struct ManageData {
+    source_account: Option<String>,
+    name: String,
+    value: Option<Vec<u8>>,
+}
+
+source_account!(ManageData);
+
+impl ManageData {
+    /// Sets the value of the named entry.
+    pub fn new(name: &str, value: &[u8]) -> Self {
+        ManageData {
+            source_account: None,
+            name: name.to_string(),
+            value: Some(value.to_vec()),
+        }
+    }
+
+    /// Deletes the named entry.
+    pub fn delete(name: &str) -> Self {
+        ManageData {
+            source_account: None,
+            name: name.to_string(),
+            value: None,
+        }
+    }
+}
+
+impl BuildOperation for ManageData {
+    fn build(&self) -> Result<xdr::Operation> {
+        if self.name.is_empty() || self.name.len() > MAX_DATA_LENGTH {
+            return Err(Error::InvalidDataName);
+        }
+        if let Some(ref value) = self.value {
+            if value.len() > MAX_DATA_LENGTH {
+                return Err(Error::InvalidDataValue);
+            }
+        }
+        operation(
+            &self.source_account,
+            OperationBody::ManageData(ManageDataOp {
+                data_name: self.name.as_bytes().to_vec(),
+                data_value: self.value.clone(),
+            }),
+        )
+    }
+}
+
+fn operation(source_account: &Option<String>, body: OperationBody) -> Result<xdr::Operation> {
+    let source_account = match *source_account {
+        Some(ref source_account) => Some(
+            source_account
+                .parse::<PublicKey>()
+                .map_err(Error::InvalidSourceAccount)?,
+        ),
+        None => None,
+    };
+    Ok(xdr::Operation {
+        source_account,
+        body,
+    })
+}
+
+fn account_id(account_id: &str) -> Result<AccountId> {
+    account_id.parse().map_err(Error::InvalidAccountId)
+}
+
+fn positive(amount: Amount) -> Result<i64> {
+    if amount.stroops() > 0 {
+        Ok(amount.stroops())
+    } else {
+        Err(Error::InvalidAmount)
+    }
+}
+
+fn price(price: PriceRatio) -> Result<Price> {
+    let max = ::std::i32::MAX as u64;
+    let (n, d) = (price.numerator(), price.denominator());
+    if n == 0 || d == 0 || n > max || d > max {
+        return Err(Error::InvalidPrice);
+    }
+    Ok(Price {
+        n: n as i32,
+        d: d as i32,
+    })
+}
+
+fn asset(identifier: &AssetIdentifier) -> Result<Asset> {
+    Ok(match *identifier {
+        AssetIdentifier::Native => Asset::Native,
+        AssetIdentifier::CreditAlphanum4(_) => Asset::AlphaNum4 {
+            code: asset_code(identifier.code(), 1, 4)?,
+            issuer: account_id(identifier.issuer())?,
+        },
+        AssetIdentifier::CreditAlphanum12(_) => Asset::AlphaNum12 {
+            code: asset_code(identifier.code(), 5, 12)?,
+            issuer: account_id(identifier.issuer())?,
+        },
+    })
+}
+
+/// Pads the code with zeros to fill the array, checking that it is
+/// alphanumeric and within the lengths allowed for the array.
+fn asset_code<T: AsMut<[u8]> + Default>(code: &str, min: usize, max: usize) -> Result<T> {
+    if code.len() < min || code.len() > max || !code.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(Error::InvalidAsset);
+    }
+    let mut padded = T::default();
+    padded.as_mut()[..code.len()].copy_from_slice(code.as_bytes());
+    Ok(padded)
+}
+
+fn flag_bits(flags: Flags) -> u32 {
+    let mut bits = 0;
+    if flags.is_auth_required() {
+        bits |= AUTH_REQUIRED_FLAG;
+    }
+    if flags.is_auth_revocable() {
+        bits |= AUTH_REVOCABLE_FLAG;
+    }
+    bits
+}
+
+#[cfg(test)]
+mod operation_tests {
+    use super::*;
+    use strkey;
+
+    static ACCOUNT_ID: &str = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+
+    fn public_key() -> PublicKey {
+        ACCOUNT_ID.parse().unwrap()
+    }
+
+    fn usd() -> AssetIdentifier {
+        AssetIdentifier::alphanum4("USD", ACCOUNT_ID)
+    }
+
+    #[test]
+    fn it_builds_a_payment() {
+        let op = Payment::new(ACCOUNT_ID, usd(), Amount::new(10_000_000))
+            .build()
+            .unwrap();
+        assert_eq!(op.source_account, None);
+        assert_eq!(
+            op.body,
+            OperationBody::Payment(PaymentOp {
+                destination: public_key(),
+                asset: Asset::AlphaNum4 {
+                    code: *b"USD\0",
+                    issuer: public_key(),
+                },
+                amount: 10_000_000,
+            })
+        );
+    }
+
+    #[test]
+    fn it_sets_the_source_account() {
+        let op = Inflation::new()
+            .with_source_account(ACCOUNT_ID)
+            .build()
+            .unwrap();
+        assert_eq!(op.source_account, Some(public_key()));
+        assert_eq!(op.body, OperationBody::Inflation);
+
+        let result = Inflation::new().with_source_account("abc123").build();
+        assert_eq!(
+            result,
+            Err(Error::InvalidSourceAccount(strkey::Error::InvalidLength))
+        );
+    }
+
+    #[test]
+    fn it_requires_positive_amounts() {
+        let result = CreateAccount::new(ACCOUNT_ID, Amount::new(0)).build();
+        assert_eq!(result, Err(Error::InvalidAmount));
+        let result = PathPayment::new(
+            ACCOUNT_ID,
+            AssetIdentifier::native(),
+            Amount::new(0),
+            usd(),
+            Amount::new(1),
+        )
+        .build();
+        assert_eq!(result, Err(Error::InvalidAmount));
+
+        // Zero amounts delete offers and trustlines.
+        let price = PriceRatio::new(1, 2);
+        assert!(
+            ManageOffer::new(usd(), AssetIdentifier::native(), Amount::new(0), price)
+                .with_offer_id(12)
+                .build()
+                .is_ok()
+        );
+        assert!(ChangeTrust::new(usd(), Amount::new(0)).build().is_ok());
+    }
+
+    #[test]
+    fn it_validates_asset_codes() {
+        let build = |asset| Payment::new(ACCOUNT_ID, asset, Amount::new(1)).build();
+        assert!(build(AssetIdentifier::alphanum12("DOLLARS", ACCOUNT_ID)).is_ok());
+        assert_eq!(
+            build(AssetIdentifier::alphanum4("DOLLARS", ACCOUNT_ID)),
+            Err(Error::InvalidAsset)
+        );
+        assert_eq!(
+            build(AssetIdentifier::alphanum12("USD", ACCOUNT_ID)),
+            Err(Error::InvalidAsset)
+        );
+        assert_eq!(
+            build(AssetIdentifier::alphanum4("U$D", ACCOUNT_ID)),
+            Err(Error::InvalidAsset)
+        );
+        assert_eq!(
+            build(AssetIdentifier::alphanum4("", ACCOUNT_ID)),
+            Err(Error::InvalidAsset)
+        );
+        assert_eq!(
+            build(AssetIdentifier::alphanum4("USD", "abc123")),
+            Err(Error::InvalidAccountId(strkey::Error::InvalidLength))
+        );
+        assert_eq!(
+            ChangeTrust::new(AssetIdentifier::native(), Amount::new(1)).build(),
+            Err(Error::InvalidAsset)
+        );
+    }
+
+    #[test]
+    fn it_validates_prices_and_paths() {
+        let offer = |price| {
+            CreatePassiveOffer::new(usd(), AssetIdentifier::native(), Amount::new(1), price).build()
+        };
+        assert!(offer(PriceRatio::new(3, 4)).is_ok());
+        assert_eq!(offer(PriceRatio::new(0, 4)), Err(Error::InvalidPrice));
+        assert_eq!(offer(PriceRatio::new(1 << 31, 1)), Err(Error::InvalidPrice));
+
+        let payment = PathPayment::new(
+            ACCOUNT_ID,
+            AssetIdentifier::native(),
+            Amount::new(1),
+            usd(),
+            Amount::new(1),
+        );
+        assert!(payment.clone().with_path(vec![usd(); 5]).build().is_ok());
+        assert_eq!(
+            payment.with_path(vec![usd(); 6]).build(),
+            Err(Error::InvalidPath)
+        );
+    }
+
+    #[test]
+    fn it_validates_data_entries() {
+        let op = ManageData::new("name", b"value").build().unwrap();
+        assert_eq!(
+            op.body,
+            OperationBody::ManageData(ManageDataOp {
+                data_name: b"name".to_vec(),
+                data_value: Some(b"value".to_vec()),
+            })
+        );
+        assert!(ManageData::delete(&"a".repeat(64)).build().is_ok());
+        assert_eq!(
+            ManageData::delete(&"a".repeat(65)).build(),
+            Err(Error::InvalidDataName)
+        );
+        assert_eq!(ManageData::delete("").build(), Err(Error::InvalidDataName));
+        assert_eq!(
+            ManageData::new("name", &[0; 65]).build(),
+            Err(Error::InvalidDataValue)
+        );
+    }
+
+    #[test]
+    fn it_builds_set_options() {
+        let op = SetOptions::new()
+            .with_set_flags(Flags::new(true, true))
+            .with_thresholds(1, 2, 3)
+            .with_home_domain("stellar.org")
+            .with_signer(ACCOUNT_ID, 5)
+            .build()
+            .unwrap();
+        match op.body {
+            OperationBody::SetOptions(ref options) => {
+                assert_eq!(options.set_flags, Some(3));
+                assert_eq!(options.clear_flags, None);
+                assert_eq!(options.master_weight, None);
+                assert_eq!(options.med_threshold, Some(2));
+                assert_eq!(options.home_domain, Some(b"stellar.org".to_vec()));
+                assert_eq!(options.signer.unwrap().weight, 5);
+            }
+            _ => panic!("Expected set options"),
+        }
+        assert_eq!(
+            SetOptions::new().with_home_domain(&"a".repeat(33)).build(),
+            Err(Error::InvalidHomeDomain)
+        );
+        assert!(SetOptions::new().with_signer("abc123", 1).build().is_err());
+    }
+
+    #[test]
+    fn it_builds_allow_trust_and_account_merge() {
+        let op = AllowTrust::new(ACCOUNT_ID, "DOLLARS", true)
+            .build()
+            .unwrap();
+        assert_eq!(
+            op.body,
+            OperationBody::AllowTrust(AllowTrustOp {
+                trustor: public_key(),
+                asset: AllowTrustAsset::AlphaNum12(*b"DOLLARS\0\0\0\0\0"),
+                authorize: true,
+            })
+        );
+        let op = AccountMerge::new(ACCOUNT_ID).build().unwrap();
+        assert_eq!(op.body, OperationBody::AccountMerge(public_key()));
+    }
+}
@@ -0,0 +1,180 @@
+use futures::{
+    future::{self, Loop},
+    Future,
+};
+use hyper::client::{HttpConnector, Service};
+use hyper::Uri;
+use std::io;
+use std::time::Duration;
+use tokio_core::net::TcpStream;
+use tokio_core::reactor::{Handle, Timeout};
+use tokio_io;
+
+type Connecting = Box<Future<Item = TcpStream, Error = io::Error>>;
+
+/// Connects to horizon, or to the proxy when there is one, and gives up once
+/// the connect timeout passes. Connections to https servers through the
+/// proxy are tunneled with `CONNECT`, so that tls is negotiated with horizon.
+#[derive(Debug, Clone)]
+pub(crate) struct Connector {
+    http: HttpConnector,
+    proxy: Option<Uri>,
+    timeout: Option<Duration>,
+    handle: Handle,
+}
+
+impl Connector {
+    pub(crate) fn new(
+        mut http: HttpConnector,
+        proxy: Option<Uri>,
+        timeout: Option<Duration>,
+        handle: &Handle,
+    ) -> Self {
+        http.enforce_http(false);
+        Connector {
+            http,
+            proxy,
+            timeout,
+            handle: handle.clone(),
+        }
+    }
+
+    fn connect(&self, uri: Uri) -> Connecting {
+        match self.proxy {
+            Some(ref proxy) if uri.scheme() == Some("https") => {
+                let authority = format!(
+                    "{}:{}",
+                    uri.host().unwrap_or_default(),
+                    uri.port().unwrap_or(443)
+                );
+                Box::new(
+                    self.http
+                        .call(proxy.clone())
+                        .and_then(move |stream| tunnel(stream, authority)),
+                )
+            }
+            Some(ref proxy) => Box::new(self.http.call(proxy.clone())),
+            None => Box::new(self.http.call(uri)),
+        }
+    }
+}
+
+impl Service for Connector {
+    type Request = Uri;
+    type Response = TcpStream;
+    type Error = io::Error;
+    type Future = Connecting;
+
+    fn call(&self, uri: Uri) -> Connecting {
+        let connecting = self.connect(uri);
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return connecting,
+        };
+        let timed_out = match Timeout::new(timeout, &self.handle) {
+            Ok(timed_out) => timed_out.and_then(|_| -> io::Result<TcpStream> {
+                Err(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "Timed out connecting to horizon",
+                ))
+            }),
+            Err(err) => return Box::new(future::err(err)),
+        };
+        let connecting = connecting
+            .select(timed_out)
+            .map(|(stream, _)| stream)
+            .map_err(|(err, _)| err);
+        Box::new(connecting)
+    }
+}
+
+/// Asks the proxy to open a tunnel to the authority and resolves to the
+/// stream once it has.
+fn tunnel(stream: TcpStream, authority: String) -> Connecting {
+    let connect = format!("CONNECT {0} HTTP/1.1\r\nHost: {0}\r\n\r\n", authority);
+    let tunnel = tokio_io::io::write_all(stream, connect.into_bytes()).and_then(|(stream, _)| {
+        future::loop_fn((stream, Vec::new()), |(stream, mut response)| {
+            tokio_io::io::read(stream, vec![0; 256]).and_then(move |(stream, buf, read)| {
+                if read == 0 {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "The proxy closed the connection",
+                    ));
+                }
+                response.extend_from_slice(&buf[..read]);
+                if !response.windows(4).any(|window| window == b"\r\n\r\n") {
+                    Ok(Loop::Continue((stream, response)))
+                } else if is_established(&response) {
+                    Ok(Loop::Break(stream))
+                } else {
+                    Err(io::Error::new(
+                        io::ErrorKind::Other,
+                        "The proxy refused to open a tunnel",
+                    ))
+                }
+            })
+        })
+    });
+    Box::new(tunnel)
+}
+
+/// Whether the proxy answered the `CONNECT` with a successful status.
+fn is_established(response: &[u8]) -> bool {
+    response.starts_with(b"HTTP/1.1 2") || response.starts_with(b"HTTP/1.0 2")
+}
+
+#[cfg(test)]
+mod connector_tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+    use tokio_core::reactor::Core;
+
+    /// A proxy that answers a single `CONNECT` with the status line and sends
+    /// back the request it received.
+    fn proxy(status: &'static str) -> (Uri, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let uri = format!("http://{}", listener.local_addr().unwrap());
+        let accepted = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0; 256];
+            while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                let read = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..read]);
+            }
+            stream.write_all(status.as_bytes()).unwrap();
+            String::from_utf8(request).unwrap()
+        });
+        (uri.parse().unwrap(), accepted)
+    }
+
+    fn connector(core: &Core, proxy: Option<Uri>) -> Connector {
+        let handle = core.handle();
+        let http = HttpConnector::new(1, &handle);
+        Connector::new(http, proxy, Some(Duration::from_secs(5)), &handle)
+    }
+
+    #[test]
+    fn it_tunnels_https_through_the_proxy() {
+        let mut core = Core::new().unwrap();
+        let (uri, accepted) = proxy("HTTP/1.1 200 Connection established\r\n\r\n");
+        let connector = connector(&core, Some(uri));
+        let connecting = connector.call("https://horizon.stellar.org".parse().unwrap());
+        core.run(connecting).unwrap();
+        assert_eq!(
+            accepted.join().unwrap(),
+            "CONNECT horizon.stellar.org:443 HTTP/1.1\r\nHost: horizon.stellar.org:443\r\n\r\n"
+        );
+    }
+
+    #[test]
+    fn it_fails_when_the_proxy_refuses_the_tunnel() {
+        let mut core = Core::new().unwrap();
+        let (uri, _) = proxy("HTTP/1.1 407 Proxy Authentication Required\r\n\r\n");
+        let connector = connector(&core, Some(uri));
+        let connecting = connector.call("https://horizon.stellar.org:8000".parse().unwrap());
+        assert!(core.run(connecting).is_err());
+    }
+}
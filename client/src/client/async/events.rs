@@ -0,0 +1,239 @@
+use super::{into_body, Client, HttpTransport, ResponseBody, Transport};
+use client::sse::{self, Parser, DEFAULT_RETRY};
+use endpoint::{Records, Streamable};
+use error::Error;
+use futures::{self, future, Async, Future, Poll};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::time::Duration;
+use tokio_core::reactor::Timeout;
+
+/// A stream of the records that horizon pushes as server sent events. The
+/// stream does not end on its own, records are yielded as horizon sends them.
+/// If the connection drops it is reopened from the paging token of the last
+/// record received, after waiting for the delay horizon requested.
+///
+/// Errors connecting to horizon are yielded from the stream and the connection
+/// is retried if the stream is polled again. If horizon rejects the request
+/// outright the error is yielded and the stream ends.
+///
+/// <https://www.stellar.org/developers/horizon/reference/streaming.html>
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_core;
+/// # extern crate stellar_client;
+/// # fn main() {
+/// use futures::Stream as FutureStream;
+/// use tokio_core::reactor::Core;
+/// use stellar_client::{
+///     endpoint::{ledger, Cursor},
+///     async::{Client, Events},
+/// };
+///
+/// let mut core = Core::new().unwrap();
+/// let client = Client::horizon_test(&core.handle()).unwrap();
+/// let endpoint = ledger::All::default().with_cursor("now");
+/// let events = Events::new(&client, endpoint);
+/// let ledgers = core.run(events.take(1).collect()).unwrap();
+/// assert_eq!(ledgers.len(), 1);
+/// # }
+/// ```
+pub struct Events<T, E, Tr = HttpTransport>
+where
+    E: Streamable<Response = Records<T>>,
+    T: DeserializeOwned,
+    Tr: Transport + Clone,
+{
+    client: Client<Tr>,
+    endpoint: E,
+    parser: Parser,
+    last_id: Option<String>,
+    retry: Duration,
+    state: State,
+    resource: PhantomData<T>,
+}
+
+enum State {
+    Idle,
+    Connecting(Box<Future<Item = ResponseBody, Error = Error>>),
+    Receiving(ResponseBody),
+    Waiting(Timeout),
+    Done,
+}
+
+impl fmt::Debug for State {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            State::Idle => f.write_str("Idle"),
+            State::Connecting(_) => f.write_str("Connecting"),
+            State::Receiving(_) => f.write_str("Receiving"),
+            State::Waiting(_) => f.write_str("Waiting"),
+            State::Done => f.write_str("Done"),
+        }
+    }
+}
+
+impl<T, E, Tr> fmt::Debug for Events<T, E, Tr>
+where
+    E: Streamable<Response = Records<T>> + fmt::Debug,
+    T: DeserializeOwned,
+    Tr: Transport + Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Events")
+            .field("client", &self.client)
+            .field("endpoint", &self.endpoint)
+            .field("last_id", &self.last_id)
+            .field("retry", &self.retry)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T, E, Tr> Events<T, E, Tr>
+where
+    E: Streamable<Response = Records<T>>,
+    T: DeserializeOwned,
+    Tr: Transport + Clone,
+{
+    /// Creates a new event stream for the client and endpoint. No request is
+    /// made until the stream is polled.
+    pub fn new(client: &Client<Tr>, endpoint: E) -> Self {
+        Events {
+            client: client.clone(),
+            endpoint,
+            parser: Parser::default(),
+            last_id: None,
+            retry: DEFAULT_RETRY,
+            state: State::Idle,
+            resource: PhantomData,
+        }
+    }
+
+    /// The id of the last event received. Horizon uses the paging token of the
+    /// record, so this can be used as a cursor to resume the stream later.
+    pub fn last_id(&self) -> Option<&str> {
+        self.last_id.as_ref().map(|s| &**s)
+    }
+
+    fn connect(&self) -> Box<Future<Item = ResponseBody, Error = Error>> {
+        let request = match sse::into_request(&self.endpoint, self.client.uri(), self.last_id()) {
+            Ok(request) => request,
+            Err(err) => return Box::new(future::err(err)),
+        };
+        Box::new(self.client.transport.send(request).and_then(into_body))
+    }
+
+    /// Waits for the retry delay before connecting again. If the timer can't
+    /// be created we reconnect immediately instead.
+    fn reconnect(&self) -> State {
+        match Timeout::new(self.retry, &self.client.handle) {
+            Ok(timeout) => State::Waiting(timeout),
+            Err(_) => State::Idle,
+        }
+    }
+}
+
+impl<T, E, Tr> futures::Stream for Events<T, E, Tr>
+where
+    E: Streamable<Response = Records<T>>,
+    T: DeserializeOwned,
+    Tr: Transport + Clone,
+{
+    type Item = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<T>, Error> {
+        loop {
+            if let Some(event) = self.parser.next_event() {
+                if let Some(retry) = event.retry() {
+                    self.retry = retry;
+                }
+                if let Some(id) = event.id() {
+                    self.last_id = Some(id.to_string());
+                }
+                if event.is_message() {
+                    return event.parse().map(|record| Async::Ready(Some(record)));
+                }
+                continue;
+            }
+
+            match mem::replace(&mut self.state, State::Done) {
+                State::Done => return Ok(Async::Ready(None)),
+                State::Idle => self.state = State::Connecting(self.connect()),
+                State::Waiting(mut timeout) => match timeout.poll() {
+                    Ok(Async::NotReady) => {
+                        self.state = State::Waiting(timeout);
+                        return Ok(Async::NotReady);
+                    }
+                    _ => self.state = State::Idle,
+                },
+                State::Connecting(mut future) => match future.poll() {
+                    Ok(Async::Ready(body)) => self.state = State::Receiving(body),
+                    Ok(Async::NotReady) => {
+                        self.state = State::Connecting(future);
+                        return Ok(Async::NotReady);
+                    }
+                    // Horizon won't accept the request, so retrying is futile.
+                    Err(err @ Error::BadResponse(_)) => return Err(err),
+                    Err(err) => {
+                        self.state = self.reconnect();
+                        return Err(err);
+                    }
+                },
+                State::Receiving(mut body) => match body.poll() {
+                    Ok(Async::Ready(Some(chunk))) => {
+                        self.parser.push(&chunk);
+                        self.state = State::Receiving(body);
+                    }
+                    Ok(Async::NotReady) => {
+                        self.state = State::Receiving(body);
+                        return Ok(Async::NotReady);
+                    }
+                    _ => {
+                        // The connection has dropped, anything partially received
+                        // is discarded and will be sent again on reconnect.
+                        self.parser.reset();
+                        self.state = self.reconnect();
+                    }
+                },
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod events_tests {
+    use super::*;
+    use endpoint::{ledger, Cursor};
+    use futures::Stream as FutureStream;
+    use tokio_core::reactor::Core;
+
+    #[test]
+    fn it_streams_ledgers_as_they_close() {
+        let mut core = Core::new().unwrap();
+        let client = Client::horizon_test(&core.handle()).unwrap();
+        let endpoint = ledger::All::default().with_cursor("now");
+        let events = Events::new(&client, endpoint);
+        let ledgers = core.run(events.take(2).collect()).unwrap();
+        assert_eq!(ledgers.len(), 2);
+        assert_eq!(ledgers[0].sequence() + 1, ledgers[1].sequence());
+    }
+
+    #[test]
+    fn it_ends_if_horizon_rejects_the_stream() {
+        let mut core = Core::new().unwrap();
+        let client =
+            Client::new("https://horizon-testnet.stellar.org/bad", &core.handle()).unwrap();
+        let endpoint = ledger::All::default();
+        let events = Events::new(&client, endpoint);
+        let results = core.run(events.then(|r| Ok::<_, ()>(r)).collect()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_err());
+    }
+}
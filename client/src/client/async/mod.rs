@@ -0,0 +1,406 @@
+//! This module contains the client for asynchronous communcation. An asynchronous
+//! client is one that returns a future for each request. The future must be
+//! executed on a tokio event loop in order to resolve into the response.
+
+use super::{ClientBuilder, Host, HORIZON_TEST_URI, HORIZON_URI};
+use endpoint::IntoRequest;
+use error::{Error, Result};
+use futures::{future, Future, Stream as FutureStream};
+use http;
+use network::Network;
+use serde_json;
+use tokio_core::reactor::Handle;
+use StellarError;
+
+mod connector;
+mod events;
+mod stream;
+mod transport;
+
+pub use self::events::Events;
+pub use self::stream::Stream;
+pub use self::transport::{HttpTransport, ResponseBody, ResponseFuture, Transport};
+
+/// A client that can issue requests to a horizon api.
+///
+/// Requests are sent with the client's `Transport`, which is http by default.
+#[derive(Debug, Clone)]
+pub struct Client<T = HttpTransport> {
+    transport: T,
+    host: Host,
+    handle: Handle,
+    network: Option<Network>,
+}
+
+impl Client {
+    /// Constructs a new stellar client.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # extern crate tokio_core;
+    /// # extern crate stellar_client;
+    /// # fn main() {
+    /// use tokio_core::reactor::Core;
+    /// use stellar_client::async::Client;
+    /// let core = Core::new().unwrap();
+    /// let client = Client::new("https://horizon-testnet.stellar.org", &core.handle()).unwrap();
+    /// # }
+    /// ```
+    pub fn new(uri: &str, handle: &Handle) -> Result<Self> {
+        // Ensure that the uri passed in can parse.
+        let _: http::Uri = uri.parse()?;
+        Self::build(Host::Other(uri.to_string()), &handle)
+    }
+
+    fn build(host: Host, handle: &Handle) -> Result<Self> {
+        ClientBuilder::from_host(host).build_async(handle)
+    }
+
+    /// Constructs a new stellar client connected to the horizon test network.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # extern crate tokio_core;
+    /// # extern crate stellar_client;
+    /// # fn main() {
+    /// use tokio_core::reactor::Core;
+    /// use stellar_client::async::Client;
+    /// let core = Core::new().unwrap();
+    /// let client = Client::horizon_test(&core.handle()).unwrap();
+    /// # }
+    /// ```
+    pub fn horizon_test(handle: &Handle) -> Result<Self> {
+        Self::build(Host::HorizonTest, &handle)
+    }
+
+    /// Constructs a new stellar client connected to the horizon prod network.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # extern crate tokio_core;
+    /// # extern crate stellar_client;
+    /// # fn main() {
+    /// use tokio_core::reactor::Core;
+    /// use stellar_client::async::Client;
+    /// let core = Core::new().unwrap();
+    /// let client = Client::horizon(&core.handle()).unwrap();
+    /// # }
+    /// ```
+    pub fn horizon(handle: &Handle) -> Result<Self> {
+        Self::build(Host::HorizonProd, &handle)
+    }
+}
+
+impl<T> Client<T>
+where
+    T: Transport,
+{
+    /// Constructs a new stellar client that sends its requests with the given
+    /// transport instead of over http.
+    ///
+    /// See `Transport` for an example.
+    pub fn from_transport(uri: &str, transport: T, handle: &Handle) -> Result<Self> {
+        let _: http::Uri = uri.parse()?;
+        Ok(Self::from_host(
+            Host::Other(uri.to_string()),
+            transport,
+            handle,
+        ))
+    }
+
+    pub(crate) fn from_host(host: Host, transport: T, handle: &Handle) -> Self {
+        let network = host.network();
+        Client {
+            transport,
+            host,
+            handle: handle.clone(),
+            network,
+        }
+    }
+
+    /// Returns true if this is a test client.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # extern crate tokio_core;
+    /// # extern crate stellar_client;
+    /// # fn main() {
+    /// # use tokio_core::reactor::Core;
+    /// # use stellar_client::async::Client;
+    /// # let core = Core::new().unwrap();
+    /// let client = Client::horizon_test(&core.handle()).unwrap();
+    /// assert!(!client.is_horizon());
+    /// assert!(client.is_horizon_test());
+    /// # }
+    /// ```
+    pub fn is_horizon_test(&self) -> bool {
+        self.host == Host::HorizonTest
+    }
+
+    /// Returns true if this is a horizon@stellar client.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # extern crate tokio_core;
+    /// # extern crate stellar_client;
+    /// # fn main() {
+    /// # use tokio_core::reactor::Core;
+    /// # use stellar_client::async::Client;
+    /// # let core = Core::new().unwrap();
+    /// let client = Client::horizon(&core.handle()).unwrap();
+    /// assert!(client.is_horizon());
+    /// assert!(!client.is_horizon_test());
+    /// # }
+    /// ```
+    pub fn is_horizon(&self) -> bool {
+        self.host == Host::HorizonProd
+    }
+
+    /// Sets the network the horizon server is connected to. Clients connected
+    /// to stellar's horizon servers know their network, but a client for any
+    /// other server needs to be told.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # extern crate tokio_core;
+    /// # extern crate stellar_client;
+    /// # fn main() {
+    /// use tokio_core::reactor::Core;
+    /// use stellar_client::{async::Client, Network};
+    ///
+    /// let core = Core::new().unwrap();
+    /// let client = Client::new("http://localhost:8000", &core.handle())
+    ///     .unwrap()
+    ///     .with_network(Network::new("Standalone Network ; February 2017"));
+    /// # }
+    /// ```
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// The network the horizon server is connected to, if it is known.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # extern crate tokio_core;
+    /// # extern crate stellar_client;
+    /// # fn main() {
+    /// use tokio_core::reactor::Core;
+    /// use stellar_client::{async::Client, Network};
+    ///
+    /// let core = Core::new().unwrap();
+    /// let client = Client::horizon_test(&core.handle()).unwrap();
+    /// assert_eq!(client.network(), Some(&Network::test()));
+    /// # }
+    /// ```
+    pub fn network(&self) -> Option<&Network> {
+        self.network.as_ref()
+    }
+
+    fn uri(&self) -> &str {
+        match self.host {
+            Host::HorizonTest => HORIZON_TEST_URI,
+            Host::HorizonProd => HORIZON_URI,
+            Host::Other(ref uri) => uri,
+        }
+    }
+
+    /// Issues a request to the stellar horizon server. The returned future will
+    /// resolve into the endpoint's response once executed on the event loop.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// # extern crate tokio_core;
+    /// # extern crate stellar_client;
+    /// # fn main() {
+    /// use tokio_core::reactor::Core;
+    /// use stellar_client::async::Client;
+    /// use stellar_client::endpoint::account;
+    ///
+    /// let mut core = Core::new().unwrap();
+    /// let client = Client::horizon_test(&core.handle()).unwrap();
+    /// let endpoint =
+    ///     account::Details::new("GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ");
+    /// let account = core.run(client.request(endpoint)).unwrap();
+    /// assert_eq!(account.id(), "GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ");
+    /// # }
+    /// ```
+    pub fn request<E>(&self, endpoint: E) -> Box<Future<Item = E::Response, Error = Error>>
+    where
+        E: IntoRequest,
+        E::Response: 'static,
+    {
+        let request = match endpoint.into_request(&self.uri()) {
+            Ok(request) => request,
+            Err(err) => return Box::new(future::err(err)),
+        };
+
+        let response = self
+            .transport
+            .send(request)
+            .and_then(into_body)
+            .and_then(|body| body.concat2())
+            .and_then(|body| -> Result<E::Response> {
+                let resp: E::Response = serde_json::from_slice(&body)?;
+                Ok(resp)
+            });
+        Box::new(response)
+    }
+}
+
+/// Resolves to the body of a successful response, or the error horizon answered with.
+fn into_body(
+    response: http::Response<ResponseBody>,
+) -> Box<Future<Item = ResponseBody, Error = Error>> {
+    let status = response.status();
+    let body = response.into_body();
+    if status.is_success() {
+        return Box::new(future::ok(body));
+    }
+    let error = body
+        .concat2()
+        .and_then(move |body| -> Result<ResponseBody> {
+            if status.is_client_error() {
+                let e: StellarError = serde_json::from_slice(&body)?;
+                Err(Error::BadResponse(e))
+            } else {
+                Err(Error::ServerError)
+            }
+        });
+    Box::new(error)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream;
+    use std::io::Read;
+    use sync::{self, Cassette, Replayer};
+    use tokio_core::reactor::Core;
+
+    /// Replays cassettes for the asynchronous client too, so that both
+    /// clients' tests stand in for horizon the same way. The body arrives in
+    /// several chunks.
+    impl Transport for Replayer {
+        fn send(&self, request: http::Request<::endpoint::Body>) -> ResponseFuture {
+            let response = sync::Transport::send(self, request).and_then(|response| {
+                let (parts, mut body) = response.into_parts();
+                let mut bytes = Vec::new();
+                body.read_to_end(&mut bytes)?;
+                let chunks: Vec<Result<Vec<u8>>> =
+                    bytes.chunks(64).map(|chunk| Ok(chunk.to_vec())).collect();
+                let body: ResponseBody = Box::new(stream::iter_result(chunks));
+                Ok(http::Response::from_parts(parts, body))
+            });
+            Box::new(future::result(response))
+        }
+    }
+
+    #[test]
+    fn it_constructs_a_test_client() {
+        let core = Core::new().unwrap();
+        let client = Client::horizon_test(&core.handle()).unwrap();
+        assert_eq!(client.host, Host::HorizonTest);
+        assert_eq!(client.uri(), "https://horizon-testnet.stellar.org");
+        assert_eq!(client.network(), Some(&Network::test()));
+    }
+
+    #[test]
+    fn it_constructs_a_horizon_client() {
+        let core = Core::new().unwrap();
+        let client = Client::horizon(&core.handle()).unwrap();
+        assert_eq!(client.host, Host::HorizonProd);
+        assert_eq!(client.uri(), "https://horizon.stellar.org");
+        assert_eq!(client.network(), Some(&Network::public()));
+    }
+
+    #[test]
+    fn it_constructs_a_client_to_other() {
+        let core = Core::new().unwrap();
+        let client = Client::new("https://www.google.com", &core.handle()).unwrap();
+        assert_eq!(
+            client.host,
+            Host::Other("https://www.google.com".to_string())
+        );
+        assert_eq!(client.uri(), "https://www.google.com");
+        assert_eq!(client.network(), None);
+    }
+
+    #[test]
+    fn it_errs_if_a_bad_uri_is_provided() {
+        let core = Core::new().unwrap();
+        let result = Client::new("htps:/www", &core.handle());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn it_can_make_a_request() {
+        use endpoint::account::Details;
+        let mut core = Core::new().unwrap();
+        let client = Client::horizon_test(&core.handle()).unwrap();
+        let endpoint = Details::new("GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ");
+        let account = core.run(client.request(endpoint)).unwrap();
+        assert_eq!(
+            account.id(),
+            "GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ"
+        );
+    }
+
+    #[test]
+    fn it_can_make_a_failed_request() {
+        use endpoint::account::Details;
+        use stellar_error;
+        let mut core = Core::new().unwrap();
+        let client = Client::horizon_test(&core.handle()).unwrap();
+        let endpoint = Details::new("LDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ");
+        match core.run(client.request(endpoint)).unwrap_err() {
+            Error::BadResponse(error) => assert_eq!(error.kind(), stellar_error::Kind::NotFound),
+            error => panic!("Client did not return a bad response {:?}", error),
+        }
+    }
+
+    #[test]
+    fn it_sends_requests_with_the_transport() {
+        use endpoint::account::Details;
+        let mut core = Core::new().unwrap();
+        let id = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+        let replayer = Replayer::new(Cassette::default().with_get(
+            &format!("/accounts/{}", id),
+            200,
+            include_str!("../../../fixtures/account.json"),
+        ));
+        let client =
+            Client::from_transport("http://localhost:8000", replayer.clone(), &core.handle())
+                .unwrap();
+        let account = core.run(client.request(Details::new(id))).unwrap();
+        assert_eq!(account.id(), id);
+        assert!(replayer.is_finished());
+    }
+
+    #[test]
+    fn it_returns_the_errors_answered_by_the_transport() {
+        use endpoint::account::Details;
+        let mut core = Core::new().unwrap();
+        let id = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+        let replayer = Replayer::new(Cassette::default().with_get(
+            &format!("/accounts/{}", id),
+            410,
+            include_str!("../../../fixtures/before_history_error.json"),
+        ));
+        let client =
+            Client::from_transport("http://localhost:8000", replayer, &core.handle()).unwrap();
+        match core.run(client.request(Details::new(id))).unwrap_err() {
+            Error::BadResponse(error) => assert!(error.is_before_history()),
+            error => panic!("Client did not return a bad response {:?}", error),
+        }
+    }
+}
@@ -0,0 +1,237 @@
+use super::{Client, HttpTransport, Transport};
+use endpoint::{IntoRequest, Records};
+use error::{Error, Result};
+use futures::{self, Async, Future, Poll};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use uri::TryFromUri;
+
+/// A stream of records. Provides the ability to lazily walk through the pages
+/// of records that are returned from the api on an event loop. The next page
+/// is only requested once the records of the current page have been consumed.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate tokio_core;
+/// # extern crate stellar_client;
+/// # fn main() {
+/// use futures::Stream as FutureStream;
+/// use tokio_core::reactor::Core;
+/// use stellar_client::{
+///     endpoint::{asset, Limit},
+///     async::{Client, Stream},
+/// };
+///
+/// let mut core = Core::new().unwrap();
+/// let client = Client::horizon_test(&core.handle()).unwrap();
+/// let endpoint = asset::All::default().with_limit(3);
+/// let stream = Stream::new(&client, endpoint);
+/// let assets = core.run(stream.take(10).collect()).unwrap();
+/// assert_eq!(assets.len(), 10);
+/// # }
+/// ```
+pub struct Stream<T, E, Tr = HttpTransport>
+where
+    E: IntoRequest<Response = Records<T>> + TryFromUri + Clone,
+    T: DeserializeOwned + Clone + 'static,
+    Tr: Transport + Clone,
+{
+    client: Client<Tr>,
+    endpoint: E,
+    records: Option<Records<T>>,
+    state: State<T>,
+}
+
+enum State<T>
+where
+    T: DeserializeOwned,
+{
+    Idle,
+    Fetching(Box<Future<Item = Records<T>, Error = Error>>),
+    OnCache(usize),
+    EOF,
+    Error,
+}
+
+impl<T> fmt::Debug for State<T>
+where
+    T: DeserializeOwned,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            State::Idle => f.write_str("Idle"),
+            State::Fetching(_) => f.write_str("Fetching"),
+            State::OnCache(next) => write!(f, "OnCache({})", next),
+            State::EOF => f.write_str("EOF"),
+            State::Error => f.write_str("Error"),
+        }
+    }
+}
+
+impl<T, E, Tr> fmt::Debug for Stream<T, E, Tr>
+where
+    E: IntoRequest<Response = Records<T>> + TryFromUri + Clone + fmt::Debug,
+    T: DeserializeOwned + Clone + fmt::Debug + 'static,
+    Tr: Transport + Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Stream")
+            .field("client", &self.client)
+            .field("endpoint", &self.endpoint)
+            .field("records", &self.records)
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<T, E, Tr> Stream<T, E, Tr>
+where
+    E: IntoRequest<Response = Records<T>> + TryFromUri + Clone,
+    T: DeserializeOwned + Clone + 'static,
+    Tr: Transport + Clone,
+{
+    /// Creates a new stream for the client and endpoint.
+    pub fn new(client: &Client<Tr>, endpoint: E) -> Self {
+        Stream {
+            client: client.clone(),
+            endpoint,
+            records: None,
+            state: State::Idle,
+        }
+    }
+
+    fn fetch(&mut self) -> Result<()> {
+        // We already have records meaning we've made a request already
+        if let Some(ref records) = self.records {
+            // When we have a next link, use it for the next endpoint, otherwise
+            // return early and set state to the end of file.
+            if let Some(ref uri) = records.next() {
+                self.endpoint = E::try_from(uri)?;
+            } else {
+                self.state = State::EOF;
+                return Ok(());
+            }
+        }
+
+        self.state = State::Fetching(self.client.request(self.endpoint.clone()));
+        Ok(())
+    }
+
+    fn poll_fetch(&mut self) -> Poll<(), Error> {
+        let records = match self.state {
+            State::Fetching(ref mut future) => try_ready!(future.poll()),
+            _ => return Ok(Async::Ready(())),
+        };
+
+        // If there are records on this page, we switch to being
+        // on the cache. If there aren't then we assume we are at
+        // the end of the file.
+        if records.records().is_empty() {
+            self.records = None;
+            self.state = State::EOF;
+        } else {
+            self.records = Some(records);
+            self.state = State::OnCache(0);
+        }
+        Ok(Async::Ready(()))
+    }
+
+    fn get_cache(&mut self, next: usize) -> Option<T> {
+        if let Some(ref records) = self.records {
+            if next < records.records().len() {
+                let val = records.records()[next].clone();
+                self.state = State::OnCache(next + 1);
+                return Some(val);
+            }
+        }
+        self.state = State::Idle;
+        None
+    }
+}
+
+impl<T, E, Tr> futures::Stream for Stream<T, E, Tr>
+where
+    E: IntoRequest<Response = Records<T>> + TryFromUri + Clone,
+    T: DeserializeOwned + Clone + 'static,
+    Tr: Transport + Clone,
+{
+    type Item = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<T>, Error> {
+        loop {
+            match self.state {
+                State::EOF | State::Error => {
+                    return Ok(Async::Ready(None));
+                }
+                State::Idle => {
+                    if let Err(err) = self.fetch() {
+                        self.state = State::Error;
+                        return Err(err);
+                    }
+                }
+                State::Fetching(_) => match self.poll_fetch() {
+                    Ok(Async::Ready(())) => {}
+                    Ok(Async::NotReady) => return Ok(Async::NotReady),
+                    Err(err) => {
+                        self.state = State::Error;
+                        return Err(err);
+                    }
+                },
+                State::OnCache(next) => {
+                    if let Some(val) = self.get_cache(next) {
+                        return Ok(Async::Ready(Some(val)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod stream_tests {
+    use super::*;
+    use endpoint::{account, asset, trade, Limit};
+    use futures::Stream as FutureStream;
+    use resources::AssetIdentifier;
+    use tokio_core::reactor::Core;
+
+    #[test]
+    fn it_can_stream_through_records() {
+        let mut core = Core::new().unwrap();
+        let client = Client::horizon_test(&core.handle()).unwrap();
+        let endpoint = asset::All::default().with_limit(3);
+        let stream = Stream::new(&client, endpoint);
+        let assets = core.run(stream.take(10).collect()).unwrap();
+        assert!(assets.len() > 3);
+    }
+
+    #[test]
+    fn it_ends_if_no_records_returned_from_horizon() {
+        let mut core = Core::new().unwrap();
+        let client = Client::horizon_test(&core.handle()).unwrap();
+        // Aggregations are odd in that they always provide a `next` url even if there
+        // is no next page. So this test will ensure that the stream actually finishes
+        // if the current page has no results.
+        let endpoint =
+            trade::Aggregations::new(&AssetIdentifier::native(), &AssetIdentifier::native());
+        let stream = Stream::new(&client, endpoint);
+        let aggregations = core.run(stream.collect()).unwrap();
+        assert_eq!(aggregations.len(), 0);
+    }
+
+    #[test]
+    fn it_returns_one_with_error_if_request_fails() {
+        let mut core = Core::new().unwrap();
+        let client = Client::horizon_test(&core.handle()).unwrap();
+        let endpoint = account::Transactions::new("NOT AN ID");
+        let stream = Stream::new(&client, endpoint);
+        let all = core
+            .run(stream.then(|result| Ok::<_, ()>(result)).collect())
+            .unwrap();
+        assert_eq!(all.len(), 1);
+        assert!(all[0].is_err());
+    }
+}
@@ -0,0 +1,267 @@
+use super::connector::Connector;
+use client::builder::{self, Config};
+use endpoint::Body;
+use error::{Error, Result};
+use futures::{future, Async, Future, Poll, Stream as FutureStream};
+use http::{self, header::HeaderMap};
+use hyper;
+use hyper_tls::HttpsConnector;
+use native_tls::{Certificate, TlsConnector};
+use std::io;
+use std::time::Duration;
+use tokio_core::reactor::{Handle, Timeout};
+
+/// The body of a response, which yields its chunks as they arrive so that
+/// streamed responses can be consumed before they end.
+pub type ResponseBody = Box<FutureStream<Item = Vec<u8>, Error = Error>>;
+
+/// A future of the response to a request, which resolves once the response
+/// headers have arrived.
+pub type ResponseFuture = Box<Future<Item = http::Response<ResponseBody>, Error = Error>>;
+
+/// Sends http requests for the asynchronous client. The client is generic
+/// over its transport, so requests can be answered from memory in tests
+/// instead of over the network.
+///
+/// ## Examples
+///
+/// ```
+/// # extern crate futures;
+/// # extern crate http;
+/// # extern crate stellar_client;
+/// # extern crate tokio_core;
+/// use futures::{future, stream};
+/// use stellar_client::async::{Client, ResponseBody, ResponseFuture, Transport};
+/// use stellar_client::endpoint::{account, Body};
+/// use stellar_client::Error;
+/// use tokio_core::reactor::Core;
+///
+/// #[derive(Debug, Clone)]
+/// struct NotFound;
+///
+/// impl Transport for NotFound {
+///     fn send(&self, _: http::Request<Body>) -> ResponseFuture {
+///         let body = r#"{"type": "https://stellar.org/horizon-errors/not_found",
+///                        "title": "Resource Missing", "status": 404, "detail": ""}"#;
+///         let body: ResponseBody = Box::new(stream::once(Ok(body.as_bytes().to_vec())));
+///         let response = http::Response::builder().status(404).body(body);
+///         Box::new(future::result(response.map_err(Error::from)))
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut core = Core::new().unwrap();
+/// let client = Client::from_transport("http://localhost:8000", NotFound, &core.handle()).unwrap();
+/// let endpoint =
+///     account::Details::new("GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ");
+/// match core.run(client.request(endpoint)) {
+///     Err(Error::BadResponse(error)) => assert!(error.is_not_found()),
+///     _ => panic!("expected horizon to answer not found"),
+/// }
+/// # }
+/// ```
+pub trait Transport {
+    /// Sends the request. The future resolves to the response once its
+    /// headers have arrived. Only a failure to get a response is an error,
+    /// responses with an error status are returned like any other.
+    fn send(&self, request: http::Request<Body>) -> ResponseFuture;
+}
+
+/// The default transport, which sends requests over http(s) with `hyper` on
+/// the event loop. It is configured by the `ClientBuilder`.
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    inner: hyper::Client<HttpsConnector<Connector>>,
+    headers: HeaderMap,
+    proxied: bool,
+    timeout: Option<Duration>,
+    handle: Handle,
+}
+
+impl HttpTransport {
+    /// Constructs a transport that runs on the event loop of the handle.
+    pub fn new(handle: &Handle) -> Result<Self> {
+        Self::configure(&Config::default(), handle)
+    }
+
+    pub(crate) fn configure(config: &Config, handle: &Handle) -> Result<Self> {
+        let proxy = match config.proxy {
+            Some(ref proxy) => Some(proxy.parse::<hyper::Uri>()?),
+            None => None,
+        };
+        let http = hyper::client::HttpConnector::new(config.dns_threads, handle);
+        let connector = Connector::new(http, proxy.clone(), config.connect_timeout, handle);
+
+        let mut tls = TlsConnector::builder().map_err(|_| Error::BadSSL)?;
+        for pem in &config.root_certificates {
+            let certificate = Certificate::from_pem(pem).map_err(|_| Error::BadSSL)?;
+            tls.add_root_certificate(certificate)
+                .map_err(|_| Error::BadSSL)?;
+        }
+        let tls = tls.build().map_err(|_| Error::BadSSL)?;
+
+        let inner = hyper::Client::configure()
+            .connector(HttpsConnector::from((connector, tls)))
+            .build(handle);
+        Ok(HttpTransport {
+            inner,
+            headers: config.headers()?,
+            proxied: proxy.is_some(),
+            timeout: config.timeout,
+            handle: handle.clone(),
+        })
+    }
+
+    fn http_to_hyper(&self, request: &http::Request<Body>) -> Result<hyper::Request> {
+        use http::method::Method;
+        let method = match *request.method() {
+            Method::GET => hyper::Method::Get,
+            Method::POST => hyper::Method::Post,
+            ref method => return Err(Error::UnsupportedMethod(method.clone())),
+        };
+        let uri: hyper::Uri = format!("{}", request.uri()).parse()?;
+        // Requests for http servers go to the proxy with the absolute uri,
+        // while those for https servers are tunneled by the connector.
+        let proxied = self.proxied && uri.scheme() == Some("http");
+        let mut hyper_request = hyper::Request::new(method, uri);
+        hyper_request.set_proxy(proxied);
+        for (name, value) in request.headers() {
+            hyper_request
+                .headers_mut()
+                .set_raw(name.as_str().to_string(), value.as_bytes().to_vec());
+        }
+        if let Some(body) = request.body().encode() {
+            hyper_request.set_body(body);
+        }
+        Ok(hyper_request)
+    }
+
+    fn hyper_to_http(response: hyper::Response) -> Result<http::Response<ResponseBody>> {
+        let mut builder = http::Response::builder();
+        builder.status(response.status().as_u16());
+        for header in response.headers().iter() {
+            for value in header.raw().iter() {
+                builder.header(header.name(), value);
+            }
+        }
+        let body = response
+            .body()
+            .map(|chunk| chunk.to_vec())
+            .from_err::<Error>();
+        Ok(builder.body(Box::new(body) as ResponseBody)?)
+    }
+}
+
+impl Transport for HttpTransport {
+    fn send(&self, mut request: http::Request<Body>) -> ResponseFuture {
+        builder::add_headers(&mut request, &self.headers);
+        let request = match self.http_to_hyper(&request) {
+            Ok(request) => request,
+            Err(err) => return Box::new(future::err(err)),
+        };
+        let response = self
+            .inner
+            .request(request)
+            .from_err::<Error>()
+            .and_then(Self::hyper_to_http);
+
+        let timeout = match self.timeout {
+            Some(timeout) => timeout,
+            None => return Box::new(response),
+        };
+        let handle = self.handle.clone();
+        let response = response.map(move |response| {
+            response.map(|body| Box::new(TimedBody::new(body, timeout, &handle)) as ResponseBody)
+        });
+        let timed_out = match Timeout::new(timeout, &self.handle) {
+            Ok(timed_out) => timed_out.from_err::<Error>().and_then(
+                |_| -> Result<http::Response<ResponseBody>> {
+                    Err(Error::Io(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "Timed out waiting for horizon to respond",
+                    )))
+                },
+            ),
+            Err(err) => return Box::new(future::err(err.into())),
+        };
+        let response = response
+            .select(timed_out)
+            .map(|(response, _)| response)
+            .map_err(|(err, _)| err);
+        Box::new(response)
+    }
+}
+
+/// A response body that fails once the timeout passes without a chunk
+/// arriving. The timer starts over with every chunk, so long responses and
+/// streams that keep sending aren't cut off.
+struct TimedBody {
+    inner: ResponseBody,
+    timeout: Duration,
+    handle: Handle,
+    timer: Option<Timeout>,
+}
+
+impl TimedBody {
+    fn new(inner: ResponseBody, timeout: Duration, handle: &Handle) -> Self {
+        TimedBody {
+            inner,
+            timeout,
+            handle: handle.clone(),
+            timer: None,
+        }
+    }
+}
+
+impl FutureStream for TimedBody {
+    type Item = Vec<u8>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, Error> {
+        if let Async::Ready(chunk) = self.inner.poll()? {
+            self.timer = None;
+            return Ok(Async::Ready(chunk));
+        }
+        if self.timer.is_none() {
+            self.timer = Some(Timeout::new(self.timeout, &self.handle)?);
+        }
+        let fired = match self.timer {
+            Some(ref mut timer) => timer.poll()?.is_ready(),
+            None => false,
+        };
+        if fired {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "Timed out waiting for horizon to send the response",
+            )));
+        }
+        Ok(Async::NotReady)
+    }
+}
+
+#[cfg(test)]
+mod http_transport_tests {
+    use super::*;
+    use futures::stream;
+    use tokio_core::reactor::Core;
+
+    #[test]
+    fn it_times_out_when_the_body_stops_arriving() {
+        let mut core = Core::new().unwrap();
+        let chunk = stream::once(Ok(b"data: hello\n\n".to_vec()));
+        let stalled = future::empty::<Vec<u8>, Error>().into_stream();
+        let body: ResponseBody = Box::new(chunk.chain(stalled));
+        let body = TimedBody::new(body, Duration::from_millis(10), &core.handle());
+
+        let (chunk, body) = core
+            .run(body.into_future())
+            .map_err(|(err, _)| err)
+            .unwrap();
+        assert_eq!(chunk.unwrap(), b"data: hello\n\n".to_vec());
+        match core.run(body.into_future()).map_err(|(err, _)| err) {
+            Err(Error::Io(ref err)) if err.kind() == io::ErrorKind::TimedOut => (),
+            Err(err) => panic!("Failed without timing out {:?}", err),
+            Ok(_) => panic!("Waited on the body past the timeout"),
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use super::{async, sync, Host};
+use endpoint::Body;
+use error::{Error, Result};
+use http::{
+    self,
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Uri,
+};
+use std::time::Duration;
+use tokio_core::reactor::Handle;
+
+/// How long a client waits for horizon to respond when no timeout is set.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The number of threads the asynchronous client resolves host names on when
+/// no other number is set.
+pub const DEFAULT_DNS_THREADS: usize = 4;
+
+/// Configures and builds either client. Stellar's own horizon servers and any
+/// other can be configured with timeouts, headers sent with every request,
+/// a proxy and root certificates to trust, which the clients otherwise
+/// construct with defaults.
+///
+/// Every request identifies the client with the `X-Client-Name` and
+/// `X-Client-Version` headers, which name this crate unless set otherwise.
+///
+/// ## Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use stellar_client::ClientBuilder;
+///
+/// let client = ClientBuilder::new("https://horizon.example.com")
+///     .unwrap()
+///     .timeout(Duration::from_secs(10))
+///     .header("X-Api-Key", "3f7b0d2e")
+///     .client_identity("my-wallet", "1.2.0")
+///     .build_sync()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct ClientBuilder {
+    host: Host,
+    config: Config,
+    retry: sync::RetryPolicy,
+}
+
+/// The settings of a builder that configure the http transports.
+#[derive(Debug, Clone)]
+pub(crate) struct Config {
+    pub(crate) timeout: Option<Duration>,
+    pub(crate) connect_timeout: Option<Duration>,
+    pub(crate) headers: Vec<(String, String)>,
+    pub(crate) proxy: Option<String>,
+    pub(crate) root_certificates: Vec<Vec<u8>>,
+    pub(crate) dns_threads: usize,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            timeout: Some(DEFAULT_TIMEOUT),
+            connect_timeout: None,
+            headers: vec![
+                (
+                    "X-Client-Name".to_string(),
+                    env!("CARGO_PKG_NAME").to_string(),
+                ),
+                (
+                    "X-Client-Version".to_string(),
+                    env!("CARGO_PKG_VERSION").to_string(),
+                ),
+            ],
+            proxy: None,
+            root_certificates: Vec::new(),
+            dns_threads: DEFAULT_DNS_THREADS,
+        }
+    }
+}
+
+impl Config {
+    /// The headers to send with every request. Headers set later replace those
+    /// of the same name set before.
+    pub(crate) fn headers(&self) -> Result<HeaderMap> {
+        let mut headers = HeaderMap::new();
+        for &(ref name, ref value) in &self.headers {
+            let name = HeaderName::from_bytes(name.as_bytes()).map_err(http::Error::from)?;
+            let value = HeaderValue::from_str(value).map_err(http::Error::from)?;
+            headers.insert(name, value);
+        }
+        Ok(headers)
+    }
+}
+
+/// Adds the headers to the request, except those it already has.
+pub(crate) fn add_headers(request: &mut http::Request<Body>, headers: &HeaderMap) {
+    for (name, value) in headers.iter() {
+        if !request.headers().contains_key(name) {
+            request.headers_mut().insert(name.clone(), value.clone());
+        }
+    }
+}
+
+impl ClientBuilder {
+    /// Starts building a client to the horizon server at the uri.
+    pub fn new(uri: &str) -> Result<Self> {
+        let _: Uri = uri.parse()?;
+        Ok(Self::from_host(Host::Other(uri.to_string())))
+    }
+
+    /// Starts building a client to stellar's horizon test network.
+    pub fn horizon_test() -> Self {
+        Self::from_host(Host::HorizonTest)
+    }
+
+    /// Starts building a client to stellar's horizon prod network.
+    pub fn horizon() -> Self {
+        Self::from_host(Host::HorizonProd)
+    }
+
+    pub(crate) fn from_host(host: Host) -> Self {
+        ClientBuilder {
+            host,
+            config: Config::default(),
+            retry: sync::RetryPolicy::never(),
+        }
+    }
+
+    /// Sets how long to wait for horizon to respond to a request, which is 60
+    /// seconds by default. Both clients apply it to the wait for the response
+    /// headers and to each read of the response body, and the synchronous
+    /// client to connecting as well. Streams of events that go quiet for
+    /// longer reconnect.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.config.timeout = Some(timeout);
+        self
+    }
+
+    /// Waits for horizon to respond for as long as it takes.
+    pub fn no_timeout(mut self) -> Self {
+        self.config.timeout = None;
+        self
+    }
+
+    /// Sets how long the asynchronous client waits to connect to horizon,
+    /// including through the proxy. The synchronous client can only bound
+    /// connecting with its `timeout`, so `build_sync` fails when this is set.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.config.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Adds a header to send with every request, such as the api key of a
+    /// hosted horizon. The header replaces one of the same name added before,
+    /// but not one the endpoint sets itself.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.config
+            .headers
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Identifies the client to horizon with the name and version, instead of
+    /// the name and version of this crate.
+    pub fn client_identity(self, name: &str, version: &str) -> Self {
+        self.header("X-Client-Name", name)
+            .header("X-Client-Version", version)
+    }
+
+    /// Sends every request through the http proxy at the uri. Requests to
+    /// https servers are tunneled through it.
+    pub fn proxy(mut self, uri: &str) -> Self {
+        self.config.proxy = Some(uri.to_string());
+        self
+    }
+
+    /// Trusts the pem encoded certificate as a root when verifying the
+    /// certificates of https servers, besides the roots of the system.
+    pub fn root_certificate(mut self, pem: &[u8]) -> Self {
+        self.config.root_certificates.push(pem.to_vec());
+        self
+    }
+
+    /// Sets the number of threads the asynchronous client resolves host names
+    /// on, which is 4 by default. `build_async` fails when it is 0.
+    pub fn dns_threads(mut self, threads: usize) -> Self {
+        self.config.dns_threads = threads;
+        self
+    }
+
+    /// Retries the requests of the synchronous client that horizon answers
+    /// with a rate limit or a server error according to the policy. Only the
+    /// synchronous client retries, so `build_async` fails when it is set.
+    pub fn retry(mut self, policy: sync::RetryPolicy) -> Self {
+        self.retry = policy;
+        self
+    }
+
+    /// Builds a synchronous client. Fails if a header, the proxy or a
+    /// certificate is invalid, or if a connect timeout is set.
+    pub fn build_sync(self) -> Result<sync::Client> {
+        if self.config.connect_timeout.is_some() {
+            return Err(Error::BadConfig(
+                "The synchronous client can't bound connecting apart from its timeout",
+            ));
+        }
+        let transport = sync::HttpTransport::configure(&self.config)?;
+        Ok(sync::Client::from_host(self.host, transport).with_retry(self.retry))
+    }
+
+    /// Builds an asynchronous client that runs on the event loop of the
+    /// handle. Fails if a header, the proxy or a certificate is invalid, if
+    /// there are no threads to resolve host names on, or if a retry policy
+    /// is set.
+    pub fn build_async(self, handle: &Handle) -> Result<async::Client> {
+        if self.config.dns_threads == 0 {
+            return Err(Error::BadConfig(
+                "The asynchronous client needs at least one dns thread",
+            ));
+        }
+        if self.retry != sync::RetryPolicy::never() {
+            return Err(Error::BadConfig(
+                "The asynchronous client does not retry requests",
+            ));
+        }
+        let transport = async::HttpTransport::configure(&self.config, handle)?;
+        Ok(async::Client::from_host(self.host, transport, handle))
+    }
+}
+
+#[cfg(test)]
+mod client_builder_tests {
+    use super::*;
+    use tokio_core::reactor::Core;
+    use Error;
+
+    #[test]
+    fn it_identifies_the_client_by_default() {
+        let headers = Config::default().headers().unwrap();
+        assert_eq!(headers["x-client-name"], "stellar-client");
+        assert_eq!(headers["x-client-version"], env!("CARGO_PKG_VERSION"));
+    }
+
+    #[test]
+    fn it_replaces_headers_set_before() {
+        let builder = ClientBuilder::horizon_test()
+            .header("X-Api-Key", "first")
+            .header("X-Api-Key", "second")
+            .client_identity("my-wallet", "1.2.0");
+        let headers = builder.config.headers().unwrap();
+        assert_eq!(headers["x-api-key"], "second");
+        assert_eq!(headers["x-client-name"], "my-wallet");
+        assert_eq!(headers["x-client-version"], "1.2.0");
+        assert_eq!(headers.len(), 3);
+    }
+
+    #[test]
+    fn it_does_not_replace_headers_of_the_request() {
+        let headers = ClientBuilder::horizon_test()
+            .header("Content-Type", "text/plain")
+            .header("X-Api-Key", "secret")
+            .config
+            .headers()
+            .unwrap();
+        let mut request = http::Request::post("https://horizon.example.com/transactions")
+            .header("Content-Type", "application/x-www-form-urlencoded")
+            .body(Body::None)
+            .unwrap();
+        add_headers(&mut request, &headers);
+        assert_eq!(
+            request.headers()["content-type"],
+            "application/x-www-form-urlencoded"
+        );
+        assert_eq!(request.headers()["x-api-key"], "secret");
+    }
+
+    #[test]
+    fn it_builds_both_clients() {
+        let client = ClientBuilder::horizon()
+            .timeout(Duration::from_secs(5))
+            .build_sync()
+            .unwrap();
+        assert!(client.is_horizon());
+
+        let core = Core::new().unwrap();
+        let client = ClientBuilder::new("http://localhost:8000")
+            .unwrap()
+            .connect_timeout(Duration::from_secs(1))
+            .dns_threads(1)
+            .build_async(&core.handle())
+            .unwrap();
+        assert!(!client.is_horizon_test());
+    }
+
+    #[test]
+    fn it_fails_to_build_with_an_invalid_header() {
+        match ClientBuilder::horizon_test()
+            .header("X-Api-Key", "line\nbreak")
+            .build_sync()
+        {
+            Err(Error::Http(_)) => (),
+            other => panic!("Built a client with an invalid header {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_fails_to_build_with_settings_the_client_can_not_honor() {
+        match ClientBuilder::horizon_test()
+            .connect_timeout(Duration::from_secs(1))
+            .build_sync()
+        {
+            Err(Error::BadConfig(_)) => (),
+            other => panic!("Built a client ignoring its connect timeout {:?}", other),
+        }
+        let core = Core::new().unwrap();
+        match ClientBuilder::horizon_test()
+            .dns_threads(0)
+            .build_async(&core.handle())
+        {
+            Err(Error::BadConfig(_)) => (),
+            other => panic!("Built a client without dns threads {:?}", other),
+        }
+        match ClientBuilder::horizon_test()
+            .retry(sync::RetryPolicy::attempts(3))
+            .build_async(&core.handle())
+        {
+            Err(Error::BadConfig(_)) => (),
+            other => panic!("Built a client that would not retry {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_fails_to_build_with_an_invalid_certificate() {
+        let core = Core::new().unwrap();
+        let result = ClientBuilder::horizon_test()
+            .root_certificate(b"not a certificate")
+            .build_async(&core.handle());
+        assert!(result.is_err());
+        let result = ClientBuilder::horizon_test()
+            .root_certificate(b"not a certificate")
+            .build_sync();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn it_fails_to_build_with_an_invalid_proxy() {
+        let result = ClientBuilder::horizon_test()
+            .proxy("not a proxy")
+            .build_sync();
+        assert!(result.is_err());
+    }
+}
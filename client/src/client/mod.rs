@@ -5,15 +5,33 @@
 //!
 //! In contrast, the async client will return a future for execution on the
 //! event loop and will yield the returned resource as a result of a future.
+use network::Network;
+
 #[derive(Debug, Clone, PartialEq)]
-enum Host {
+pub(crate) enum Host {
     HorizonTest,
     HorizonProd,
     Other(String),
 }
 
+impl Host {
+    /// The network served by the host, if it is known.
+    fn network(&self) -> Option<Network> {
+        match *self {
+            Host::HorizonTest => Some(Network::test()),
+            Host::HorizonProd => Some(Network::public()),
+            Host::Other(_) => None,
+        }
+    }
+}
+
 static HORIZON_TEST_URI: &'static str = "https://horizon-testnet.stellar.org";
 static HORIZON_URI: &'static str = "https://horizon.stellar.org";
 
+mod builder;
+mod sse;
+
 pub mod async;
 pub mod sync;
+
+pub use self::builder::{ClientBuilder, DEFAULT_DNS_THREADS, DEFAULT_TIMEOUT};
@@ -0,0 +1,246 @@
+//! A minimal parser for the server sent events protocol that horizon uses when
+//! streaming. It is shared by the sync and async clients, neither of which needs
+//! to know how the bytes arrived.
+//!
+//! <https://html.spec.whatwg.org/multipage/server-sent-events.html>
+use endpoint::{Body, Streamable};
+use error::Result;
+use http::{
+    self,
+    header::{HeaderName, HeaderValue, ACCEPT},
+};
+use serde::de::DeserializeOwned;
+use serde_json;
+use std::time::Duration;
+
+/// The content type that horizon expects in order to respond with an event stream.
+static EVENT_STREAM: &'static str = "text/event-stream";
+
+/// The delay used before reconnecting if horizon hasn't told us otherwise.
+pub(crate) const DEFAULT_RETRY: Duration = Duration::from_secs(1);
+
+/// Forms the request used to open (or reopen) a stream. When resuming, the
+/// cursor is moved to the last event received and the id is also passed along
+/// in the `Last-Event-ID` header.
+pub(crate) fn into_request<E>(
+    endpoint: &E,
+    host: &str,
+    last_id: Option<&str>,
+) -> Result<http::Request<Body>>
+where
+    E: Streamable,
+{
+    let endpoint = match last_id {
+        Some(id) => endpoint.clone().with_cursor(id),
+        None => endpoint.clone(),
+    };
+    let mut request = endpoint.into_request(host)?;
+    {
+        let headers = request.headers_mut();
+        headers.insert(ACCEPT, HeaderValue::from_static(EVENT_STREAM));
+        if let Some(id) = last_id.and_then(|id| HeaderValue::from_str(id).ok()) {
+            headers.insert(HeaderName::from_static("last-event-id"), id);
+        }
+    }
+    Ok(request)
+}
+
+/// A single event that was dispatched from the stream.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct Event {
+    id: Option<String>,
+    event: Option<String>,
+    data: Option<String>,
+    retry: Option<u64>,
+}
+
+impl Event {
+    /// The id of the event. Horizon uses the paging token of the record.
+    pub(crate) fn id(&self) -> Option<&str> {
+        self.id.as_ref().map(|s| &**s)
+    }
+
+    /// The reconnection time that the server requested, if any.
+    pub(crate) fn retry(&self) -> Option<Duration> {
+        self.retry.map(Duration::from_millis)
+    }
+
+    /// Returns true if this event carries a resource. Horizon will send control
+    /// messages, such as the `open` event, which should not be parsed as records.
+    pub(crate) fn is_message(&self) -> bool {
+        self.data.is_some() && self.event.as_ref().map_or(true, |e| e == "message")
+    }
+
+    /// Parses the data of the event into the resource.
+    pub(crate) fn parse<T>(&self) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let data = self.data.as_ref().map(|s| &**s).unwrap_or("");
+        Ok(serde_json::from_str(data)?)
+    }
+}
+
+/// Buffers the bytes of an event stream as they arrive and splits them into
+/// events once they have been completely received.
+#[derive(Debug, Default)]
+pub(crate) struct Parser {
+    buffer: Vec<u8>,
+    pending: Option<Event>,
+}
+
+impl Parser {
+    /// Appends newly received bytes to the parser.
+    pub(crate) fn push(&mut self, bytes: &[u8]) {
+        self.buffer.extend_from_slice(bytes);
+    }
+
+    /// Discards anything that has been buffered. Used when a connection is
+    /// dropped part way through an event.
+    pub(crate) fn reset(&mut self) {
+        self.buffer.clear();
+        self.pending = None;
+    }
+
+    /// Returns the next complete event if one has been buffered.
+    pub(crate) fn next_event(&mut self) -> Option<Event> {
+        while let Some(position) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..position + 1).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_right_matches(|c| c == '\n' || c == '\r');
+
+            // A blank line dispatches the event.
+            if line.is_empty() {
+                if let Some(event) = self.pending.take() {
+                    return Some(event);
+                }
+                continue;
+            }
+
+            // Lines starting with a colon are comments
+            if line.starts_with(':') {
+                continue;
+            }
+
+            let (field, value) = match line.find(':') {
+                Some(index) => {
+                    let value = &line[index + 1..];
+                    let value = if value.starts_with(' ') {
+                        &value[1..]
+                    } else {
+                        value
+                    };
+                    (&line[..index], value)
+                }
+                None => (line, ""),
+            };
+
+            let event = self.pending.get_or_insert_with(Event::default);
+            match field {
+                "id" => event.id = Some(value.to_string()),
+                "event" => event.event = Some(value.to_string()),
+                "retry" => event.retry = value.parse().ok(),
+                "data" => match event.data {
+                    Some(ref mut data) => {
+                        data.push('\n');
+                        data.push_str(value);
+                    }
+                    None => event.data = Some(value.to_string()),
+                },
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod parser_tests {
+    use super::*;
+
+    #[derive(Deserialize, Debug)]
+    struct Foo {
+        foo: String,
+    }
+
+    #[test]
+    fn it_requests_an_event_stream() {
+        use endpoint::payment;
+        let request = into_request(
+            &payment::All::default(),
+            "https://horizon.stellar.org",
+            None,
+        )
+        .unwrap();
+        assert_eq!(request.uri().query(), None);
+        assert_eq!(request.headers()[ACCEPT], "text/event-stream");
+        assert!(request.headers().get("last-event-id").is_none());
+    }
+
+    #[test]
+    fn it_resumes_from_the_last_event() {
+        use endpoint::payment;
+        let request = into_request(
+            &payment::All::default(),
+            "https://horizon.stellar.org",
+            Some("12345"),
+        )
+        .unwrap();
+        assert_eq!(request.uri().query(), Some("cursor=12345&"));
+        assert_eq!(request.headers()["last-event-id"], "12345");
+    }
+
+    #[test]
+    fn it_parses_the_horizon_open_event() {
+        let mut parser = Parser::default();
+        parser.push(b"retry: 1000\nevent: open\ndata: \"hello\"\n\n");
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.retry(), Some(Duration::from_millis(1000)));
+        assert!(!event.is_message());
+        assert_eq!(parser.next_event(), None);
+    }
+
+    #[test]
+    fn it_parses_a_record() {
+        let mut parser = Parser::default();
+        parser.push(b"id: 12345\ndata: {\"foo\":\"bar\"}\n\n");
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.id(), Some("12345"));
+        assert!(event.is_message());
+        let foo: Foo = event.parse().unwrap();
+        assert_eq!(foo.foo, "bar");
+    }
+
+    #[test]
+    fn it_waits_for_the_whole_event() {
+        let mut parser = Parser::default();
+        parser.push(b"id: 12345\r\nda");
+        assert_eq!(parser.next_event(), None);
+        parser.push(b"ta: {\"foo\":\"bar\"}\r\n");
+        assert_eq!(parser.next_event(), None);
+        parser.push(b"\r\n");
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.id(), Some("12345"));
+        assert_eq!(event.parse::<Foo>().unwrap().foo, "bar");
+    }
+
+    #[test]
+    fn it_joins_multiline_data_and_skips_comments() {
+        let mut parser = Parser::default();
+        parser.push(b": keep alive\n\ndata: {\"foo\":\ndata: \"bar\"}\n\nid: 1\n");
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.id(), None);
+        assert_eq!(event.parse::<Foo>().unwrap().foo, "bar");
+        assert_eq!(parser.next_event(), None);
+    }
+
+    #[test]
+    fn it_discards_partial_events_on_reset() {
+        let mut parser = Parser::default();
+        parser.push(b"id: 1\ndata: {\"foo\":");
+        parser.reset();
+        parser.push(b"id: 2\ndata: {\"foo\":\"bar\"}\n\n");
+        let event = parser.next_event().unwrap();
+        assert_eq!(event.id(), Some("2"));
+    }
+}
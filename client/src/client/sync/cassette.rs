@@ -0,0 +1,419 @@
+use super::{HttpTransport, ResponseBody, Transport};
+use endpoint::Body;
+use error::{Error, Result};
+use http;
+use serde_json;
+use std::fs::File;
+use std::io::{self, Cursor, Read};
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+/// The requests a client made and the responses horizon answered them with,
+/// in the order they were made. A cassette is recorded against a real horizon
+/// with a `Recorder` and saved to a json file, which a `Replayer` loads to
+/// answer the same requests without the network.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Cassette {
+    interactions: Vec<Interaction>,
+}
+
+impl Cassette {
+    /// Loads a cassette from the json file at the path.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Cassette> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(file)?)
+    }
+
+    /// Saves the cassette as json to the file at the path, replacing it if it
+    /// exists.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let file = File::create(path)?;
+        Ok(serde_json::to_writer_pretty(file, self)?)
+    }
+
+    /// The number of requests recorded.
+    pub fn len(&self) -> usize {
+        self.interactions.len()
+    }
+
+    /// Whether no requests have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.interactions.is_empty()
+    }
+}
+
+/// Cassettes written out in tests, which replay responses that would be hard
+/// to record from horizon, such as errors and retries.
+#[cfg(test)]
+impl Cassette {
+    /// Adds a recording of the request answered with the status and body.
+    pub(crate) fn with(mut self, request: &http::Request<Body>, status: u16, body: &str) -> Self {
+        self.interactions.push(Interaction {
+            request: RecordedRequest::new(request),
+            response: RecordedResponse {
+                status,
+                headers: Vec::new(),
+                body: body.to_string(),
+            },
+        });
+        self
+    }
+
+    /// Adds a recording of a get of the uri answered with the status and body.
+    /// The uri may leave out the host, which replaying ignores.
+    pub(crate) fn with_get(self, uri: &str, status: u16, body: &str) -> Self {
+        let request = http::Request::get(uri)
+            .body(Body::None)
+            .expect("Cassette uris in tests are valid");
+        self.with(&request, status, body)
+    }
+
+    /// Adds a header to the response of the last recording.
+    pub(crate) fn with_header(mut self, name: &str, value: &str) -> Self {
+        if let Some(interaction) = self.interactions.last_mut() {
+            interaction
+                .response
+                .headers
+                .push((name.to_string(), value.to_string()));
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct Interaction {
+    request: RecordedRequest,
+    response: RecordedResponse,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedRequest {
+    method: String,
+    uri: String,
+    body: Option<String>,
+}
+
+impl RecordedRequest {
+    fn new(request: &http::Request<Body>) -> Self {
+        RecordedRequest {
+            method: request.method().as_str().to_string(),
+            uri: request.uri().to_string(),
+            body: request.body().encode(),
+        }
+    }
+
+    /// Requests match when their method, path, query and body are the same.
+    /// The host is ignored so that a cassette recorded against one horizon
+    /// can be replayed by a client of another.
+    fn matches(&self, other: &RecordedRequest) -> bool {
+        self.method == other.method
+            && path_and_query(&self.uri) == path_and_query(&other.uri)
+            && self.body == other.body
+    }
+}
+
+fn path_and_query(uri: &str) -> Option<String> {
+    uri.parse::<http::Uri>()
+        .ok()
+        .and_then(|uri| uri.path_and_query().map(|path| path.as_str().to_string()))
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct RecordedResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl RecordedResponse {
+    fn read(response: http::Response<ResponseBody>) -> Result<Self> {
+        let (parts, mut body) = response.into_parts();
+        let mut bytes = Vec::new();
+        body.read_to_end(&mut bytes)?;
+        let body = String::from_utf8(bytes)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        let headers = parts
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let value = String::from_utf8_lossy(value.as_bytes()).into_owned();
+                (name.as_str().to_string(), value)
+            })
+            .collect();
+        Ok(RecordedResponse {
+            status: parts.status.as_u16(),
+            headers,
+            body,
+        })
+    }
+
+    fn to_http(&self) -> Result<http::Response<ResponseBody>> {
+        let mut builder = http::Response::builder();
+        builder.status(self.status);
+        for &(ref name, ref value) in &self.headers {
+            builder.header(name.as_str(), value.as_str());
+        }
+        let body: ResponseBody = Box::new(Cursor::new(self.body.clone().into_bytes()));
+        Ok(builder.body(body)?)
+    }
+}
+
+/// A transport that sends requests with another transport, horizon over http
+/// by default, and records each request and response onto a cassette.
+///
+/// Responses are read to their end before they are returned, so event
+/// streams, which do not end, cannot be recorded. Clones of a recorder record
+/// onto the same cassette, so a clone can be kept to save the cassette after
+/// the client is done with the other.
+///
+/// ## Examples
+///
+/// ```no_run
+/// use stellar_client::{
+///     endpoint::{asset, Limit},
+///     sync::{Client, Iter, Recorder},
+/// };
+///
+/// let recorder = Recorder::new().unwrap();
+/// let client =
+///     Client::from_transport("https://horizon-testnet.stellar.org", recorder.clone()).unwrap();
+/// let assets = Iter::new(&client, asset::All::default().with_limit(2));
+/// assert_eq!(assets.take(5).count(), 5);
+/// recorder.save("fixtures/cassettes/assets.json").unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct Recorder<T = HttpTransport> {
+    inner: T,
+    cassette: Arc<Mutex<Cassette>>,
+}
+
+impl Recorder {
+    /// Constructs a recorder that sends requests to horizon over http.
+    pub fn new() -> Result<Self> {
+        Ok(Self::wrap(HttpTransport::new()?))
+    }
+}
+
+impl<T: Transport> Recorder<T> {
+    /// Constructs a recorder that sends requests with the transport.
+    pub fn wrap(inner: T) -> Self {
+        Recorder {
+            inner,
+            cassette: Arc::new(Mutex::new(Cassette::default())),
+        }
+    }
+
+    /// The cassette of the requests recorded so far.
+    pub fn cassette(&self) -> Cassette {
+        self.cassette
+            .lock()
+            .expect("Recorder cassette was poisoned")
+            .clone()
+    }
+
+    /// Saves the requests recorded so far to the file at the path.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        self.cassette().save(path)
+    }
+}
+
+impl<T: Transport> Transport for Recorder<T> {
+    fn send(&self, request: http::Request<Body>) -> Result<http::Response<ResponseBody>> {
+        let recorded = RecordedRequest::new(&request);
+        let response = RecordedResponse::read(self.inner.send(request)?)?;
+        let replay = response.to_http();
+        self.cassette
+            .lock()
+            .expect("Recorder cassette was poisoned")
+            .interactions
+            .push(Interaction {
+                request: recorded,
+                response,
+            });
+        replay
+    }
+}
+
+/// A transport that answers requests with the responses recorded on a
+/// cassette, byte for byte, without the network.
+///
+/// Each request is answered with the first recording of the same method,
+/// path, query and body that has not been replayed yet. A request without one
+/// fails with `Error::Unrecorded`.
+///
+/// ## Examples
+///
+/// ```
+/// use stellar_client::{
+///     endpoint::{asset, Limit},
+///     resources::Asset,
+///     sync::{Client, Iter, Replayer},
+/// };
+///
+/// let replayer = Replayer::load("fixtures/cassettes/assets.json").unwrap();
+/// let client =
+///     Client::from_transport("https://horizon-testnet.stellar.org", replayer).unwrap();
+/// let assets: Vec<Asset> = Iter::new(&client, asset::All::default().with_limit(2))
+///     .map(|asset| asset.unwrap())
+///     .collect();
+/// assert_eq!(assets.len(), 5);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Replayer {
+    cassette: Arc<Cassette>,
+    replayed: Arc<Mutex<Vec<bool>>>,
+}
+
+impl Replayer {
+    /// Constructs a replayer of the cassette.
+    pub fn new(cassette: Cassette) -> Self {
+        let replayed = vec![false; cassette.len()];
+        Replayer {
+            cassette: Arc::new(cassette),
+            replayed: Arc::new(Mutex::new(replayed)),
+        }
+    }
+
+    /// Constructs a replayer of the cassette in the json file at the path.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Ok(Self::new(Cassette::load(path)?))
+    }
+
+    /// Whether every recording on the cassette has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.replayed
+            .lock()
+            .expect("Replayer state was poisoned")
+            .iter()
+            .all(|&replayed| replayed)
+    }
+}
+
+impl Transport for Replayer {
+    fn send(&self, request: http::Request<Body>) -> Result<http::Response<ResponseBody>> {
+        let request = RecordedRequest::new(&request);
+        let mut replayed = self.replayed.lock().expect("Replayer state was poisoned");
+        let index = self
+            .cassette
+            .interactions
+            .iter()
+            .zip(replayed.iter())
+            .position(|(interaction, &replayed)| !replayed && interaction.request.matches(&request))
+            .ok_or(Error::Unrecorded)?;
+        replayed[index] = true;
+        self.cassette.interactions[index].response.to_http()
+    }
+}
+
+#[cfg(test)]
+mod cassette_tests {
+    use super::*;
+    use endpoint::ledger;
+    use std::env;
+    use std::fs;
+    use sync::Client;
+
+    static LEDGER: &str = include_str!("../../../fixtures/ledger.json");
+
+    /// Stands in for horizon, answering gets of the ledgers with the ledger
+    /// fixture and a rate limit header.
+    fn horizon(sequences: &[u32]) -> Replayer {
+        let cassette = sequences
+            .iter()
+            .fold(Cassette::default(), |cassette, sequence| {
+                cassette
+                    .with_get(&format!("/ledgers/{}", sequence), 200, LEDGER)
+                    .with_header("content-type", "application/hal+json")
+                    .with_header("x-ratelimit-remaining", "3599")
+            });
+        Replayer::new(cassette)
+    }
+
+    fn read(response: http::Response<ResponseBody>) -> String {
+        let mut body = String::new();
+        response.into_body().read_to_string(&mut body).unwrap();
+        body
+    }
+
+    #[test]
+    fn it_records_each_request_and_response() {
+        let recorder = Recorder::wrap(horizon(&[69859, 2]));
+        let client =
+            Client::from_transport("https://horizon.example.com", recorder.clone()).unwrap();
+        let ledger = client.request(ledger::Details::new(69859)).unwrap();
+        assert_eq!(ledger.sequence(), 69859);
+        client.request(ledger::Details::new(2)).unwrap();
+
+        let cassette = recorder.cassette();
+        assert_eq!(cassette.len(), 2);
+        let interaction = &cassette.interactions[0];
+        assert_eq!(interaction.request.method, "GET");
+        assert_eq!(
+            interaction.request.uri,
+            "https://horizon.example.com/ledgers/69859"
+        );
+        assert_eq!(interaction.request.body, None);
+        assert_eq!(interaction.response.status, 200);
+        assert_eq!(interaction.response.body, LEDGER);
+        assert!(interaction
+            .response
+            .headers
+            .contains(&("x-ratelimit-remaining".to_string(), "3599".to_string())));
+    }
+
+    #[test]
+    fn it_replays_the_recordings_byte_for_byte() {
+        let recorder = Recorder::wrap(horizon(&[1]));
+        let request = || {
+            http::Request::get("https://horizon.example.com/ledgers/1")
+                .body(Body::None)
+                .unwrap()
+        };
+        let recorded = read(recorder.send(request()).unwrap());
+
+        let replayer = Replayer::new(recorder.cassette());
+        assert!(!replayer.is_finished());
+        // The host is ignored, so cassettes can be replayed by any client.
+        let response = replayer
+            .send(
+                http::Request::get("http://localhost:8000/ledgers/1")
+                    .body(Body::None)
+                    .unwrap(),
+            )
+            .unwrap();
+        assert_eq!(response.status(), 200);
+        assert_eq!(response.headers()["x-ratelimit-remaining"], "3599");
+        assert_eq!(read(response), recorded);
+        assert!(replayer.is_finished());
+
+        // Each recording is only replayed once.
+        match replayer.send(request()) {
+            Err(Error::Unrecorded) => (),
+            _ => panic!("Replayed a recording twice"),
+        }
+    }
+
+    #[test]
+    fn it_fails_requests_that_were_not_recorded() {
+        let client = Client::from_transport("https://horizon.example.com", horizon(&[1])).unwrap();
+        client.request(ledger::Details::new(1)).unwrap();
+        match client.request(ledger::Details::new(2)).unwrap_err() {
+            Error::Unrecorded => (),
+            error => panic!("Replayed an unrecorded request {:?}", error),
+        }
+    }
+
+    #[test]
+    fn it_saves_and_loads_cassettes() {
+        let recorder = Recorder::wrap(horizon(&[1]));
+        let client =
+            Client::from_transport("https://horizon.example.com", recorder.clone()).unwrap();
+        client.request(ledger::Details::new(1)).unwrap();
+
+        let path = env::temp_dir().join("stellar-client-cassette-test.json");
+        recorder.save(&path).unwrap();
+        let cassette = Cassette::load(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        assert_eq!(cassette, recorder.cassette());
+    }
+}
@@ -0,0 +1,194 @@
+use super::{into_body, Client, HttpTransport, ResponseBody, Transport};
+use client::sse::{self, Parser, DEFAULT_RETRY};
+use endpoint::{Records, Streamable};
+use error::{Error, Result};
+use serde::de::DeserializeOwned;
+use std::fmt;
+use std::io::Read;
+use std::marker::PhantomData;
+use std::thread;
+use std::time::Duration;
+
+/// An iterator over the records that horizon streams as server sent events.
+/// The iterator blocks until the next record arrives and does not end on its
+/// own. If the connection drops it is reopened from the paging token of the
+/// last record received, after waiting for the delay horizon requested.
+///
+/// Errors connecting to horizon are returned from the iterator and the
+/// connection is retried on the next call. If horizon rejects the request
+/// outright the error is returned and the iterator ends.
+///
+/// <https://www.stellar.org/developers/horizon/reference/streaming.html>
+///
+/// # Examples
+///
+/// ```
+/// use stellar_client::{
+///     endpoint::{ledger, Cursor},
+///     sync::{Client, Events},
+/// };
+/// let client = Client::horizon_test().unwrap();
+/// let endpoint = ledger::All::default().with_cursor("now");
+/// let events = Events::new(&client, endpoint);
+/// # drop(events);
+/// ```
+pub struct Events<'a, T, E, Tr = HttpTransport>
+where
+    E: Streamable<Response = Records<T>>,
+    T: DeserializeOwned,
+    Tr: Transport + 'a,
+{
+    client: &'a Client<Tr>,
+    endpoint: E,
+    parser: Parser,
+    response: Option<ResponseBody>,
+    last_id: Option<String>,
+    retry: Duration,
+    connected: bool,
+    done: bool,
+    resource: PhantomData<T>,
+}
+
+impl<'a, T, E, Tr> fmt::Debug for Events<'a, T, E, Tr>
+where
+    E: Streamable<Response = Records<T>> + fmt::Debug,
+    T: DeserializeOwned,
+    Tr: Transport + fmt::Debug + 'a,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Events")
+            .field("client", &self.client)
+            .field("endpoint", &self.endpoint)
+            .field("connected", &self.response.is_some())
+            .field("last_id", &self.last_id)
+            .field("retry", &self.retry)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+impl<'a, T, E, Tr> Events<'a, T, E, Tr>
+where
+    E: Streamable<Response = Records<T>>,
+    T: DeserializeOwned,
+    Tr: Transport + 'a,
+{
+    /// Creates a new event stream for the client and endpoint. No request is
+    /// made until the first record is requested from the iterator.
+    pub fn new(client: &'a Client<Tr>, endpoint: E) -> Self {
+        Events {
+            client,
+            endpoint,
+            parser: Parser::default(),
+            response: None,
+            last_id: None,
+            retry: DEFAULT_RETRY,
+            connected: false,
+            done: false,
+            resource: PhantomData,
+        }
+    }
+
+    /// The id of the last event received. Horizon uses the paging token of the
+    /// record, so this can be used as a cursor to resume the stream later.
+    pub fn last_id(&self) -> Option<&str> {
+        self.last_id.as_ref().map(|s| &**s)
+    }
+
+    fn connect(&mut self) -> Result<ResponseBody> {
+        // Only wait between attempts, never before the first.
+        if self.connected {
+            thread::sleep(self.retry);
+        }
+        self.connected = true;
+
+        let request = sse::into_request(
+            &self.endpoint,
+            self.client.uri(),
+            self.last_id.as_ref().map(|s| &**s),
+        )?;
+        into_body(self.client.transport.send(request)?)
+    }
+}
+
+impl<'a, T, E, Tr> Iterator for Events<'a, T, E, Tr>
+where
+    E: Streamable<Response = Records<T>>,
+    T: DeserializeOwned,
+    Tr: Transport + 'a,
+{
+    type Item = Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.done {
+                return None;
+            }
+
+            if let Some(event) = self.parser.next_event() {
+                if let Some(retry) = event.retry() {
+                    self.retry = retry;
+                }
+                if let Some(id) = event.id() {
+                    self.last_id = Some(id.to_string());
+                }
+                if event.is_message() {
+                    return Some(event.parse());
+                }
+                continue;
+            }
+
+            if self.response.is_none() {
+                match self.connect() {
+                    Ok(response) => self.response = Some(response),
+                    Err(error) => {
+                        if let Error::BadResponse(_) = error {
+                            self.done = true;
+                        }
+                        return Some(Err(error));
+                    }
+                }
+            }
+
+            let mut buffer = [0; 4096];
+            let read = self.response.as_mut().map(|r| r.read(&mut buffer));
+            match read {
+                Some(Ok(n)) if n > 0 => self.parser.push(&buffer[..n]),
+                _ => {
+                    // The connection has dropped, anything partially received
+                    // is discarded and will be sent again on reconnect.
+                    self.response = None;
+                    self.parser.reset();
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod events_tests {
+    use super::*;
+    use endpoint::{ledger, Cursor};
+    use resources::Ledger;
+
+    #[test]
+    fn it_streams_ledgers_as_they_close() {
+        let client = Client::horizon_test().unwrap();
+        let endpoint = ledger::All::default().with_cursor("now");
+        let ledgers: Vec<Ledger> = Events::new(&client, endpoint)
+            .take(2)
+            .map(|ledger| ledger.unwrap())
+            .collect();
+        assert_eq!(ledgers.len(), 2);
+        assert_eq!(ledgers[0].sequence() + 1, ledgers[1].sequence());
+    }
+
+    #[test]
+    fn it_ends_if_horizon_rejects_the_stream() {
+        let client = Client::new("https://horizon-testnet.stellar.org/bad").unwrap();
+        let endpoint = ledger::All::default();
+        let mut events = Events::new(&client, endpoint);
+        assert!(events.next().unwrap().is_err());
+        assert!(events.next().is_none());
+    }
+}
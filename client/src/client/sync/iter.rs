@@ -1,12 +1,17 @@
-use super::Client;
+use super::{Client, HttpTransport, Transport};
 use endpoint::{IntoRequest, Records};
-use error::Result;
+use error::{Error, Result};
 use serde::de::DeserializeOwned;
 use uri::TryFromUri;
 
 /// An iterator for records. Provides the ability to use the iterator
 /// in rust against records that are returned from the api.
 ///
+/// When horizon answers with a rate limit or a server error, even after the
+/// client's retries, the error is returned and the next call fetches the same
+/// page again. The iteration ends after three such errors in a row, or after
+/// any other error.
+///
 /// # Examples
 ///
 /// ```
@@ -20,17 +25,23 @@ use uri::TryFromUri;
 /// assert_eq!(iter.take(10).count(), 10);
 /// ```
 #[derive(Debug)]
-pub struct Iter<'a, T, E>
+pub struct Iter<'a, T, E, Tr = HttpTransport>
 where
     E: IntoRequest<Response = Records<T>> + TryFromUri + Clone,
     T: DeserializeOwned + Clone,
+    Tr: Transport + 'a,
 {
-    client: &'a Client,
+    client: &'a Client<Tr>,
     endpoint: E,
     records: Option<Records<T>>,
     state: State,
+    errors: u32,
 }
 
+/// The number of rate limits or server errors in a row after which the
+/// iterator stops fetching the page.
+const MAX_TRANSIENT_ERRORS: u32 = 3;
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum State {
     Fetching,
@@ -39,18 +50,20 @@ enum State {
     Error,
 }
 
-impl<'a, T, E> Iter<'a, T, E>
+impl<'a, T, E, Tr> Iter<'a, T, E, Tr>
 where
     E: IntoRequest<Response = Records<T>> + TryFromUri + Clone,
     T: DeserializeOwned + Clone,
+    Tr: Transport + 'a,
 {
     /// Creates a new iterator for the client and endpoint.
-    pub fn new(client: &'a Client, endpoint: E) -> Self {
+    pub fn new(client: &'a Client<Tr>, endpoint: E) -> Self {
         Iter {
             client,
             endpoint,
             records: None,
             state: State::Fetching,
+            errors: 0,
         }
     }
 
@@ -94,10 +107,11 @@ where
     }
 }
 
-impl<'a, T, E> Iterator for Iter<'a, T, E>
+impl<'a, T, E, Tr> Iterator for Iter<'a, T, E, Tr>
 where
     E: IntoRequest<Response = Records<T>> + TryFromUri + Clone,
     T: DeserializeOwned + Clone,
+    Tr: Transport + 'a,
 {
     type Item = Result<T>;
 
@@ -109,9 +123,16 @@ where
                 }
                 State::Fetching => {
                     if let Err(err) = self.fetch() {
-                        self.state = State::Error;
+                        // Horizon may answer with the page once it recovers, so
+                        // the next call fetches the same page again, unless it
+                        // has kept failing.
+                        self.errors += 1;
+                        if !is_transient(&err) || self.errors >= MAX_TRANSIENT_ERRORS {
+                            self.state = State::Error;
+                        }
                         return Some(Err(err));
                     }
+                    self.errors = 0;
                 }
                 State::OnCache(next) => {
                     if let Some(val) = self.get_cache(next) {
@@ -123,11 +144,23 @@ where
     }
 }
 
+/// Whether the error was a rate limit or a server error, after which the
+/// request may succeed when sent again.
+fn is_transient(err: &Error) -> bool {
+    match *err {
+        Error::ServerError => true,
+        Error::BadResponse(ref err) => err.is_rate_limit_exceeded(),
+        _ => false,
+    }
+}
+
 #[cfg(test)]
 mod iterator_tests {
     use super::*;
     use endpoint::{account, asset, trade, Limit};
-    use resources::{AssetIdentifier, Transaction};
+    use mock::{fixtures, Page, Response};
+    use resources::{Asset, AssetIdentifier, Transaction};
+    use sync::{Cassette, Replayer};
 
     #[test]
     fn it_can_iterate_through_records() {
@@ -137,6 +170,22 @@ mod iterator_tests {
         assert!(iter.take(10).count() > 3);
     }
 
+    #[test]
+    fn it_iterates_through_pages_recorded_from_horizon() {
+        let replayer = Replayer::load("fixtures/cassettes/assets.json").unwrap();
+        let client =
+            Client::from_transport("https://horizon-testnet.stellar.org", replayer.clone())
+                .unwrap();
+        let endpoint = asset::All::default().with_limit(2);
+        let assets: Vec<Asset> = Iter::new(&client, endpoint)
+            .map(|asset| asset.unwrap())
+            .collect();
+        let codes: Vec<&str> = assets.iter().map(|asset| asset.code()).collect();
+        assert_eq!(codes, vec!["AAA", "BTC", "EURT", "LUMENAUT", "USD"]);
+        assert_eq!(assets[4].num_accounts(), 91547871);
+        assert!(replayer.is_finished());
+    }
+
     #[test]
     fn it_breaks_if_no_records_returned_from_horizon() {
         let client = Client::horizon_test().unwrap();
@@ -158,4 +207,46 @@ mod iterator_tests {
         assert_eq!(all.len(), 1);
         assert!(all[0].is_err());
     }
+
+    #[test]
+    fn it_resumes_from_the_same_page_after_a_server_error() {
+        let first: Response = Page::new(&[fixtures::ASSET, fixtures::ASSET])
+            .with_next("http://localhost:8000/assets?order=asc&limit=2&cursor=2")
+            .into();
+        let second: Response = Page::new(&[fixtures::ASSET]).into();
+        let replayer = Replayer::new(
+            Cassette::default()
+                .with_get("/assets?limit=2", 200, first.body())
+                .with_get("/assets?order=asc&cursor=2&limit=2", 503, "")
+                .with_get("/assets?order=asc&cursor=2&limit=2", 200, second.body()),
+        );
+        let client = Client::from_transport("http://localhost:8000", replayer.clone()).unwrap();
+        let endpoint = asset::All::default().with_limit(2);
+        let results: Vec<bool> = Iter::new(&client, endpoint)
+            .map(|asset| asset.is_ok())
+            .collect();
+        assert_eq!(results, vec![true, true, false, true]);
+        assert!(replayer.is_finished());
+    }
+
+    #[test]
+    fn it_ends_once_horizon_keeps_failing() {
+        let page: Response = Page::new(&[fixtures::ASSET])
+            .with_next("http://localhost:8000/assets?order=asc&limit=1&cursor=1")
+            .into();
+        let replayer = Replayer::new(
+            Cassette::default()
+                .with_get("/assets?limit=1", 200, page.body())
+                .with_get("/assets?order=asc&cursor=1&limit=1", 503, "")
+                .with_get("/assets?order=asc&cursor=1&limit=1", 503, "")
+                .with_get("/assets?order=asc&cursor=1&limit=1", 503, ""),
+        );
+        let client = Client::from_transport("http://localhost:8000", replayer.clone()).unwrap();
+        let endpoint = asset::All::default().with_limit(1);
+        let results: Vec<bool> = Iter::new(&client, endpoint)
+            .map(|asset| asset.is_ok())
+            .collect();
+        assert_eq!(results, vec![true, false, false, false]);
+        assert!(replayer.is_finished());
+    }
 }
@@ -13,29 +13,40 @@
 //! let client = Client::new("https://horizon-testnet.stellar.org").unwrap();
 //! ```
 
-use super::{Host, HORIZON_TEST_URI, HORIZON_URI};
-use endpoint::IntoRequest;
+use super::{ClientBuilder, Host, HORIZON_TEST_URI, HORIZON_URI};
+use endpoint::{Body, IntoRequest};
 use error::{Error, Result};
 use http::{self, Uri};
-use reqwest;
+use network::Network;
 use serde_json;
-use std::time::Duration;
+use std::thread;
 use StellarError;
 
+mod cassette;
+mod events;
 mod iter;
+mod retry;
+mod transport;
 
+pub use self::cassette::{Cassette, Recorder, Replayer};
+pub use self::events::Events;
 pub use self::iter::Iter;
-
-const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+pub use self::retry::RetryPolicy;
+pub use self::transport::{HttpTransport, ResponseBody, Transport};
 
 /// A client that can issue requests to a horizon api in a synchronous
 /// fashion, meaning that the functions will block until the response
 /// has been formed. The overall performance of this is slightly slower
 /// than using async but will generally be simpler to implement.
+///
+/// Requests are sent with the client's `Transport`, which is http by default,
+/// and are only sent once unless the client is given a `RetryPolicy`.
 #[derive(Debug, Clone)]
-pub struct Client {
-    inner: reqwest::Client,
+pub struct Client<T = HttpTransport> {
+    transport: T,
     host: Host,
+    network: Option<Network>,
+    retry: RetryPolicy,
 }
 
 impl Client {
@@ -54,11 +65,7 @@ impl Client {
     }
 
     fn build(host: Host) -> Result<Self> {
-        let inner = reqwest::ClientBuilder::new()
-            .timeout(Some(DEFAULT_TIMEOUT))
-            .build()
-            .expect("Http client failed to build");
-        Ok(Client { host, inner })
+        ClientBuilder::from_host(host).build_sync()
     }
 
     /// Constructs a new stellar client connected to the horizon test network.
@@ -73,6 +80,42 @@ impl Client {
         Self::build(Host::HorizonTest)
     }
 
+    /// Constructs a new stellar client connected to the horizon prod network.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use stellar_client::sync::Client;
+    /// let client = Client::horizon().unwrap();
+    /// ```
+    pub fn horizon() -> Result<Self> {
+        Self::build(Host::HorizonProd)
+    }
+}
+
+impl<T> Client<T>
+where
+    T: Transport,
+{
+    /// Constructs a new stellar synchronous client that sends its requests
+    /// with the given transport instead of over http.
+    ///
+    /// See `Transport` for an example.
+    pub fn from_transport(uri: &str, transport: T) -> Result<Self> {
+        let _: Uri = uri.parse()?;
+        Ok(Self::from_host(Host::Other(uri.to_string()), transport))
+    }
+
+    pub(crate) fn from_host(host: Host, transport: T) -> Self {
+        let network = host.network();
+        Client {
+            transport,
+            host,
+            network,
+            retry: RetryPolicy::never(),
+        }
+    }
+
     /// Returns true if this is a test client.
     ///
     /// ## Examples
@@ -87,33 +130,61 @@ impl Client {
         self.host == Host::HorizonTest
     }
 
-    /// Constructs a new stellar client connected to the horizon prod network.
+    /// Returns true if this is a horizon@stellar client.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use stellar_client::sync::Client;
+    /// # use stellar_client::sync::Client;
     /// let client = Client::horizon().unwrap();
+    /// assert!(client.is_horizon());
+    /// assert!(!client.is_horizon_test());
     /// ```
-    pub fn horizon() -> Result<Self> {
-        Self::build(Host::HorizonProd)
+    pub fn is_horizon(&self) -> bool {
+        self.host == Host::HorizonProd
     }
 
-    /// Returns true if this is a horizon@stellar client.
+    /// Sets the network the horizon server is connected to. Clients connected
+    /// to stellar's horizon servers know their network, but a client for any
+    /// other server needs to be told.
     ///
     /// ## Examples
     ///
     /// ```
-    /// # use stellar_client::sync::Client;
-    /// let client = Client::horizon().unwrap();
-    /// assert!(client.is_horizon());
-    /// assert!(!client.is_horizon_test());
+    /// use stellar_client::{sync::Client, Network};
+    ///
+    /// let client = Client::new("http://localhost:8000")
+    ///     .unwrap()
+    ///     .with_network(Network::new("Standalone Network ; February 2017"));
     /// ```
-    pub fn is_horizon(&self) -> bool {
-        self.host == Host::HorizonProd
+    pub fn with_network(mut self, network: Network) -> Self {
+        self.network = Some(network);
+        self
+    }
+
+    /// The network the horizon server is connected to, if it is known.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use stellar_client::{sync::Client, Network};
+    ///
+    /// let client = Client::horizon_test().unwrap();
+    /// assert_eq!(client.network(), Some(&Network::test()));
+    /// ```
+    pub fn network(&self) -> Option<&Network> {
+        self.network.as_ref()
+    }
+
+    /// Retries requests that horizon answers with a rate limit or a server
+    /// error according to the policy.
+    ///
+    /// See `RetryPolicy` for an example.
+    pub fn with_retry(mut self, policy: RetryPolicy) -> Self {
+        self.retry = policy;
+        self
     }
 
-    #[allow(dead_code)]
     fn uri(&self) -> &str {
         match self.host {
             Host::HorizonTest => HORIZON_TEST_URI,
@@ -140,42 +211,63 @@ impl Client {
         E: IntoRequest,
     {
         let request = endpoint.into_request(&self.uri())?;
-        let request = Self::http_to_reqwest(&request);
-        let response = self.inner.execute(request)?;
-        if response.status().is_success() {
-            let resp: E::Response = serde_json::from_reader(response)?;
-            Ok(resp)
-        } else if response.status().is_client_error() {
-            let e: StellarError = serde_json::from_reader(response)?;
-            Err(Error::BadResponse(e))
-        } else {
-            Err(Error::ServerError)
+        let body = into_body(self.send(request)?)?;
+        let resp: E::Response = serde_json::from_reader(body)?;
+        Ok(resp)
+    }
+
+    /// Sends the request with the transport, and again for as long as the
+    /// retry policy allows it.
+    fn send(&self, mut request: http::Request<Body>) -> Result<http::Response<ResponseBody>> {
+        let mut attempt = 1;
+        loop {
+            let retry = retry::retryable_copy(&request);
+            let response = self.transport.send(request)?;
+            match (retry, self.retry.delay(attempt, &response)) {
+                (Some(retry), Some(delay)) => {
+                    thread::sleep(delay);
+                    request = retry;
+                    attempt += 1;
+                }
+                _ => return Ok(response),
+            }
         }
     }
+}
 
-    fn http_to_reqwest<T>(request: &http::Request<T>) -> reqwest::Request {
-        use http::method::Method;
-        let method = match *request.method() {
-            Method::GET => reqwest::Method::Get,
-            _ => unimplemented!(),
-        };
-        // infalliable because it's already passed the more strenuous http crate
-        // url parsing.
-        let url: reqwest::Url = format!("{}", request.uri()).parse().unwrap();
-        reqwest::Request::new(method, url)
+/// Returns the body of a successful response, or the error horizon answered with.
+fn into_body(response: http::Response<ResponseBody>) -> Result<ResponseBody> {
+    let status = response.status();
+    let body = response.into_body();
+    if status.is_success() {
+        Ok(body)
+    } else if status.is_client_error() {
+        let e: StellarError = serde_json::from_reader(body)?;
+        Err(Error::BadResponse(e))
+    } else {
+        Err(Error::ServerError)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::time::Duration;
     use stellar_error;
 
+    const RATE_LIMIT_EXCEEDED: &str = r#"{
+        "type": "https://stellar.org/horizon-errors/rate_limit_exceeded",
+        "title": "Rate Limit Exceeded",
+        "status": 429,
+        "detail": "The rate limit for the requesting IP address is over its alloted limit."
+    }"#;
+
     #[test]
     fn it_constructs_a_test_client() {
         let client = Client::horizon_test().unwrap();
         assert_eq!(client.host, Host::HorizonTest);
         assert_eq!(client.uri(), "https://horizon-testnet.stellar.org");
+        assert_eq!(client.network(), Some(&Network::test()));
     }
 
     #[test]
@@ -183,6 +275,7 @@ mod tests {
         let client = Client::horizon().unwrap();
         assert_eq!(client.host, Host::HorizonProd);
         assert_eq!(client.uri(), "https://horizon.stellar.org");
+        assert_eq!(client.network(), Some(&Network::public()));
     }
 
     #[test]
@@ -193,6 +286,11 @@ mod tests {
             Host::Other("https://www.google.com".to_string())
         );
         assert_eq!(client.uri(), "https://www.google.com");
+        assert_eq!(client.network(), None);
+
+        let network = Network::new("Private Network");
+        let client = client.with_network(network.clone());
+        assert_eq!(client.network(), Some(&network));
     }
 
     #[test]
@@ -223,4 +321,123 @@ mod tests {
             error => panic!("Client did not return a bad response {:?}", error),
         }
     }
+
+    #[test]
+    fn it_sends_requests_with_the_transport() {
+        use endpoint::account::Details;
+        let id = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+        let replayer = Replayer::new(Cassette::default().with_get(
+            &format!("http://localhost:8000/accounts/{}", id),
+            200,
+            include_str!("../../../fixtures/account.json"),
+        ));
+        let client = Client::from_transport("http://localhost:8000", replayer.clone()).unwrap();
+        assert_eq!(client.network(), None);
+
+        let account = client.request(Details::new(id)).unwrap();
+        assert_eq!(account.id(), id);
+        assert!(replayer.is_finished());
+    }
+
+    #[test]
+    fn it_returns_the_errors_answered_by_the_transport() {
+        use endpoint::account::Details;
+        let id = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+        let uri = format!("/accounts/{}", id);
+        let cassette = Cassette::default()
+            .with_get(
+                &uri,
+                410,
+                include_str!("../../../fixtures/before_history_error.json"),
+            )
+            .with_get(&uri, 500, "");
+        let client =
+            Client::from_transport("http://localhost:8000", Replayer::new(cassette)).unwrap();
+
+        match client.request(Details::new(id)).unwrap_err() {
+            Error::BadResponse(error) => assert!(error.is_before_history()),
+            error => panic!("Client did not return a bad response {:?}", error),
+        }
+        match client.request(Details::new(id)).unwrap_err() {
+            Error::ServerError => (),
+            error => panic!("Client did not return a server error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn it_rejects_a_bad_account_id_before_sending_a_request() {
+        use endpoint::account::Details;
+        use strkey;
+        // The last character is off, so the checksum does not match.
+        let id = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF4";
+        // A request would find no recording and fail as unrecorded.
+        let client =
+            Client::from_transport("http://localhost:8000", Replayer::new(Cassette::default()))
+                .unwrap();
+
+        match client.request(Details::new(id)).unwrap_err() {
+            Error::StrKey(strkey::Error::InvalidChecksum) => (),
+            error => panic!("Client did not reject the account id {:?}", error),
+        }
+    }
+
+    #[test]
+    fn it_retries_rate_limits_and_server_errors() {
+        use endpoint::account::Details;
+        let id = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+        let uri = format!("/accounts/{}", id);
+        let replayer = Replayer::new(
+            Cassette::default()
+                .with_get(&uri, 503, "")
+                .with_get(&uri, 429, RATE_LIMIT_EXCEEDED)
+                .with_header("Retry-After", "0")
+                .with_get(&uri, 200, include_str!("../../../fixtures/account.json")),
+        );
+        let client = Client::from_transport("http://localhost:8000", replayer.clone())
+            .unwrap()
+            .with_retry(RetryPolicy::attempts(3).with_base_delay(Duration::from_millis(1)));
+
+        let account = client.request(Details::new(id)).unwrap();
+        assert_eq!(account.id(), id);
+        assert!(replayer.is_finished());
+    }
+
+    #[test]
+    fn it_returns_the_last_error_once_out_of_attempts() {
+        use endpoint::account::Details;
+        let id = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+        let uri = format!("/accounts/{}", id);
+        let cassette =
+            Cassette::default()
+                .with_get(&uri, 503, "")
+                .with_get(&uri, 429, RATE_LIMIT_EXCEEDED);
+        let replayer = Replayer::new(cassette);
+        let client = Client::from_transport("http://localhost:8000", replayer.clone())
+            .unwrap()
+            .with_retry(RetryPolicy::attempts(2).with_base_delay(Duration::from_millis(1)));
+
+        match client.request(Details::new(id)).unwrap_err() {
+            Error::BadResponse(error) => assert!(error.is_rate_limit_exceeded()),
+            error => panic!("Client did not return the rate limit {:?}", error),
+        }
+        assert!(replayer.is_finished());
+    }
+
+    #[test]
+    fn it_does_not_retry_submissions() {
+        use endpoint::transaction::Submit;
+        let submission = Submit::new("AAAA")
+            .into_request("http://localhost:8000")
+            .unwrap();
+        // A retry would find no second recording and fail as unrecorded.
+        let replayer = Replayer::new(Cassette::default().with(&submission, 503, ""));
+        let client = Client::from_transport("http://localhost:8000", replayer)
+            .unwrap()
+            .with_retry(RetryPolicy::attempts(3).with_base_delay(Duration::from_millis(1)));
+
+        match client.request(Submit::new("AAAA")).unwrap_err() {
+            Error::ServerError => (),
+            error => panic!("Client did not return a server error {:?}", error),
+        }
+    }
 }
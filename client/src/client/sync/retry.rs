@@ -0,0 +1,224 @@
+use chrono::{DateTime, Utc};
+use endpoint::Body;
+use http::{self, header::RETRY_AFTER, Method, StatusCode};
+use ring::rand::{SecureRandom, SystemRandom};
+use std::time::Duration;
+
+/// How the synchronous client retries requests that horizon answers with a
+/// rate limit or a server error. Only gets are retried, since sending them
+/// again does not change anything on horizon.
+///
+/// Before each retry the client waits for as long as horizon asks with the
+/// `Retry-After` header. Without one, it backs off exponentially from the
+/// base delay, doubling it after each attempt up to the max delay, and waits
+/// between half and all of that chosen at random so that clients don't retry
+/// in lockstep.
+///
+/// ## Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use stellar_client::sync::{Client, RetryPolicy};
+///
+/// let client = Client::horizon_test().unwrap().with_retry(
+///     RetryPolicy::attempts(5)
+///         .with_base_delay(Duration::from_millis(500))
+///         .with_max_delay(Duration::from_secs(30)),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::never()
+    }
+}
+
+impl RetryPolicy {
+    /// Sends each request once, which is what clients do by default.
+    pub fn never() -> Self {
+        Self::attempts(1)
+    }
+
+    /// Sends each request up to the number of attempts, waiting a second
+    /// before the first retry and up to a minute before later ones.
+    pub fn attempts(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_secs(1),
+            max_delay: Duration::from_secs(60),
+        }
+    }
+
+    /// Sets the delay that backing off starts from.
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Sets the longest the client waits before a retry. Requests horizon
+    /// asks to retry after longer than this are not retried.
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// The most times a request is sent.
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// How long to wait before sending the request again after the attempt
+    /// was answered with the response, or `None` if it shouldn't be.
+    pub(crate) fn delay<B>(&self, attempt: u32, response: &http::Response<B>) -> Option<Duration> {
+        if attempt >= self.max_attempts || !is_retryable(response.status()) {
+            return None;
+        }
+        match retry_after(response) {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => Some(self.backoff(attempt, jitter())),
+        }
+    }
+
+    /// The delay after the attempt, where jitter between 0 and 1 picks how
+    /// much of the second half of the exponential delay to wait.
+    fn backoff(&self, attempt: u32, jitter: f64) -> Duration {
+        let exponential = 2u32
+            .checked_pow(attempt.saturating_sub(1))
+            .and_then(|factor| self.base_delay.checked_mul(factor))
+            .map(|delay| delay.min(self.max_delay))
+            .unwrap_or(self.max_delay);
+        let millis =
+            exponential.as_secs() * 1000 + u64::from(exponential.subsec_nanos() / 1_000_000);
+        let half = millis / 2;
+        Duration::from_millis(half + ((millis - half) as f64 * jitter) as u64)
+    }
+}
+
+/// Horizon may answer the same request successfully once it's no longer
+/// limiting the rate of requests or failing.
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+/// The delay horizon asked for, in seconds or as the date to retry after.
+fn retry_after<B>(response: &http::Response<B>) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.with_timezone(&Utc).signed_duration_since(Utc::now());
+    Some(delay.to_std().unwrap_or_else(|_| Duration::from_secs(0)))
+}
+
+/// A random fraction between 0 and 1.
+fn jitter() -> f64 {
+    let mut bytes = [0; 4];
+    if SystemRandom::new().fill(&mut bytes).is_err() {
+        return 1.0;
+    }
+    let random = (u32::from(bytes[0]) << 24)
+        | (u32::from(bytes[1]) << 16)
+        | (u32::from(bytes[2]) << 8)
+        | u32::from(bytes[3]);
+    f64::from(random) / f64::from(u32::max_value())
+}
+
+/// A copy of the request to send again, if it is a get without a body.
+pub(crate) fn retryable_copy(request: &http::Request<Body>) -> Option<http::Request<Body>> {
+    match (request.method(), request.body()) {
+        (&Method::GET, &Body::None) => {
+            let mut copy = http::Request::new(Body::None);
+            *copy.uri_mut() = request.uri().clone();
+            *copy.headers_mut() = request.headers().clone();
+            Some(copy)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod retry_policy_tests {
+    use super::*;
+
+    fn response(status: u16, retry_after: Option<&str>) -> http::Response<()> {
+        let mut builder = http::Response::builder();
+        builder.status(status);
+        if let Some(retry_after) = retry_after {
+            builder.header("Retry-After", retry_after);
+        }
+        builder.body(()).unwrap()
+    }
+
+    #[test]
+    fn it_backs_off_exponentially_up_to_the_max_delay() {
+        let policy = RetryPolicy::attempts(10)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_secs(1));
+        assert_eq!(policy.backoff(1, 1.0), Duration::from_millis(100));
+        assert_eq!(policy.backoff(2, 1.0), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3, 1.0), Duration::from_millis(400));
+        assert_eq!(policy.backoff(5, 1.0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(40, 1.0), Duration::from_secs(1));
+        assert_eq!(policy.backoff(3, 0.0), Duration::from_millis(200));
+        assert_eq!(policy.backoff(3, 0.5), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn it_retries_rate_limits_and_server_errors() {
+        let policy = RetryPolicy::attempts(3);
+        for &status in &[429, 500, 502, 503, 504] {
+            let delay = policy.delay(1, &response(status, None)).unwrap();
+            assert!(delay >= Duration::from_millis(500) && delay <= Duration::from_secs(1));
+        }
+        for &status in &[200, 400, 404, 410] {
+            assert_eq!(policy.delay(1, &response(status, None)), None);
+        }
+    }
+
+    #[test]
+    fn it_stops_after_the_max_attempts() {
+        let policy = RetryPolicy::attempts(3);
+        assert!(policy.delay(2, &response(503, None)).is_some());
+        assert_eq!(policy.delay(3, &response(503, None)), None);
+        assert_eq!(RetryPolicy::never().delay(1, &response(503, None)), None);
+    }
+
+    #[test]
+    fn it_honors_retry_after() {
+        let policy = RetryPolicy::attempts(3).with_max_delay(Duration::from_secs(30));
+        assert_eq!(
+            policy.delay(1, &response(429, Some("12"))),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(
+            policy.delay(1, &response(503, Some("Wed, 21 Oct 2015 07:28:00 GMT"))),
+            Some(Duration::from_secs(0))
+        );
+        assert_eq!(policy.delay(1, &response(429, Some("3600"))), None);
+    }
+
+    #[test]
+    fn it_only_copies_gets_without_a_body() {
+        let get = http::Request::get("https://horizon.example.com/ledgers?cursor=12")
+            .header("X-Api-Key", "secret")
+            .body(Body::None)
+            .unwrap();
+        let copy = retryable_copy(&get).unwrap();
+        assert_eq!(copy.method(), Method::GET);
+        assert_eq!(copy.uri(), get.uri());
+        assert_eq!(copy.headers()["x-api-key"], "secret");
+
+        let post = http::Request::post("https://horizon.example.com/transactions")
+            .body(Body::Form(vec![("tx".to_string(), "AAAA".to_string())]))
+            .unwrap();
+        assert!(retryable_copy(&post).is_none());
+    }
+}
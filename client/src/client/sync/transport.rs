@@ -0,0 +1,105 @@
+use client::builder::{self, Config};
+use endpoint::Body;
+use error::{Error, Result};
+use http::{self, header::HeaderMap};
+use reqwest;
+use std::io::Read;
+
+/// The body of a response, which is read as it arrives so that streamed
+/// responses can be consumed before they end.
+pub type ResponseBody = Box<Read + Send>;
+
+/// Sends http requests for the synchronous client and blocks until the
+/// response headers have arrived. The client is generic over its transport,
+/// so requests can be answered without the network, such as by a `Replayer`
+/// of a cassette recorded from horizon.
+pub trait Transport {
+    /// Sends the request and returns the response once its headers have
+    /// arrived. Only a failure to get a response is an error, responses with
+    /// an error status are returned like any other.
+    fn send(&self, request: http::Request<Body>) -> Result<http::Response<ResponseBody>>;
+}
+
+/// The default transport, which sends requests over http(s) with `reqwest`.
+/// It is configured by the `ClientBuilder`.
+#[derive(Debug, Clone)]
+pub struct HttpTransport {
+    inner: reqwest::Client,
+    headers: HeaderMap,
+}
+
+impl HttpTransport {
+    /// Constructs a transport with the default timeout of 60 seconds.
+    pub fn new() -> Result<Self> {
+        Self::configure(&Config::default())
+    }
+
+    pub(crate) fn configure(config: &Config) -> Result<Self> {
+        let mut builder = reqwest::ClientBuilder::new();
+        builder.timeout(config.timeout);
+        if let Some(ref proxy) = config.proxy {
+            builder.proxy(reqwest::Proxy::all(proxy.as_str())?);
+        }
+        for pem in &config.root_certificates {
+            builder.add_root_certificate(reqwest::Certificate::from_pem(pem)?);
+        }
+        Ok(HttpTransport {
+            inner: builder.build()?,
+            headers: config.headers()?,
+        })
+    }
+
+    fn http_to_reqwest(request: &http::Request<Body>) -> Result<reqwest::Request> {
+        use http::method::Method;
+        let method = match *request.method() {
+            Method::GET => reqwest::Method::Get,
+            Method::POST => reqwest::Method::Post,
+            ref method => return Err(Error::UnsupportedMethod(method.clone())),
+        };
+        // infalliable because it's already passed the more strenuous http crate
+        // url parsing.
+        let url: reqwest::Url = format!("{}", request.uri()).parse().unwrap();
+        let mut req = reqwest::Request::new(method, url);
+        for (name, value) in request.headers() {
+            req.headers_mut()
+                .set_raw(name.as_str().to_string(), value.as_bytes().to_vec());
+        }
+        *req.body_mut() = request.body().encode().map(reqwest::Body::from);
+        Ok(req)
+    }
+
+    fn reqwest_to_http(response: reqwest::Response) -> Result<http::Response<ResponseBody>> {
+        let mut builder = http::Response::builder();
+        builder.status(response.status().as_u16());
+        for header in response.headers().iter() {
+            for value in header.raw().iter() {
+                builder.header(header.name(), value);
+            }
+        }
+        Ok(builder.body(Box::new(response) as ResponseBody)?)
+    }
+}
+
+impl Transport for HttpTransport {
+    fn send(&self, mut request: http::Request<Body>) -> Result<http::Response<ResponseBody>> {
+        builder::add_headers(&mut request, &self.headers);
+        let response = self.inner.execute(Self::http_to_reqwest(&request)?)?;
+        Self::reqwest_to_http(response)
+    }
+}
+
+#[cfg(test)]
+mod http_transport_tests {
+    use super::*;
+
+    #[test]
+    fn it_fails_to_convert_methods_horizon_does_not_answer() {
+        let request = http::Request::put("https://horizon.example.com/ledgers")
+            .body(Body::None)
+            .unwrap();
+        match HttpTransport::http_to_reqwest(&request) {
+            Err(Error::UnsupportedMethod(method)) => assert_eq!(method, http::Method::PUT),
+            _ => panic!("Converted a put request"),
+        }
+    }
+}
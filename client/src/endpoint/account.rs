@@ -1,5 +1,10 @@
 //! Contains endpoints for accessing accounts and related information.
-use super::{Body, Cursor, Direction, IntoRequest, Limit, Order, Records};
+//!
+//! Account ids are decoded as strkeys when an endpoint is turned into a request,
+//! so an id with a typo fails with `Error::StrKey` instead of a 404 from horizon.
+use super::{
+    check_account_id, Body, Cursor, Direction, IntoRequest, Limit, Order, Records, Streamable,
+};
 use error::Result;
 use http::{Request, Uri};
 use resources::{Account, Datum, Effect, Offer, Operation, Trade, Transaction};
@@ -42,7 +47,7 @@ impl Details {
     /// ```
     /// use stellar_client::endpoint::account;
     ///
-    /// let details = account::Details::new("abc123");
+    /// let details = account::Details::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
     /// ```
     pub fn new(account_id: &str) -> Self {
         Self {
@@ -55,6 +60,7 @@ impl IntoRequest for Details {
     type Response = Account;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.account_id)?;
         let uri = Uri::from_str(&format!("{}/accounts/{}", host, self.account_id))?;
         let request = Request::get(uri).body(Body::None)?;
         Ok(request)
@@ -90,7 +96,7 @@ impl Data {
     /// ```
     /// use stellar_client::endpoint::account;
     ///
-    /// let data = account::Data::new("abc123", "Food");
+    /// let data = account::Data::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5", "Food");
     /// ```
     pub fn new(account_id: &str, key: &str) -> Self {
         Self {
@@ -104,6 +110,7 @@ impl IntoRequest for Data {
     type Response = Datum;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.account_id)?;
         let uri = Uri::from_str(&format!(
             "{}/accounts/{}/data/{}",
             host, self.account_id, self.key
@@ -119,22 +126,31 @@ mod account_tests {
 
     #[test]
     fn it_can_make_an_account_uri() {
-        let details = Details::new("abc123");
+        let details = Details::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let request = details
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
         assert_eq!(request.uri().host().unwrap(), "horizon-testnet.stellar.org");
-        assert_eq!(request.uri().path(), "/accounts/abc123");
+        assert_eq!(
+            request.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5"
+        );
     }
 
     #[test]
     fn it_can_make_an_account_data_uri() {
-        let data = Data::new("abc123", "key");
+        let data = Data::new(
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5",
+            "key",
+        );
         let request = data
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
         assert_eq!(request.uri().host().unwrap(), "horizon-testnet.stellar.org");
-        assert_eq!(request.uri().path(), "/accounts/abc123/data/key");
+        assert_eq!(
+            request.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/data/key"
+        );
     }
 }
 
@@ -174,6 +190,8 @@ impl_cursor!(Trades);
 impl_limit!(Trades);
 impl_order!(Trades);
 
+impl Streamable for Trades {}
+
 impl Trades {
     /// Creates a new account::Trades endpoint struct. Hand this to the client in order to
     /// request trades for a specific account.
@@ -181,7 +199,7 @@ impl Trades {
     /// ```
     /// use stellar_client::endpoint::account;
     ///
-    /// let trades = account::Trades::new("abc123");
+    /// let trades = account::Trades::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
     /// ```
     pub fn new(account_id: &str) -> Self {
         Self {
@@ -201,6 +219,7 @@ impl IntoRequest for Trades {
     type Response = Records<Trade>;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.account_id)?;
         let mut uri = format!("{}/accounts/{}/trades", host, self.account_id);
         if self.has_query() {
             uri.push_str("?");
@@ -247,32 +266,41 @@ mod trades_tests {
 
     #[test]
     fn it_leaves_off_the_params_if_not_specified() {
-        let trades = Trades::new("abc123");
+        let trades = Trades::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let req = trades
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/trades");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/trades"
+        );
         assert_eq!(req.uri().query(), None);
     }
 
     #[test]
     fn it_can_make_a_trades_uri() {
-        let trades = Trades::new("abc123");
+        let trades = Trades::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let request = trades
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
         assert_eq!(request.uri().host().unwrap(), "horizon-testnet.stellar.org");
-        assert_eq!(request.uri().path(), "/accounts/abc123/trades");
+        assert_eq!(
+            request.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/trades"
+        );
     }
 
     #[test]
     fn it_puts_the_query_params_on_the_uri() {
-        let ep = Trades::new("abc123")
+        let ep = Trades::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5")
             .with_cursor("CURSOR")
             .with_order(Direction::Desc)
             .with_limit(123);
         let req = ep.into_request("https://www.google.com").unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/trades");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/trades"
+        );
         assert_eq!(
             req.uri().query(),
             Some("cursor=CURSOR&order=desc&limit=123")
@@ -281,11 +309,14 @@ mod trades_tests {
 
     #[test]
     fn it_parses_from_a_uri() {
-        let uri: Uri = "/accounts/abc123/trades?cursor=CURSOR&order=desc&limit=123"
+        let uri: Uri = "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/trades?cursor=CURSOR&order=desc&limit=123"
             .parse()
             .unwrap();
         let ep = Trades::try_from(&uri).unwrap();
-        assert_eq!(ep.account_id, "abc123");
+        assert_eq!(
+            ep.account_id,
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5"
+        );
         assert_eq!(ep.limit, Some(123));
         assert_eq!(ep.cursor, Some("CURSOR".to_string()));
         assert_eq!(ep.order, Some(Direction::Desc));
@@ -328,6 +359,8 @@ impl_cursor!(Transactions);
 impl_limit!(Transactions);
 impl_order!(Transactions);
 
+impl Streamable for Transactions {}
+
 impl Transactions {
     /// Creates a new account::Transactions endpoint struct. Hand this to the client in order to
     /// request transactions for a specific account.
@@ -335,7 +368,7 @@ impl Transactions {
     /// ```
     /// use stellar_client::endpoint::account;
     ///
-    /// let txns = account::Transactions::new("abc123");
+    /// let txns = account::Transactions::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
     /// ```
     pub fn new(account_id: &str) -> Self {
         Self {
@@ -355,6 +388,7 @@ impl IntoRequest for Transactions {
     type Response = Records<Transaction>;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.account_id)?;
         let mut uri = format!("{}/accounts/{}/transactions", host, self.account_id);
         if self.has_query() {
             uri.push_str("?");
@@ -401,32 +435,43 @@ mod transactions_tests {
 
     #[test]
     fn it_leaves_off_the_params_if_not_specified() {
-        let transactions = Transactions::new("abc123");
+        let transactions =
+            Transactions::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let req = transactions
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/transactions");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/transactions"
+        );
         assert_eq!(req.uri().query(), None);
     }
 
     #[test]
     fn it_can_make_a_transactions_uri() {
-        let transactions = Transactions::new("abc123");
+        let transactions =
+            Transactions::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let request = transactions
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
         assert_eq!(request.uri().host().unwrap(), "horizon-testnet.stellar.org");
-        assert_eq!(request.uri().path(), "/accounts/abc123/transactions");
+        assert_eq!(
+            request.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/transactions"
+        );
     }
 
     #[test]
     fn it_puts_the_query_params_on_the_uri() {
-        let ep = Transactions::new("abc123")
+        let ep = Transactions::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5")
             .with_cursor("CURSOR")
             .with_order(Direction::Desc)
             .with_limit(123);
         let req = ep.into_request("https://www.google.com").unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/transactions");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/transactions"
+        );
         assert_eq!(
             req.uri().query(),
             Some("cursor=CURSOR&order=desc&limit=123")
@@ -435,11 +480,14 @@ mod transactions_tests {
 
     #[test]
     fn it_parses_from_a_uri() {
-        let uri: Uri = "/accounts/abc123/transactions?cursor=CURSOR&order=desc&limit=123"
+        let uri: Uri = "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/transactions?cursor=CURSOR&order=desc&limit=123"
             .parse()
             .unwrap();
         let ep = Transactions::try_from(&uri).unwrap();
-        assert_eq!(ep.account_id, "abc123");
+        assert_eq!(
+            ep.account_id,
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5"
+        );
         assert_eq!(ep.limit, Some(123));
         assert_eq!(ep.cursor, Some("CURSOR".to_string()));
         assert_eq!(ep.order, Some(Direction::Desc));
@@ -482,6 +530,8 @@ impl_cursor!(Effects);
 impl_limit!(Effects);
 impl_order!(Effects);
 
+impl Streamable for Effects {}
+
 impl Effects {
     /// Creates a new account::Effects endpoint struct. Hand this to the client in order to
     /// request effects for a specific account.
@@ -489,7 +539,7 @@ impl Effects {
     /// ```
     /// use stellar_client::endpoint::account;
     ///
-    /// let effects = account::Effects::new("abc123");
+    /// let effects = account::Effects::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
     /// ```
     pub fn new(account_id: &str) -> Self {
         Self {
@@ -509,6 +559,7 @@ impl IntoRequest for Effects {
     type Response = Records<Effect>;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.account_id)?;
         let mut uri = format!("{}/accounts/{}/effects", host, self.account_id);
         if self.has_query() {
             uri.push_str("?");
@@ -555,44 +606,56 @@ mod effects_tests {
 
     #[test]
     fn it_leaves_off_the_params_if_not_specified() {
-        let effects = Effects::new("abc123");
+        let effects = Effects::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let req = effects
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/effects");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/effects"
+        );
         assert_eq!(req.uri().query(), None);
     }
 
     #[test]
     fn it_can_make_a_actions_uri() {
-        let effects = Effects::new("abc123");
+        let effects = Effects::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let request = effects
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
         assert_eq!(request.uri().host().unwrap(), "horizon-testnet.stellar.org");
-        assert_eq!(request.uri().path(), "/accounts/abc123/effects");
+        assert_eq!(
+            request.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/effects"
+        );
     }
 
     #[test]
     fn it_puts_the_query_params_on_the_uri() {
-        let ep = Effects::new("abc123")
+        let ep = Effects::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5")
             .with_cursor("CURSOR")
             .with_order(Direction::Asc)
             .with_limit(123);
         let req = ep
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/effects");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/effects"
+        );
         assert_eq!(req.uri().query(), Some("cursor=CURSOR&order=asc&limit=123"));
     }
 
     #[test]
     fn it_parses_from_a_uri() {
-        let uri: Uri = "/accounts/abc123/effects?cursor=CURSOR&order=desc&limit=123"
+        let uri: Uri = "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/effects?cursor=CURSOR&order=desc&limit=123"
             .parse()
             .unwrap();
         let ep = Effects::try_from(&uri).unwrap();
-        assert_eq!(ep.account_id, "abc123");
+        assert_eq!(
+            ep.account_id,
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5"
+        );
         assert_eq!(ep.limit, Some(123));
         assert_eq!(ep.cursor, Some("CURSOR".to_string()));
         assert_eq!(ep.order, Some(Direction::Desc));
@@ -637,13 +700,15 @@ impl_cursor!(Operations);
 impl_limit!(Operations);
 impl_order!(Operations);
 
+impl Streamable for Operations {}
+
 impl Operations {
     /// Creates a new account::Operations endpoint struct.
     ///
     /// ```
     /// use stellar_client::endpoint::account;
     ///
-    /// let txns = account::Operations::new("abc123");
+    /// let txns = account::Operations::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
     /// ```
     pub fn new(account_id: &str) -> Operations {
         Operations {
@@ -663,6 +728,7 @@ impl IntoRequest for Operations {
     type Response = Records<Operation>;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.account_id)?;
         let mut uri = format!("{}/accounts/{}/operations", host, self.account_id);
 
         if self.has_query() {
@@ -710,20 +776,26 @@ mod ledger_operations_tests {
 
     #[test]
     fn it_leaves_off_the_params_if_not_specified() {
-        let ep = Operations::new("abc123");
+        let ep = Operations::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let req = ep.into_request("https://www.google.com").unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/operations");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/operations"
+        );
         assert_eq!(req.uri().query(), None);
     }
 
     #[test]
     fn it_puts_the_query_params_on_the_uri() {
-        let ep = Operations::new("abc123")
+        let ep = Operations::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5")
             .with_cursor("CURSOR")
             .with_limit(123)
             .with_order(Direction::Desc);
         let req = ep.into_request("https://www.google.com").unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/operations");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/operations"
+        );
         assert_eq!(
             req.uri().query(),
             Some("order=desc&cursor=CURSOR&limit=123")
@@ -732,11 +804,14 @@ mod ledger_operations_tests {
 
     #[test]
     fn it_parses_from_a_uri() {
-        let uri: Uri = "/accounts/abc123/operations?cursor=CURSOR&order=desc&limit=123"
+        let uri: Uri = "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/operations?cursor=CURSOR&order=desc&limit=123"
             .parse()
             .unwrap();
         let ep = Operations::try_from(&uri).unwrap();
-        assert_eq!(ep.account_id, "abc123");
+        assert_eq!(
+            ep.account_id,
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5"
+        );
         assert_eq!(ep.limit, Some(123));
         assert_eq!(ep.cursor, Some("CURSOR".to_string()));
         assert_eq!(ep.order, Some(Direction::Desc));
@@ -784,6 +859,8 @@ impl_cursor!(Payments);
 impl_limit!(Payments);
 impl_order!(Payments);
 
+impl Streamable for Payments {}
+
 impl Payments {
     /// Creates a new account::Payments endpoint struct. Hand this to the client in order to
     /// request payment operations for a specific account.
@@ -791,7 +868,7 @@ impl Payments {
     /// ```
     /// use stellar_client::endpoint::account;
     ///
-    /// let payments = account::Payments::new("abc123");
+    /// let payments = account::Payments::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
     /// ```
     pub fn new(account_id: &str) -> Self {
         Self {
@@ -811,6 +888,7 @@ impl IntoRequest for Payments {
     type Response = Records<Operation>;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.account_id)?;
         let mut uri = format!("{}/accounts/{}/payments", host, self.account_id);
         if self.has_query() {
             uri.push_str("?");
@@ -857,23 +935,29 @@ mod payments_tests {
 
     #[test]
     fn it_can_make_a_payments_uri() {
-        let payments = Payments::new("abc123");
+        let payments = Payments::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let request = payments
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
         assert_eq!(request.uri().host().unwrap(), "horizon-testnet.stellar.org");
-        assert_eq!(request.uri().path(), "/accounts/abc123/payments");
+        assert_eq!(
+            request.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/payments"
+        );
         assert_eq!(request.uri().query(), None);
     }
 
     #[test]
     fn it_puts_the_query_params_on_the_uri() {
-        let ep = Payments::new("abc123")
+        let ep = Payments::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5")
             .with_cursor("CURSOR")
             .with_order(Direction::Desc)
             .with_limit(123);
         let req = ep.into_request("https://www.google.com").unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/payments");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/payments"
+        );
         assert_eq!(
             req.uri().query(),
             Some("cursor=CURSOR&order=desc&limit=123")
@@ -882,11 +966,14 @@ mod payments_tests {
 
     #[test]
     fn it_parses_from_a_uri() {
-        let uri: Uri = "/accounts/abc123/payments?cursor=CURSOR&order=desc&limit=123"
+        let uri: Uri = "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/payments?cursor=CURSOR&order=desc&limit=123"
             .parse()
             .unwrap();
         let ep = Payments::try_from(&uri).unwrap();
-        assert_eq!(ep.account_id, "abc123");
+        assert_eq!(
+            ep.account_id,
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5"
+        );
         assert_eq!(ep.limit, Some(123));
         assert_eq!(ep.cursor, Some("CURSOR".to_string()));
         assert_eq!(ep.order, Some(Direction::Desc));
@@ -936,7 +1023,7 @@ impl Offers {
     /// ```
     /// use stellar_client::endpoint::account;
     ///
-    /// let offers = account::Offers::new("abc123");
+    /// let offers = account::Offers::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
     /// ```
     pub fn new(account_id: &str) -> Self {
         Self {
@@ -956,6 +1043,7 @@ impl IntoRequest for Offers {
     type Response = Records<Offer>;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.account_id)?;
         let mut uri = format!("{}/accounts/{}/offers", host, self.account_id);
         if self.has_query() {
             uri.push_str("?");
@@ -1002,23 +1090,29 @@ mod offers_tests {
 
     #[test]
     fn it_can_make_an_offers_uri() {
-        let payments = Offers::new("abc123");
+        let payments = Offers::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
         let request = payments
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
         assert_eq!(request.uri().host().unwrap(), "horizon-testnet.stellar.org");
-        assert_eq!(request.uri().path(), "/accounts/abc123/offers");
+        assert_eq!(
+            request.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/offers"
+        );
         assert_eq!(request.uri().query(), None);
     }
 
     #[test]
     fn it_puts_the_query_params_on_the_uri() {
-        let ep = Offers::new("abc123")
+        let ep = Offers::new("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5")
             .with_cursor("CURSOR")
             .with_order(Direction::Desc)
             .with_limit(123);
         let req = ep.into_request("https://www.google.com").unwrap();
-        assert_eq!(req.uri().path(), "/accounts/abc123/offers");
+        assert_eq!(
+            req.uri().path(),
+            "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/offers"
+        );
         assert_eq!(
             req.uri().query(),
             Some("cursor=CURSOR&order=desc&limit=123")
@@ -1027,11 +1121,14 @@ mod offers_tests {
 
     #[test]
     fn it_parses_from_a_uri() {
-        let uri: Uri = "/accounts/abc123/offers?cursor=CURSOR&order=desc&limit=123"
+        let uri: Uri = "/accounts/GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5/offers?cursor=CURSOR&order=desc&limit=123"
             .parse()
             .unwrap();
         let ep = Offers::try_from(&uri).unwrap();
-        assert_eq!(ep.account_id, "abc123");
+        assert_eq!(
+            ep.account_id,
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5"
+        );
         assert_eq!(ep.limit, Some(123));
         assert_eq!(ep.cursor, Some("CURSOR".to_string()));
         assert_eq!(ep.order, Some(Direction::Desc));
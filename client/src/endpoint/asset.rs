@@ -1,5 +1,5 @@
 //! Contains endpoints for assets and related information to specific assets.
-use super::{Body, Cursor, Direction, IntoRequest, Limit, Order, Records};
+use super::{check_account_id, Body, Cursor, Direction, IntoRequest, Limit, Order, Records};
 use error::Result;
 use http::{Request, Uri};
 use resources::Asset;
@@ -102,6 +102,7 @@ impl IntoRequest for All {
             }
 
             if let Some(issuer) = self.issuer {
+                check_account_id(&issuer)?;
                 uri.push_str(&format!("asset_issuer={}&", issuer));
             }
 
@@ -153,7 +154,7 @@ mod all_assets_tests {
     fn it_puts_the_query_params_on_the_uri() {
         let ep = All::default()
             .with_asset_code("CODE")
-            .with_asset_issuer("ISSUER")
+            .with_asset_issuer("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5")
             .with_cursor("CURSOR")
             .with_limit(123)
             .with_order(Direction::Desc);
@@ -161,7 +162,10 @@ mod all_assets_tests {
         assert_eq!(req.uri().path(), "/assets");
         assert_eq!(
             req.uri().query(),
-            Some("asset_code=CODE&asset_issuer=ISSUER&order=desc&cursor=CURSOR&limit=123")
+            Some(
+                "asset_code=CODE&asset_issuer=GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5&\
+                 order=desc&cursor=CURSOR&limit=123"
+            )
         );
     }
 
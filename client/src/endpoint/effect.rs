@@ -1,5 +1,5 @@
 //! Contains the endpoint for all effects.
-use super::{Body, Cursor, Direction, IntoRequest, Limit, Order, Records};
+use super::{Body, Cursor, Direction, IntoRequest, Limit, Order, Records, Streamable};
 use error::Result;
 use http::{Request, Uri};
 use resources::Effect;
@@ -38,6 +38,8 @@ impl_cursor!(All);
 impl_limit!(All);
 impl_order!(All);
 
+impl Streamable for All {}
+
 impl All {
     fn has_query(&self) -> bool {
         self.order.is_some() || self.cursor.is_some() || self.limit.is_some()
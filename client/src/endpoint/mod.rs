@@ -19,7 +19,9 @@
 //! ```
 use error::Result;
 use http;
+use resources::AssetIdentifier;
 use serde::de::DeserializeOwned;
+use strkey::{self, Version};
 
 #[macro_use]
 mod cursor;
@@ -29,6 +31,7 @@ mod limit;
 mod order;
 
 mod records;
+mod streamable;
 
 pub mod account;
 pub mod asset;
@@ -44,12 +47,66 @@ pub use self::cursor::Cursor;
 pub use self::limit::Limit;
 pub use self::order::{Direction, Order, ParseDirectionError};
 pub use self::records::Records;
+pub use self::streamable::Streamable;
 
 /// Represents the body of a request to an IntoRequest.
 #[derive(Debug)]
 pub enum Body {
     /// Declares that the endpoint does not have a body.
     None,
+    /// Declares that the endpoint posts url encoded form fields.
+    Form(Vec<(String, String)>),
+}
+
+impl Body {
+    /// Encodes the body as it should be sent to horizon. Returns `None`
+    /// when there is nothing to send.
+    pub(crate) fn encode(&self) -> Option<String> {
+        match *self {
+            Body::None => None,
+            Body::Form(ref fields) => Some(
+                fields
+                    .iter()
+                    .map(|&(ref name, ref value)| {
+                        format!("{}={}", form_encode(name), form_encode(value))
+                    })
+                    .collect::<Vec<_>>()
+                    .join("&"),
+            ),
+        }
+    }
+}
+
+/// Percent encodes a value for use in an `application/x-www-form-urlencoded` body.
+fn form_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'*' => {
+                encoded.push(byte as char)
+            }
+            b' ' => encoded.push('+'),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// Checks that an account id is a public key strkey, so that a typo fails
+/// before any request is made instead of coming back from horizon as a 404.
+fn check_account_id(account_id: &str) -> Result<()> {
+    strkey::decode(Version::PublicKey, account_id)?;
+    Ok(())
+}
+
+/// Checks the issuer of an asset the same way as an account id. Native assets
+/// have no issuer to check.
+fn check_asset(asset: &AssetIdentifier) -> Result<()> {
+    if asset.is_native() {
+        Ok(())
+    } else {
+        check_account_id(asset.issuer())
+    }
 }
 
 /// Declares the definition of a stellar endpoint and the return type.
@@ -1,5 +1,5 @@
 //! Contains the endpoint for fetching the orderbook for a given asset pair
-use super::{Body, IntoRequest, Limit};
+use super::{check_asset, Body, IntoRequest, Limit};
 use error::Result;
 use http::{Request, Uri};
 use resources::{AssetIdentifier, Orderbook};
@@ -63,6 +63,8 @@ impl IntoRequest for Details {
     type Response = Orderbook;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_asset(&self.base_asset)?;
+        check_asset(&self.counter_asset)?;
         let mut uri_str = format!("{}/order_book?", host);
 
         uri_str.push_str(&format!(
@@ -112,13 +114,16 @@ mod details_tests {
     #[test]
     fn it_can_make_an_account_uri() {
         let xlm = AssetIdentifier::native();
-        let foxcoin = AssetIdentifier::alphanum4("USD", "FantasticMrFox");
+        let foxcoin = AssetIdentifier::alphanum4(
+            "USD",
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5",
+        );
         let details = Details::for_asset_pair(xlm, foxcoin);
         let request = details
             .into_request("https://horizon-testnet.stellar.org")
             .unwrap();
         assert_eq!(request.uri().host().unwrap(), "horizon-testnet.stellar.org");
         assert_eq!(request.uri().path(), "/order_book");
-        assert_eq!(request.uri().query().unwrap(), "selling_asset_type=native&buying_asset_type=credit_alphanum4&buying_asset_code=USD&buying_asset_issuer=FantasticMrFox");
+        assert_eq!(request.uri().query().unwrap(), "selling_asset_type=native&buying_asset_type=credit_alphanum4&buying_asset_code=USD&buying_asset_issuer=GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5");
     }
 }
@@ -1,5 +1,8 @@
 //! Contains the endpoint for all payment operations.
-use super::{Body, Cursor, Direction, IntoRequest, Limit, Order, Records};
+use super::{
+    check_account_id, check_asset, Body, Cursor, Direction, IntoRequest, Limit, Order, Records,
+    Streamable,
+};
 use error::Result;
 use http::{Request, Uri};
 use resources::{Amount, AssetIdentifier, Operation, PaymentPath};
@@ -37,6 +40,8 @@ impl_cursor!(All);
 impl_limit!(All);
 impl_order!(All);
 
+impl Streamable for All {}
+
 impl All {
     fn has_query(&self) -> bool {
         self.order.is_some() || self.cursor.is_some() || self.limit.is_some()
@@ -123,7 +128,8 @@ mod all_payments_tests {
 /// This endpoint represents a search for a series of assets through which to route a payment,
 /// from source asset (debited from payer) to destination asset (credited to payee).
 /// The endpoint will return any payment paths using assets available to a source account to the
-/// desired destination asset.
+/// desired destination asset. Both accounts and the issuer of the destination asset must be
+/// valid account ids, or the request fails with `Error::StrKey` before it is sent.
 ///
 /// <https://www.stellar.org/developers/horizon/reference/endpoints/path-finding.html>
 ///
@@ -209,6 +215,9 @@ impl IntoRequest for FindPath {
     type Response = Records<PaymentPath>;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_account_id(&self.source_account)?;
+        check_account_id(&self.destination_account)?;
+        check_asset(&self.destination_asset)?;
         let mut uri = format!(
             "{}/paths?source_account={}&destination_account={}&\
              destination_amount={}&destination_asset_type={}",
@@ -250,12 +259,14 @@ impl TryFromUri for FindPath {
 #[cfg(test)]
 mod find_path_tests {
     use super::*;
+    use error::Error;
+    use strkey;
 
     #[test]
     fn it_can_make_a_paths_uri_for_native_assets() {
         let paths = FindPath::new(
-            "account_a",
-            "account_b",
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5",
+            "GA6HCMBLTZS5VYYBCATRBRZ3BZJMAFUDKYYF6AH6MVCMGWMRDNSWJPIH",
             AssetIdentifier::new("native", None, None).unwrap(),
             Amount::new(1000),
         );
@@ -267,7 +278,8 @@ mod find_path_tests {
         assert_eq!(
             request.uri().query(),
             Some(
-                "source_account=account_a&destination_account=account_b&\
+                "source_account=GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5&\
+                 destination_account=GA6HCMBLTZS5VYYBCATRBRZ3BZJMAFUDKYYF6AH6MVCMGWMRDNSWJPIH&\
                  destination_amount=0.0001000&destination_asset_type=native"
             )
         );
@@ -276,12 +288,12 @@ mod find_path_tests {
     #[test]
     fn it_can_make_a_paths_uri_for_non_native_assets() {
         let paths = FindPath::new(
-            "account_a",
-            "account_b",
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5",
+            "GA6HCMBLTZS5VYYBCATRBRZ3BZJMAFUDKYYF6AH6MVCMGWMRDNSWJPIH",
             AssetIdentifier::new(
                 "credit_alphanum4",
                 Some("codx".to_string()),
-                Some("me".to_string()),
+                Some("GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5".to_string()),
             ).unwrap(),
             Amount::new(1000),
         );
@@ -293,10 +305,26 @@ mod find_path_tests {
         assert_eq!(
             request.uri().query(),
             Some(
-                "source_account=account_a&destination_account=account_b&\
+                "source_account=GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5&\
+                 destination_account=GA6HCMBLTZS5VYYBCATRBRZ3BZJMAFUDKYYF6AH6MVCMGWMRDNSWJPIH&\
                  destination_amount=0.0001000&destination_asset_type=credit_alphanum4&\
-                 destination_asset_code=codx&destination_asset_issuer=me"
+                 destination_asset_code=codx&\
+                 destination_asset_issuer=GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5"
             )
         );
     }
+
+    #[test]
+    fn it_rejects_an_asset_issuer_that_is_not_an_account_id() {
+        let paths = FindPath::new(
+            "GATLAI2D7SSH6PE3HXTDPTRM4RE5VRK6HGA63K5EWP75PSANCZRFDNB5",
+            "GA6HCMBLTZS5VYYBCATRBRZ3BZJMAFUDKYYF6AH6MVCMGWMRDNSWJPIH",
+            AssetIdentifier::alphanum4("codx", "me"),
+            Amount::new(1000),
+        );
+        match paths.into_request("https://horizon-testnet.stellar.org") {
+            Err(Error::StrKey(strkey::Error::InvalidLength)) => (),
+            result => panic!("The issuer was not rejected {:?}", result),
+        }
+    }
 }
@@ -0,0 +1,21 @@
+use super::{Cursor, IntoRequest};
+
+/// Declares that an endpoint can be streamed from horizon as server sent events.
+/// Streams are resumed from the paging token of the last record received, so
+/// an endpoint must have a cursor in order to be streamed.
+///
+/// <https://www.stellar.org/developers/horizon/reference/streaming.html>
+///
+/// ## Example
+///
+/// ```
+/// use stellar_client::endpoint::{payment, Cursor, Streamable};
+///
+/// fn resume<E: Streamable>(endpoint: E, paging_token: &str) -> E {
+///     endpoint.with_cursor(paging_token)
+/// }
+///
+/// let payments = resume(payment::All::default(), "12884905984");
+/// assert_eq!(payments.cursor(), Some("12884905984"));
+/// ```
+pub trait Streamable: IntoRequest + Cursor + Clone {}
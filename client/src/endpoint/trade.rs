@@ -1,5 +1,5 @@
 //! Contains the endpoint for all trades.
-use super::{Body, Cursor, Direction, IntoRequest, Limit, Order, Records};
+use super::{check_asset, Body, Cursor, Direction, IntoRequest, Limit, Order, Records, Streamable};
 use error::Result;
 use http::{Request, Uri};
 use resources::{AssetIdentifier, Trade, TradeAggregation};
@@ -151,6 +151,8 @@ impl_cursor!(All);
 impl_limit!(All);
 impl_order!(All);
 
+impl Streamable for All {}
+
 impl All {
     /// Fetches the record for a specified trade pair.
     ///
@@ -220,6 +222,8 @@ impl IntoRequest for All {
             uri.push_str("?");
 
             if let Some(asset_pair) = self.asset_pair {
+                check_asset(&asset_pair.base)?;
+                check_asset(&asset_pair.counter)?;
                 uri.push_str(&asset_pair.to_param());
             }
 
@@ -496,6 +500,8 @@ impl IntoRequest for Aggregations {
     type Response = Records<TradeAggregation>;
 
     fn into_request(self, host: &str) -> Result<Request<Body>> {
+        check_asset(&self.asset_pair.base)?;
+        check_asset(&self.asset_pair.counter)?;
         let mut uri = format!("{}/trade_aggregations?", host);
 
         uri.push_str(&self.asset_pair.to_param());
@@ -1,8 +1,8 @@
 //! Contains endpoints for transactions and related information.
-use super::{Body, Cursor, Direction, IntoRequest, Limit, Order, Records};
+use super::{Body, Cursor, Direction, IntoRequest, Limit, Order, Records, Streamable};
 use error::Result;
-use http::{Request, Uri};
-use resources::{Effect, Operation, Transaction};
+use http::{header, Request, Uri};
+use resources::{Effect, Operation, Transaction, TransactionSubmission};
 use std::str::FromStr;
 use uri::{self, TryFromUri, UriWrap};
 
@@ -37,6 +37,8 @@ impl_cursor!(All);
 impl_limit!(All);
 impl_order!(All);
 
+impl Streamable for All {}
+
 impl All {
     fn has_query(&self) -> bool {
         self.order.is_some() || self.cursor.is_some() || self.limit.is_some()
@@ -179,6 +181,108 @@ mod transaction_details_tests {
     }
 }
 
+/// Submits a transaction to the stellar network. The transaction must be a
+/// signed TransactionEnvelope xdr struct, encoded in base64. Horizon responds
+/// once the transaction has been applied to a ledger, or fails with a
+/// `transaction_failed` error whose result codes explain why.
+///
+/// <https://www.stellar.org/developers/horizon/reference/endpoints/transactions-create.html>
+///
+/// ## Example
+///
+/// The example submits to the mock horizon of the `mock` feature, so that
+/// nothing is written to the network.
+///
+/// ```
+/// # extern crate http;
+/// # extern crate stellar_client;
+/// # #[cfg(feature = "mock")]
+/// # fn main() {
+/// use stellar_client::endpoint::transaction;
+/// use stellar_client::mock::{Response, Server};
+/// use stellar_client::sync::Client;
+/// use stellar_client::Error;
+///
+/// let server = Server::start().unwrap();
+/// let client = Client::new(server.uri()).unwrap();
+/// # let envelope_xdr =
+/// #     "AAAAAH2Hmt1JWMfqAdUlDeyUtO9V8zPqJ0aLG8KrZyXv78QGAAAAZAAIgb4AAtRiAAAAAAAAAAEAAAAAAAAAAQ\
+/// #      AAAAAAAAABAAAAAJZgy/0KAk+3JQwG8hPGBNTZVGew2Joi1TwkVBdwPn9QAAAAAAAAAAA7mUNgAAAAAAAAAAHv\
+/// #      78QGAAAAQITCXzWfgHgAjF3djx1VK9JK08UypfpftzFoyNXv7A0Agau/ur/3/+ZZtQb8xSsao8yVAsTiV4ttiT\
+/// #      /HqfvvlAk=";
+/// let submission = client.request(transaction::Submit::new(envelope_xdr)).unwrap();
+/// assert_eq!(submission.ledger(), 16751283);
+///
+/// server.mount(http::Method::POST, "/transactions", Response::transaction_failed());
+/// match client.request(transaction::Submit::new(envelope_xdr)) {
+///     Err(Error::BadResponse(ref error)) if error.is_transaction_failed() => {
+///         let codes = error.result_codes().unwrap();
+///         assert_eq!(codes.transaction(), "tx_failed");
+///     }
+///     _ => panic!("expected the transaction to fail"),
+/// }
+/// # }
+/// # #[cfg(not(feature = "mock"))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone)]
+pub struct Submit {
+    envelope_xdr: String,
+}
+
+impl Submit {
+    /// Creates a new endpoint that submits the base64 encoded transaction envelope.
+    pub fn new(envelope_xdr: &str) -> Self {
+        Submit {
+            envelope_xdr: envelope_xdr.to_string(),
+        }
+    }
+}
+
+impl IntoRequest for Submit {
+    type Response = TransactionSubmission;
+
+    fn into_request(self, host: &str) -> Result<Request<Body>> {
+        let uri = Uri::from_str(&format!("{}/transactions", host))?;
+        let request = Request::post(uri)
+            .header(header::CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(Body::Form(vec![("tx".to_string(), self.envelope_xdr)]))?;
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod submit_tests {
+    use super::*;
+    use http::Method;
+
+    #[test]
+    fn it_posts_to_the_transactions_uri() {
+        let submit = Submit::new("AAAA");
+        let request = submit
+            .into_request("https://horizon-testnet.stellar.org")
+            .unwrap();
+        assert_eq!(request.method(), &Method::POST);
+        assert_eq!(request.uri().path(), "/transactions");
+        assert_eq!(
+            request.headers()[header::CONTENT_TYPE],
+            "application/x-www-form-urlencoded"
+        );
+    }
+
+    #[test]
+    fn it_form_encodes_the_envelope() {
+        let submit = Submit::new("AAAAAH2H+/0KAk==");
+        let request = submit
+            .into_request("https://horizon-testnet.stellar.org")
+            .unwrap();
+        assert_eq!(
+            request.body().encode(),
+            Some("tx=AAAAAH2H%2B%2F0KAk%3D%3D".to_string())
+        );
+    }
+}
+
 /// ```
 /// use stellar_client::sync::Client;
 /// use stellar_client::endpoint::{transaction, effect, Limit};
@@ -1,5 +1,6 @@
 //! Error and result module
 use super::StellarError;
+use builder;
 use http;
 use hyper;
 use hyper::error::UriError;
@@ -7,7 +8,10 @@ use reqwest;
 use serde_json;
 use std::error::Error as StdError;
 use std::fmt;
+use std::io;
+use strkey;
 use uri;
+use xdr;
 
 /// A set of errors for use in the client
 #[derive(Debug)]
@@ -16,6 +20,10 @@ pub enum Error {
     BadUri,
     /// Was unable to resolve ssl configuration
     BadSSL,
+    /// The client was configured with a setting it can't honor.
+    BadConfig(&'static str),
+    /// The system was unable to generate random bytes for a key.
+    BadRandom,
     /// Placeholder for errors that come back from the client.
     BadResponse(StellarError),
     /// Server error detected
@@ -34,6 +42,19 @@ pub enum Error {
     Reqwest(reqwest::Error),
     /// Errors that occur when converting from uri into something else.
     TryFromUri(uri::Error),
+    /// A key was not a valid strkey.
+    StrKey(strkey::Error),
+    /// A transaction could not be built.
+    Build(builder::Error),
+    /// An error occurred while decoding xdr.
+    Xdr(xdr::Error),
+    /// An error occurred while reading or writing, such as a cassette file.
+    Io(io::Error),
+    /// A request was replayed that the cassette has no recording of.
+    Unrecorded,
+    /// A request was sent with a method the transport can't send. Horizon
+    /// only answers gets and posts.
+    UnsupportedMethod(http::Method),
     #[doc(hidden)]
     __Nonexhaustive,
 }
@@ -46,11 +67,19 @@ impl StdError for Error {
         match *self {
             Error::BadUri => "An invalid uri was specified when constructing the client",
             Error::BadSSL => "Unable to resolve tls",
+            Error::BadConfig(reason) => reason,
+            Error::BadRandom => "Unable to generate random bytes",
             Error::Http(ref inner) => inner.description(),
             Error::Reqwest(ref inner) => inner.description(),
             Error::JsonParseError(ref inner) => inner.description(),
             Error::BadResponse(ref inner) => inner.description(),
             Error::TryFromUri(ref inner) => inner.description(),
+            Error::StrKey(ref inner) => inner.description(),
+            Error::Build(ref inner) => inner.description(),
+            Error::Xdr(ref inner) => inner.description(),
+            Error::Io(ref inner) => inner.description(),
+            Error::Unrecorded => "The cassette has no recording of the request",
+            Error::UnsupportedMethod(_) => "Only get and post requests can be sent",
             Error::ServerError => "An unknown error on the server has occurred",
             Error::__Nonexhaustive => unreachable!(),
         }
@@ -111,6 +140,30 @@ impl From<uri::Error> for Error {
     }
 }
 
+impl From<strkey::Error> for Error {
+    fn from(inner: strkey::Error) -> Self {
+        Error::StrKey(inner)
+    }
+}
+
+impl From<builder::Error> for Error {
+    fn from(inner: builder::Error) -> Self {
+        Error::Build(inner)
+    }
+}
+
+impl From<xdr::Error> for Error {
+    fn from(inner: xdr::Error) -> Self {
+        Error::Xdr(inner)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(inner: io::Error) -> Self {
+        Error::Io(inner)
+    }
+}
+
 #[cfg(test)]
 mod error_coversion_tests {
     use super::*;
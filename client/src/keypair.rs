@@ -0,0 +1,209 @@
+//! Ed25519 keypairs for signing and verifying on behalf of accounts.
+use error::{Error, Result};
+use ring::rand::{SecureRandom, SystemRandom};
+use ring::signature::{self, Ed25519KeyPair};
+use std::fmt;
+use strkey::{self, Version};
+use untrusted::Input;
+use xdr::{DecoratedSignature, PublicKey};
+
+/// An ed25519 keypair, used to sign transactions on behalf of an account.
+///
+/// The keypair is derived from a 32 byte secret seed. The seed is usually
+/// shared as a strkey starting with an `S`, and the public key is the id of
+/// the account, which starts with a `G`.
+///
+/// ## Example
+/// ```
+/// use stellar_client::Keypair;
+///
+/// let keypair = Keypair::random().unwrap();
+/// let restored = Keypair::from_secret_seed(&keypair.secret_seed()).unwrap();
+/// assert_eq!(keypair.account_id(), restored.account_id());
+///
+/// let signature = keypair.sign(b"hello");
+/// assert!(restored.verify(b"hello", &signature));
+/// ```
+pub struct Keypair {
+    seed: [u8; 32],
+    public_key: [u8; 32],
+    key_pair: Ed25519KeyPair,
+}
+
+impl Keypair {
+    /// Generates a new keypair from a random seed.
+    pub fn random() -> Result<Keypair> {
+        let mut seed = [0; 32];
+        SystemRandom::new()
+            .fill(&mut seed)
+            .map_err(|_| Error::BadRandom)?;
+        Ok(Keypair::from_raw_seed(seed))
+    }
+
+    /// Loads a keypair from a secret seed encoded as a strkey, such as
+    /// `SBMVFTVKOTJWEROPLCAG4OBSXYC56U5LAFKXA636MO2DJRSFU6BBBXUV`.
+    pub fn from_secret_seed(secret_seed: &str) -> Result<Keypair> {
+        let seed = strkey::decode(Version::Seed, secret_seed)?;
+        Ok(Keypair::from_raw_seed(seed))
+    }
+
+    /// Derives a keypair from the raw bytes of a seed.
+    pub fn from_raw_seed(seed: [u8; 32]) -> Keypair {
+        // Any 32 bytes make a valid ed25519 seed, so this can't fail.
+        let key_pair = Ed25519KeyPair::from_seed_unchecked(Input::from(&seed))
+            .expect("A 32 byte seed is always valid");
+        let mut public_key = [0; 32];
+        public_key.copy_from_slice(key_pair.public_key_bytes());
+        Keypair {
+            seed,
+            public_key,
+            key_pair,
+        }
+    }
+
+    /// The secret seed encoded as a strkey. Anyone with the seed can sign on
+    /// behalf of the account, so take care where it is written.
+    pub fn secret_seed(&self) -> String {
+        strkey::encode(Version::Seed, &self.seed)
+    }
+
+    /// The id of the account this keypair controls.
+    pub fn account_id(&self) -> String {
+        strkey::encode(Version::PublicKey, &self.public_key)
+    }
+
+    /// The public key, as it appears in xdr.
+    pub fn public_key(&self) -> PublicKey {
+        PublicKey::Ed25519(self.public_key)
+    }
+
+    /// The raw bytes of the public key.
+    pub fn public_key_bytes(&self) -> &[u8; 32] {
+        &self.public_key
+    }
+
+    /// The last four bytes of the public key. These are attached to signatures
+    /// so that validators can find the key that made each signature.
+    pub fn signature_hint(&self) -> [u8; 4] {
+        let mut hint = [0; 4];
+        hint.copy_from_slice(&self.public_key[28..]);
+        hint
+    }
+
+    /// Signs the bytes, returning the 64 byte signature.
+    pub fn sign(&self, message: &[u8]) -> Vec<u8> {
+        self.key_pair.sign(message).as_ref().to_vec()
+    }
+
+    /// Signs the bytes and attaches the signature hint, ready to be added to a
+    /// transaction envelope.
+    pub fn sign_decorated(&self, message: &[u8]) -> DecoratedSignature {
+        DecoratedSignature {
+            hint: self.signature_hint(),
+            signature: self.sign(message),
+        }
+    }
+
+    /// Returns true if the signature was made over the bytes by this keypair.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> bool {
+        verify(&self.public_key(), message, signature)
+    }
+}
+
+/// Returns true if the signature was made over the bytes by the private key
+/// of the public key. Unlike `Keypair::verify` this needs no secret.
+pub fn verify(public_key: &PublicKey, message: &[u8], signature: &[u8]) -> bool {
+    let PublicKey::Ed25519(ref key) = *public_key;
+    signature::verify(
+        &signature::ED25519,
+        Input::from(key),
+        Input::from(message),
+        Input::from(signature),
+    )
+    .is_ok()
+}
+
+impl Clone for Keypair {
+    fn clone(&self) -> Self {
+        Keypair::from_raw_seed(self.seed)
+    }
+}
+
+impl fmt::Debug for Keypair {
+    // The seed is deliberately left out so it doesn't end up in logs.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Keypair")
+            .field("account_id", &self.account_id())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod keypair_tests {
+    use super::*;
+
+    static SEED: &str = "SBMVFTVKOTJWEROPLCAG4OBSXYC56U5LAFKXA636MO2DJRSFU6BBBXUV";
+
+    #[test]
+    fn it_loads_a_keypair_from_a_seed() {
+        let keypair = Keypair::from_secret_seed(SEED).unwrap();
+        assert_eq!(keypair.secret_seed(), SEED);
+        assert!(keypair.account_id().starts_with('G'));
+        assert!(strkey::is_valid_account_id(&keypair.account_id()));
+    }
+
+    #[test]
+    fn it_derives_the_rfc_8032_public_key() {
+        // The first test vector of RFC 8032.
+        let seed = [
+            0x9d, 0x61, 0xb1, 0x9d, 0xef, 0xfd, 0x5a, 0x60, 0xba, 0x84, 0x4a, 0xf4, 0x92, 0xec,
+            0x2c, 0xc4, 0x44, 0x49, 0xc5, 0x69, 0x7b, 0x32, 0x69, 0x19, 0x70, 0x3b, 0xac, 0x03,
+            0x1c, 0xae, 0x7f, 0x60,
+        ];
+        let keypair = Keypair::from_raw_seed(seed);
+        assert_eq!(
+            keypair.public_key_bytes(),
+            &[
+                0xd7, 0x5a, 0x98, 0x01, 0x82, 0xb1, 0x0a, 0xb7, 0xd5, 0x4b, 0xfe, 0xd3, 0xc9, 0x64,
+                0x07, 0x3a, 0x0e, 0xe1, 0x72, 0xf3, 0xda, 0xa6, 0x23, 0x25, 0xaf, 0x02, 0x1a, 0x68,
+                0xf7, 0x07, 0x51, 0x1a,
+            ]
+        );
+        assert_eq!(&keypair.sign(b"")[..4], &[0xe5, 0x56, 0x43, 0x00]);
+        assert_eq!(keypair.signature_hint(), [0xf7, 0x07, 0x51, 0x1a]);
+    }
+
+    #[test]
+    fn it_rejects_a_public_key_as_a_seed() {
+        let account_id = Keypair::from_secret_seed(SEED).unwrap().account_id();
+        assert!(Keypair::from_secret_seed(&account_id).is_err());
+    }
+
+    #[test]
+    fn it_signs_and_verifies() {
+        let keypair = Keypair::random().unwrap();
+        let other = Keypair::random().unwrap();
+        let signature = keypair.sign(b"a message");
+        assert_eq!(signature.len(), 64);
+        assert!(keypair.verify(b"a message", &signature));
+        assert!(!keypair.verify(b"another message", &signature));
+        assert!(!other.verify(b"a message", &signature));
+        assert!(verify(&keypair.public_key(), b"a message", &signature));
+    }
+
+    #[test]
+    fn it_decorates_signatures_with_the_hint() {
+        let keypair = Keypair::from_secret_seed(SEED).unwrap();
+        let decorated = keypair.sign_decorated(b"a message");
+        assert_eq!(decorated.hint, keypair.signature_hint());
+        assert_eq!(decorated.signature, keypair.sign(b"a message"));
+    }
+
+    #[test]
+    fn it_keeps_the_seed_out_of_debug_output() {
+        let keypair = Keypair::from_secret_seed(SEED).unwrap();
+        let debug = format!("{:?}", keypair);
+        assert!(debug.contains(&keypair.account_id()));
+        assert!(!debug.contains(SEED));
+    }
+}
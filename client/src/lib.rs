@@ -38,23 +38,35 @@
 
 extern crate base64;
 extern crate chrono;
+#[macro_use]
 extern crate futures;
 extern crate http;
 extern crate hyper;
 extern crate hyper_tls;
+extern crate native_tls;
 extern crate reqwest;
+extern crate ring;
 extern crate serde;
 #[macro_use]
 extern crate serde_derive;
 extern crate serde_json;
 extern crate tokio_core;
+extern crate tokio_io;
+extern crate untrusted;
 
+pub mod builder;
 pub mod client;
 pub mod endpoint;
 pub mod error;
+pub mod keypair;
+#[cfg(any(test, feature = "mock"))]
+pub mod mock;
+pub mod network;
 pub mod resources;
 mod stellar_error;
+pub mod strkey;
 mod uri;
+pub mod xdr;
 
 /// The stellar client is a data structure that wraps the logic and state of the
 /// stellar horizon api. Interaction generally relies on building resources from
@@ -65,6 +77,9 @@ mod uri;
 /// need to hand it the tokio handle and execute the futures on your own. Once tokio
 /// 0.2.0 is released with the global event loop, it will add them to the event loop
 /// itself.
-pub use client::{async, sync};
+pub use builder::TransactionBuilder;
+pub use client::{async, sync, ClientBuilder};
 pub use error::{Error, Result};
-pub use stellar_error::StellarError;
+pub use keypair::Keypair;
+pub use network::Network;
+pub use stellar_error::{ResultCodes, StellarError};
@@ -0,0 +1,88 @@
+//! The json fixtures the mock server answers with by default. They can be used
+//! to build other responses, such as pages that link to each other.
+
+/// An account with balances, signers, thresholds and flags.
+pub const ACCOUNT: &str = include_str!("../../fixtures/account.json");
+/// A data entry of an account.
+pub const DATUM: &str = r#"{"value": "dGhlIHZhbHVl"}"#;
+/// A credit asset.
+pub const ASSET: &str = include_str!("../../fixtures/asset.json");
+/// A ledger.
+pub const LEDGER: &str = include_str!("../../fixtures/ledger.json");
+/// An offer to trade assets.
+pub const OFFER: &str = include_str!("../../fixtures/offer.json");
+/// An orderbook with bids and asks.
+pub const ORDERBOOK: &str = include_str!("../../fixtures/orderbook.json");
+/// A path a payment could take.
+pub const PAYMENT_PATH: &str = include_str!("../../fixtures/payment_path.json");
+/// A trade between two accounts.
+pub const TRADE: &str = include_str!("../../fixtures/trade.json");
+/// An aggregation of trades over a period.
+pub const TRADE_AGGREGATION: &str = include_str!("../../fixtures/trade_aggregation.json");
+/// A transaction with a text memo.
+pub const TRANSACTION: &str =
+    include_str!("../../fixtures/transactions/transaction_memo_text.json");
+/// The result of submitting a transaction.
+pub const SUBMISSION: &str = include_str!("../../fixtures/transactions/submission.json");
+/// A payment operation.
+pub const PAYMENT: &str = include_str!("../../fixtures/operations/payment.json");
+/// The error horizon answers with when asked for history it has not recorded.
+pub const BEFORE_HISTORY_ERROR: &str = include_str!("../../fixtures/before_history_error.json");
+/// The error horizon answers with when a submitted transaction fails.
+pub const TRANSACTION_FAILED_ERROR: &str =
+    include_str!("../../fixtures/transaction_failed_error.json");
+
+/// An effect of each type.
+pub const EFFECTS: &[&str] = &[
+    include_str!("../../fixtures/effects/account_created.json"),
+    include_str!("../../fixtures/effects/account_removed.json"),
+    include_str!("../../fixtures/effects/account_credited.json"),
+    include_str!("../../fixtures/effects/account_debited.json"),
+    include_str!("../../fixtures/effects/account_threshold_updated.json"),
+    include_str!("../../fixtures/effects/account_home_domain_updated.json"),
+    include_str!("../../fixtures/effects/account_flags_updated.json"),
+    include_str!("../../fixtures/effects/signer_created.json"),
+    include_str!("../../fixtures/effects/signer_removed.json"),
+    include_str!("../../fixtures/effects/signer_updated.json"),
+    include_str!("../../fixtures/effects/trustline_created.json"),
+    include_str!("../../fixtures/effects/trustline_removed.json"),
+    include_str!("../../fixtures/effects/trustline_updated.json"),
+    include_str!("../../fixtures/effects/trustline_authorized.json"),
+    include_str!("../../fixtures/effects/trustline_deauthorized.json"),
+    include_str!("../../fixtures/effects/trade.json"),
+    include_str!("../../fixtures/effects/data_created.json"),
+    include_str!("../../fixtures/effects/data_removed.json"),
+    include_str!("../../fixtures/effects/data_updated.json"),
+];
+
+/// An operation of each type.
+pub const OPERATIONS: &[&str] = &[
+    include_str!("../../fixtures/operations/create_account.json"),
+    PAYMENT,
+    include_str!("../../fixtures/operations/path_payment.json"),
+    include_str!("../../fixtures/operations/manage_offer.json"),
+    include_str!("../../fixtures/operations/create_passive_offer.json"),
+    include_str!("../../fixtures/operations/set_options.json"),
+    include_str!("../../fixtures/operations/change_trust.json"),
+    include_str!("../../fixtures/operations/allow_trust.json"),
+    include_str!("../../fixtures/operations/account_merge.json"),
+    include_str!("../../fixtures/operations/inflation.json"),
+    include_str!("../../fixtures/operations/manage_data.json"),
+];
+
+/// The operations that horizon lists as payments.
+pub const PAYMENTS: &[&str] = &[
+    include_str!("../../fixtures/operations/create_account.json"),
+    PAYMENT,
+    include_str!("../../fixtures/operations/path_payment.json"),
+    include_str!("../../fixtures/operations/account_merge.json"),
+];
+
+/// Transactions with each type of memo.
+pub const TRANSACTIONS: &[&str] = &[
+    include_str!("../../fixtures/transactions/transaction_memo_none.json"),
+    TRANSACTION,
+    include_str!("../../fixtures/transactions/transaction_memo_id.json"),
+    include_str!("../../fixtures/transactions/transaction_memo_hash.json"),
+    include_str!("../../fixtures/transactions/transaction_memo_return.json"),
+];
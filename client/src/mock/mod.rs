@@ -0,0 +1,310 @@
+//! A local stand-in for horizon to test against. The server answers requests
+//! over http on a port of localhost, so any client can be pointed at it,
+//! including the clients of crates that depend on this one.
+//!
+//! A server started with `Server::start` answers every endpoint the client can
+//! request with the fixtures in this crate. More routes can be mounted to
+//! answer with other records, pages that link to each other, or errors. This
+//! module is only compiled for this crate's tests, or with the `mock` feature.
+//!
+//! ```
+//! use stellar_client::{
+//!     endpoint::{account, ledger},
+//!     mock::{Response, Server},
+//!     sync::Client,
+//!     Error,
+//! };
+//!
+//! let server = Server::start().unwrap();
+//! server.mount_get("/ledgers/{sequence}", Response::before_history());
+//!
+//! let client = Client::new(server.uri()).unwrap();
+//! let account = client
+//!     .request(account::Details::new(
+//!         "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3",
+//!     )).unwrap();
+//! assert_eq!(account.sequence(), 2394452857640034);
+//!
+//! match client.request(ledger::Details::new(1)) {
+//!     Err(Error::BadResponse(error)) => assert!(error.is_before_history()),
+//!     _ => panic!("expected the ledger to be before history"),
+//! }
+//! assert_eq!(server.requests().len(), 2);
+//! ```
+use error::Result;
+use futures::{future, sync::oneshot, Future};
+use http::Method;
+use hyper::{
+    self,
+    header::ContentType,
+    server::{Http, Service},
+    StatusCode,
+};
+use std::net::SocketAddr;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+pub mod fixtures;
+mod response;
+mod route;
+
+pub use self::response::{Page, Response};
+use self::route::Route;
+
+/// A horizon stand-in that serves canned responses on a port of localhost
+/// until it is dropped.
+#[derive(Debug)]
+pub struct Server {
+    uri: String,
+    routes: Arc<Mutex<Vec<Route>>>,
+    requests: Arc<Mutex<Vec<String>>>,
+    shutdown: Option<oneshot::Sender<()>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl Server {
+    /// Starts a server that answers every endpoint with the fixtures.
+    pub fn start() -> Result<Self> {
+        Self::with_routes(route::defaults())
+    }
+
+    /// Starts a server without any routes, which answers every request as not
+    /// found until routes are mounted.
+    pub fn empty() -> Result<Self> {
+        Self::with_routes(Vec::new())
+    }
+
+    fn with_routes(routes: Vec<Route>) -> Result<Self> {
+        let routes = Arc::new(Mutex::new(routes));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let (shutdown, stop) = oneshot::channel::<()>();
+        let (started, listening) = mpsc::channel();
+
+        let handler = Handler {
+            routes: Arc::clone(&routes),
+            requests: Arc::clone(&requests),
+        };
+        let thread = thread::spawn(move || {
+            let addr: SocketAddr = ([127, 0, 0, 1], 0).into();
+            let server = Http::new().bind(&addr, move || Ok(handler.clone()));
+            let mut server = match server.and_then(|server| {
+                let addr = server.local_addr()?;
+                Ok((server, addr))
+            }) {
+                Ok((server, addr)) => {
+                    let _ = started.send(Ok(addr));
+                    server
+                }
+                Err(err) => {
+                    let _ = started.send(Err(err));
+                    return;
+                }
+            };
+            server.shutdown_timeout(Duration::from_millis(100));
+            let _ = server.run_until(stop.then(|_| Ok::<(), ()>(())));
+        });
+
+        let addr = listening.recv().map_err(|_| hyper::Error::Incomplete)??;
+        Ok(Server {
+            uri: format!("http://{}", addr),
+            routes,
+            requests,
+            shutdown: Some(shutdown),
+            thread: Some(thread),
+        })
+    }
+
+    /// The uri of the server, to construct a client with.
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Answers requests with the method whose path matches the pattern with
+    /// the response. Segments of the pattern in braces, like `{account_id}`,
+    /// match any segment. The pattern may have a query, in which case only
+    /// requests with each of its parameters match, e.g. `/ledgers?cursor=2`.
+    ///
+    /// Routes mounted later take precedence over those mounted before.
+    pub fn mount<R>(&self, method: Method, pattern: &str, response: R)
+    where
+        R: Into<Response>,
+    {
+        let route = Route::new(method, pattern, response.into());
+        self.routes
+            .lock()
+            .expect("Mock server routes were poisoned")
+            .push(route);
+    }
+
+    /// Answers get requests whose path matches the pattern with the response.
+    /// See `mount` for how the pattern matches.
+    pub fn mount_get<R>(&self, pattern: &str, response: R)
+    where
+        R: Into<Response>,
+    {
+        self.mount(Method::GET, pattern, response)
+    }
+
+    /// The method, path and query of each request the server has received, in
+    /// order. For example, `GET /ledgers?limit=2`.
+    pub fn requests(&self) -> Vec<String> {
+        self.requests
+            .lock()
+            .expect("Mock server requests were poisoned")
+            .clone()
+    }
+}
+
+impl Drop for Server {
+    fn drop(&mut self) {
+        if let Some(shutdown) = self.shutdown.take() {
+            let _ = shutdown.send(());
+        }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Answers each request with the last route mounted that matches it.
+#[derive(Debug, Clone)]
+struct Handler {
+    routes: Arc<Mutex<Vec<Route>>>,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl Handler {
+    fn respond(&self, method: &Method, path: &str, query: Option<&str>) -> Response {
+        let line = match query {
+            Some(query) => format!("{} {}?{}", method, path, query),
+            None => format!("{} {}", method, path),
+        };
+        if let Ok(mut requests) = self.requests.lock() {
+            requests.push(line);
+        }
+        self.routes
+            .lock()
+            .ok()
+            .and_then(|routes| {
+                routes
+                    .iter()
+                    .rev()
+                    .find(|route| route.matches(method, path, query))
+                    .map(|route| route.response().clone())
+            })
+            .unwrap_or_else(Response::not_found)
+    }
+}
+
+impl Service for Handler {
+    type Request = hyper::Request;
+    type Response = hyper::Response;
+    type Error = hyper::Error;
+    type Future = future::FutureResult<hyper::Response, hyper::Error>;
+
+    fn call(&self, request: hyper::Request) -> Self::Future {
+        let response = match Method::from_bytes(request.method().as_ref().as_bytes()) {
+            Ok(method) => self.respond(&method, request.path(), request.query()),
+            Err(_) => Response::new(405, ""),
+        };
+
+        let status =
+            StatusCode::try_from(response.status()).unwrap_or(StatusCode::InternalServerError);
+        let mut reply = hyper::Response::new()
+            .with_status(status)
+            .with_header(ContentType(
+                "application/hal+json"
+                    .parse()
+                    .expect("The content type is a valid mime"),
+            ));
+        for &(ref name, ref value) in response.headers() {
+            reply
+                .headers_mut()
+                .set_raw(name.to_string(), value.to_string());
+        }
+        future::ok(reply.with_body(response.body().to_string()))
+    }
+}
+
+#[cfg(test)]
+mod mock_tests {
+    use super::*;
+    use endpoint::{asset, ledger, transaction, Limit};
+    use resources::Asset;
+    use std::io::{Read, Write};
+    use std::net::TcpStream;
+    use sync::{Client, Iter};
+    use Error;
+
+    #[test]
+    fn it_answers_every_endpoint_with_the_fixtures() {
+        let server = Server::start().unwrap();
+        let client = Client::new(server.uri()).unwrap();
+
+        let ledger = client.request(ledger::Details::new(1)).unwrap();
+        assert_eq!(ledger.sequence(), 69859);
+        let transactions = client.request(ledger::Transactions::new(1)).unwrap();
+        assert_eq!(transactions.records().len(), 5);
+        let payments = client.request(transaction::Payments::new("abc")).unwrap();
+        assert_eq!(payments.records().len(), 4);
+
+        assert_eq!(
+            server.requests(),
+            vec![
+                "GET /ledgers/1",
+                "GET /ledgers/1/transactions",
+                "GET /transactions/abc/payments",
+            ]
+        );
+    }
+
+    #[test]
+    fn it_follows_pages_that_are_mounted() {
+        let server = Server::empty().unwrap();
+        server.mount_get(
+            "/assets",
+            Page::new(&[fixtures::ASSET]).with_next("/assets?cursor=1&limit=1"),
+        );
+        server.mount_get(
+            "/assets?cursor=1",
+            Page::new(&[fixtures::ASSET]).with_next("/assets?cursor=2&limit=1"),
+        );
+        server.mount_get("/assets?cursor=2", Page::new(&[]));
+
+        let client = Client::new(server.uri()).unwrap();
+        let assets: Vec<Asset> = Iter::new(&client, asset::All::default().with_limit(1))
+            .map(|asset| asset.unwrap())
+            .collect();
+        assert_eq!(assets.len(), 2);
+        assert_eq!(server.requests().len(), 3);
+    }
+
+    #[test]
+    fn it_answers_with_the_status_and_body_mounted() {
+        let server = Server::empty().unwrap();
+        let client = Client::new(server.uri()).unwrap();
+        match client.request(ledger::Details::new(1)).unwrap_err() {
+            Error::BadResponse(error) => assert!(error.is_not_found()),
+            error => panic!("Client did not return a bad response {:?}", error),
+        }
+
+        server.mount_get("/ledgers/{sequence}", Response::new(503, ""));
+        match client.request(ledger::Details::new(1)).unwrap_err() {
+            Error::ServerError => (),
+            error => panic!("Client did not return a server error {:?}", error),
+        }
+    }
+
+    #[test]
+    fn it_only_answers_requests_with_the_method_mounted() {
+        let server = Server::start().unwrap();
+        let mut stream = TcpStream::connect(&server.uri()["http://".len()..]).unwrap();
+        let request = b"DELETE /ledgers/1 HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n";
+        stream.write_all(request).unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 404"));
+        assert_eq!(server.requests(), vec!["DELETE /ledgers/1"]);
+    }
+}
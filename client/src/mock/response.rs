@@ -0,0 +1,167 @@
+use serde_json;
+
+/// A canned response that the mock server answers a route with.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Response {
+    /// A response with the status and json body.
+    pub fn new(status: u16, body: &str) -> Self {
+        Response {
+            status,
+            headers: Vec::new(),
+            body: body.to_string(),
+        }
+    }
+
+    /// A successful response with the json body.
+    pub fn ok(body: &str) -> Self {
+        Self::new(200, body)
+    }
+
+    /// The error horizon answers with when a resource does not exist.
+    pub fn not_found() -> Self {
+        Self::new(
+            404,
+            r#"{
+  "type": "https://stellar.org/horizon-errors/not_found",
+  "title": "Resource Missing",
+  "status": 404,
+  "detail": "The resource at the url requested was not found."
+}"#,
+        )
+    }
+
+    /// The error horizon answers with when asked for history it has not recorded.
+    pub fn before_history() -> Self {
+        Self::new(410, super::fixtures::BEFORE_HISTORY_ERROR)
+    }
+
+    /// The error horizon answers with when a submitted transaction fails.
+    pub fn transaction_failed() -> Self {
+        Self::new(400, super::fixtures::TRANSACTION_FAILED_ERROR)
+    }
+
+    /// Adds a header to the response, such as `Retry-After`.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// The http status of the response.
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// The headers of the response, besides the content type.
+    pub fn headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    /// The json body of the response.
+    pub fn body(&self) -> &str {
+        &self.body
+    }
+}
+
+/// A page of records, as horizon answers requests to collection endpoints.
+/// The `_links` to the next and previous pages are only included when set,
+/// so a page without a next link is the last one.
+///
+/// ## Example
+///
+/// ```
+/// use stellar_client::mock::{Page, Response};
+///
+/// let page: Response = Page::new(&[r#"{"foo": "bar"}"#])
+///     .with_next("/ledgers?cursor=2&order=asc")
+///     .into();
+/// assert_eq!(page.status(), 200);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Page {
+    records: Vec<String>,
+    next: Option<String>,
+    prev: Option<String>,
+}
+
+impl Page {
+    /// A page of the records, each of which is a json object.
+    pub fn new(records: &[&str]) -> Self {
+        Page {
+            records: records.iter().map(|record| record.to_string()).collect(),
+            next: None,
+            prev: None,
+        }
+    }
+
+    /// Links the page to the next one. The href may be relative to the server.
+    pub fn with_next(mut self, href: &str) -> Self {
+        self.next = Some(href.to_string());
+        self
+    }
+
+    /// Links the page to the previous one. The href may be relative to the server.
+    pub fn with_prev(mut self, href: &str) -> Self {
+        self.prev = Some(href.to_string());
+        self
+    }
+
+    fn links(&self) -> String {
+        let links: Vec<String> = [("next", &self.next), ("prev", &self.prev)]
+            .iter()
+            .filter_map(|&(name, href)| {
+                href.as_ref().map(|href| {
+                    let href = serde_json::to_string(href).unwrap_or_default();
+                    format!(r#""{}": {{"href": {}}}"#, name, href)
+                })
+            })
+            .collect();
+        links.join(", ")
+    }
+}
+
+impl From<Page> for Response {
+    fn from(page: Page) -> Response {
+        let body = format!(
+            r#"{{"_links": {{{}}}, "_embedded": {{"records": [{}]}}}}"#,
+            page.links(),
+            page.records.join(", ")
+        );
+        Response::ok(&body)
+    }
+}
+
+#[cfg(test)]
+mod response_tests {
+    use super::*;
+    use endpoint::Records;
+    use resources::Ledger;
+    use serde_json;
+
+    #[test]
+    fn it_writes_pages_that_parse_as_records() {
+        let page: Response = Page::new(&[super::super::fixtures::LEDGER])
+            .with_next("/ledgers?cursor=2&order=asc")
+            .into();
+        let records: Records<Ledger> = serde_json::from_str(page.body()).unwrap();
+        assert_eq!(records.records().len(), 1);
+        assert_eq!(
+            records.next().map(|uri| uri.to_string()),
+            Some("/ledgers?cursor=2&order=asc".to_string())
+        );
+        assert_eq!(records.prev(), None);
+    }
+
+    #[test]
+    fn it_writes_empty_pages() {
+        let page: Response = Page::new(&[]).into();
+        let records: Records<Ledger> = serde_json::from_str(page.body()).unwrap();
+        assert!(records.records().is_empty());
+        assert_eq!(records.next(), None);
+    }
+}
@@ -0,0 +1,145 @@
+use super::{fixtures, Page, Response};
+use http::Method;
+
+/// A response mounted on the requests whose method and path match a pattern.
+#[derive(Debug, Clone)]
+pub(crate) struct Route {
+    method: Method,
+    path: Vec<String>,
+    query: Vec<String>,
+    response: Response,
+}
+
+impl Route {
+    /// Path segments in braces, like `{account_id}`, match any segment. Query
+    /// parameters in the pattern must all be in the request, which may also
+    /// have others.
+    pub(crate) fn new(method: Method, pattern: &str, response: Response) -> Self {
+        let mut parts = pattern.splitn(2, '?');
+        let path = parts.next().unwrap_or("");
+        let query = parts.next().unwrap_or("");
+        Route {
+            method,
+            path: segments(path),
+            query: pairs(query),
+            response,
+        }
+    }
+
+    pub(crate) fn matches(&self, method: &Method, path: &str, query: Option<&str>) -> bool {
+        let path = segments(path);
+        let query = pairs(query.unwrap_or(""));
+        self.method == *method
+            && self.path.len() == path.len()
+            && self
+                .path
+                .iter()
+                .zip(&path)
+                .all(|(pattern, segment)| is_placeholder(pattern) || pattern == segment)
+            && self.query.iter().all(|pair| query.contains(pair))
+    }
+
+    pub(crate) fn response(&self) -> &Response {
+        &self.response
+    }
+}
+
+fn segments(path: &str) -> Vec<String> {
+    path.split('/')
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.to_string())
+        .collect()
+}
+
+fn pairs(query: &str) -> Vec<String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| pair.to_string())
+        .collect()
+}
+
+fn is_placeholder(segment: &str) -> bool {
+    segment.starts_with('{') && segment.ends_with('}')
+}
+
+/// Routes for every endpoint the client can request, answered with the
+/// fixtures. Collections are a single page without a next link.
+pub(crate) fn defaults() -> Vec<Route> {
+    let get = |pattern: &str, response: Response| Route::new(Method::GET, pattern, response);
+    let effects = || Page::new(fixtures::EFFECTS).into();
+    let operations = || Page::new(fixtures::OPERATIONS).into();
+    let payments = || Page::new(fixtures::PAYMENTS).into();
+    let transactions = || Page::new(fixtures::TRANSACTIONS).into();
+    let trades = || Page::new(&[fixtures::TRADE]).into();
+
+    vec![
+        get("/accounts/{account_id}", Response::ok(fixtures::ACCOUNT)),
+        get(
+            "/accounts/{account_id}/data/{key}",
+            Response::ok(fixtures::DATUM),
+        ),
+        get("/accounts/{account_id}/effects", effects()),
+        get(
+            "/accounts/{account_id}/offers",
+            Page::new(&[fixtures::OFFER]).into(),
+        ),
+        get("/accounts/{account_id}/operations", operations()),
+        get("/accounts/{account_id}/payments", payments()),
+        get("/accounts/{account_id}/trades", trades()),
+        get("/accounts/{account_id}/transactions", transactions()),
+        get("/assets", Page::new(&[fixtures::ASSET]).into()),
+        get("/effects", effects()),
+        get("/ledgers", Page::new(&[fixtures::LEDGER]).into()),
+        get("/ledgers/{sequence}", Response::ok(fixtures::LEDGER)),
+        get("/ledgers/{sequence}/effects", effects()),
+        get("/ledgers/{sequence}/operations", operations()),
+        get("/ledgers/{sequence}/payments", payments()),
+        get("/ledgers/{sequence}/transactions", transactions()),
+        get("/operations", operations()),
+        get("/operations/{id}", Response::ok(fixtures::PAYMENT)),
+        get("/operations/{id}/effects", effects()),
+        get("/order_book", Response::ok(fixtures::ORDERBOOK)),
+        get("/paths", Page::new(&[fixtures::PAYMENT_PATH]).into()),
+        get("/payments", payments()),
+        get(
+            "/trade_aggregations",
+            Page::new(&[fixtures::TRADE_AGGREGATION]).into(),
+        ),
+        get("/trades", trades()),
+        get("/transactions", transactions()),
+        get("/transactions/{hash}", Response::ok(fixtures::TRANSACTION)),
+        get("/transactions/{hash}/effects", effects()),
+        get("/transactions/{hash}/operations", operations()),
+        get("/transactions/{hash}/payments", payments()),
+        Route::new(
+            Method::POST,
+            "/transactions",
+            Response::ok(fixtures::SUBMISSION),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod route_tests {
+    use super::*;
+
+    #[test]
+    fn it_matches_placeholders_and_query_parameters() {
+        let route = Route::new(
+            Method::GET,
+            "/ledgers/{sequence}/payments?cursor=12",
+            Response::ok("{}"),
+        );
+        assert!(route.matches(&Method::GET, "/ledgers/1/payments", Some("cursor=12")));
+        assert!(route.matches(
+            &Method::GET,
+            "/ledgers/1/payments/",
+            Some("order=asc&cursor=12")
+        ));
+        assert!(!route.matches(&Method::POST, "/ledgers/1/payments", Some("cursor=12")));
+        assert!(!route.matches(&Method::GET, "/ledgers/1/payments", None));
+        assert!(!route.matches(&Method::GET, "/ledgers/1/effects", Some("cursor=12")));
+        assert!(!route.matches(&Method::GET, "/ledgers/payments", Some("cursor=12")));
+    }
+}
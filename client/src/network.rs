@@ -0,0 +1,134 @@
+//! Identifies the stellar network that transactions are meant for.
+use ring::digest::{digest, SHA256};
+use xdr::{self, Hash, Transaction};
+
+/// The passphrase of the public network.
+pub static PUBLIC_PASSPHRASE: &'static str = "Public Global Stellar Network ; September 2015";
+
+/// The passphrase of the test network.
+pub static TESTNET_PASSPHRASE: &'static str = "Test SDF Network ; September 2015";
+
+/// The discriminant of a transaction in the envelope type xdr union. It is
+/// included in the hash so that signatures can't be reused for other kinds of
+/// payloads.
+const ENVELOPE_TYPE_TX: i32 = 2;
+
+/// A stellar network, identified by its passphrase. Transaction hashes, and
+/// so signatures, are scoped to the network so that a transaction signed for
+/// the test network can't be replayed on the public network.
+///
+/// <https://www.stellar.org/developers/guides/concepts/networks.html>
+///
+/// ## Example
+/// ```
+/// use stellar_client::Network;
+///
+/// let network = Network::test();
+/// assert_eq!(network.passphrase(), "Test SDF Network ; September 2015");
+/// assert_ne!(network.network_id(), Network::public().network_id());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Network {
+    passphrase: String,
+}
+
+impl Network {
+    /// A network with a custom passphrase, such as a private network.
+    pub fn new(passphrase: &str) -> Network {
+        Network {
+            passphrase: passphrase.to_string(),
+        }
+    }
+
+    /// The public network.
+    pub fn public() -> Network {
+        Network::new(PUBLIC_PASSPHRASE)
+    }
+
+    /// The test network.
+    pub fn test() -> Network {
+        Network::new(TESTNET_PASSPHRASE)
+    }
+
+    /// The passphrase of the network.
+    pub fn passphrase(&self) -> &str {
+        &self.passphrase
+    }
+
+    /// The id of the network, which is the SHA-256 hash of its passphrase.
+    pub fn network_id(&self) -> Hash {
+        sha256(self.passphrase.as_bytes())
+    }
+
+    /// The hash of the transaction on this network. This is the value that
+    /// is signed, and that horizon returns as the transaction's hash.
+    pub fn hash_transaction(&self, tx: &Transaction) -> Hash {
+        let mut payload = self.network_id().to_vec();
+        payload.extend(xdr::to_bytes(&ENVELOPE_TYPE_TX));
+        payload.extend(xdr::to_bytes(tx));
+        sha256(&payload)
+    }
+
+    /// The hash of the transaction on this network, hex encoded as it appears
+    /// on horizon.
+    pub fn hash_transaction_hex(&self, tx: &Transaction) -> String {
+        self.hash_transaction(tx)
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+pub(crate) fn sha256(bytes: &[u8]) -> Hash {
+    let mut hash = [0; 32];
+    hash.copy_from_slice(digest(&SHA256, bytes).as_ref());
+    hash
+}
+
+#[cfg(test)]
+mod network_tests {
+    use super::*;
+    use keypair;
+    use resources;
+    use serde_json;
+
+    fn transaction() -> resources::Transaction {
+        serde_json::from_str(include_str!(
+            "../fixtures/transactions/transaction_memo_text.json"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn it_hashes_the_passphrase_into_the_network_id() {
+        let network = Network::new("a private network");
+        assert_eq!(network.passphrase(), "a private network");
+        assert_eq!(network.network_id(), sha256(b"a private network"));
+        assert_ne!(Network::public().network_id(), Network::test().network_id());
+    }
+
+    #[test]
+    fn it_matches_the_hash_from_horizon() {
+        let transaction = transaction();
+        let envelope = transaction.envelope().unwrap();
+        assert_eq!(
+            &Network::public().hash_transaction_hex(&envelope.tx),
+            transaction.hash()
+        );
+        assert_ne!(
+            &Network::test().hash_transaction_hex(&envelope.tx),
+            transaction.hash()
+        );
+    }
+
+    #[test]
+    fn it_verifies_the_signature_from_horizon() {
+        let envelope = transaction().envelope().unwrap();
+        let hash = Network::public().hash_transaction(&envelope.tx);
+        assert!(keypair::verify(
+            &envelope.tx.source_account,
+            &hash,
+            &envelope.signatures[0].signature,
+        ));
+    }
+}
@@ -1,5 +1,7 @@
 use super::deserialize;
 use resources::base64string::Base64String;
+use resources::{Amount, AssetIdentifier, Flags};
+use serde::{de, Deserialize, Deserializer};
 use std::collections::HashMap;
 
 /// In the Stellar network, users interact using accounts which can be controlled by a
@@ -13,6 +15,14 @@ pub struct Account {
     #[serde(deserialize_with = "deserialize::from_str")]
     sequence: u64,
     subentry_count: u64,
+    #[serde(default)]
+    inflation_destination: Option<String>,
+    #[serde(default)]
+    home_domain: Option<String>,
+    thresholds: Thresholds,
+    flags: Flags,
+    balances: Vec<Balance>,
+    signers: Vec<Signer>,
     data: HashMap<String, Base64String>,
 }
 
@@ -53,8 +63,332 @@ impl Account {
         self.subentry_count
     }
 
+    /// The account that this account's inflation votes go to, if it has chosen
+    /// one.
+    pub fn inflation_destination(&self) -> Option<&str> {
+        self.inflation_destination.as_ref().map(String::as_str)
+    }
+
+    /// The domain that hosts this account's stellar.toml, if it has one.
+    pub fn home_domain(&self) -> Option<&str> {
+        self.home_domain.as_ref().map(String::as_str)
+    }
+
+    /// The weights the signers must reach for low, medium and high threshold
+    /// operations.
+    pub fn thresholds(&self) -> Thresholds {
+        self.thresholds
+    }
+
+    /// The flags that govern the assets this account issues.
+    pub fn flags(&self) -> Flags {
+        self.flags
+    }
+
+    /// The balances of each asset this account holds, including lumens.
+    pub fn balances(&self) -> &[Balance] {
+        &self.balances
+    }
+
+    /// The balance of a single asset, if the account holds it.
+    pub fn balance(&self, asset: &AssetIdentifier) -> Option<&Balance> {
+        self.balances
+            .iter()
+            .find(|balance| balance.asset_identifier() == asset)
+    }
+
+    /// The balance of lumens, which every account holds.
+    pub fn native_balance(&self) -> Option<&Balance> {
+        self.balance(&AssetIdentifier::native())
+    }
+
+    /// The lumens the account must hold, given the base reserve of the latest
+    /// ledger. Every account reserves two base reserves for itself and one
+    /// more for each subentry.
+    ///
+    /// <https://www.stellar.org/developers/guides/concepts/fees.html#minimum-account-balance>
+    pub fn minimum_balance(&self, base_reserve: Amount) -> Amount {
+        let entries = 2i64.saturating_add(self.subentry_count as i64);
+        Amount::new(entries.saturating_mul(base_reserve.stroops()))
+    }
+
+    /// The lumens the account can send without dropping below its minimum
+    /// balance, once the lumens committed to selling offers are set aside.
+    ///
+    /// ## Example
+    /// ```
+    /// # use stellar_client::resources::{Account, Amount, Ledger};
+    /// fn can_send(account: &Account, ledger: &Ledger, amount: Amount) -> bool {
+    ///     amount <= account.spendable_balance(ledger.base_reserve_as_amount())
+    /// }
+    /// ```
+    pub fn spendable_balance(&self, base_reserve: Amount) -> Amount {
+        let (balance, selling_liabilities) = self
+            .native_balance()
+            .map(|native| (native.balance(), native.selling_liabilities()))
+            .unwrap_or((Amount::new(0), Amount::new(0)));
+        let spendable = balance
+            .stroops()
+            .saturating_sub(self.minimum_balance(base_reserve).stroops())
+            .saturating_sub(selling_liabilities.stroops());
+        Amount::new(spendable.max(0))
+    }
+
+    /// The keys that can sign on behalf of this account, including the
+    /// account's own key.
+    pub fn signers(&self) -> &[Signer] {
+        &self.signers
+    }
+
     /// A key/value store of data attached to this account.
     pub fn data(&self) -> &HashMap<String, Base64String> {
         &self.data
     }
 }
+
+/// The weights that the signers of an account must reach for each category of
+/// operation.
+///
+/// <https://www.stellar.org/developers/guides/concepts/multi-sig.html#thresholds>
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Thresholds {
+    low_threshold: u8,
+    med_threshold: u8,
+    high_threshold: u8,
+}
+
+impl Thresholds {
+    /// The weight needed for allow trust, bump sequence and inflation.
+    pub fn low(&self) -> u8 {
+        self.low_threshold
+    }
+
+    /// The weight needed for most operations, such as payments and offers.
+    pub fn med(&self) -> u8 {
+        self.med_threshold
+    }
+
+    /// The weight needed for account merges and changing signers or
+    /// thresholds.
+    pub fn high(&self) -> u8 {
+        self.high_threshold
+    }
+}
+
+/// The amount of a single asset held by an account.
+#[derive(Debug, Clone)]
+pub struct Balance {
+    asset_identifier: AssetIdentifier,
+    balance: Amount,
+    limit: Option<Amount>,
+    buying_liabilities: Amount,
+    selling_liabilities: Amount,
+}
+
+#[derive(Deserialize, Debug)]
+struct IntermediateBalance {
+    asset_type: String,
+    asset_code: Option<String>,
+    asset_issuer: Option<String>,
+    balance: Amount,
+    limit: Option<Amount>,
+    buying_liabilities: Option<Amount>,
+    selling_liabilities: Option<Amount>,
+}
+
+impl<'de> Deserialize<'de> for Balance {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let rep = IntermediateBalance::deserialize(d)?;
+        let asset_identifier =
+            AssetIdentifier::new(&rep.asset_type, rep.asset_code, rep.asset_issuer)
+                .map_err(de::Error::custom)?;
+        // Liabilities were added in protocol 10, before which there were none.
+        Ok(Balance {
+            asset_identifier,
+            balance: rep.balance,
+            limit: rep.limit,
+            buying_liabilities: rep.buying_liabilities.unwrap_or_else(|| Amount::new(0)),
+            selling_liabilities: rep.selling_liabilities.unwrap_or_else(|| Amount::new(0)),
+        })
+    }
+}
+
+impl Balance {
+    /// The asset held.
+    pub fn asset_identifier(&self) -> &AssetIdentifier {
+        &self.asset_identifier
+    }
+
+    /// The amount of the asset held.
+    pub fn balance(&self) -> Amount {
+        self.balance
+    }
+
+    /// The most of the asset the account is willing to hold. Lumens have no
+    /// limit.
+    pub fn limit(&self) -> Option<Amount> {
+        self.limit
+    }
+
+    /// The amount of the asset that the account's offers are waiting to buy.
+    pub fn buying_liabilities(&self) -> Amount {
+        self.buying_liabilities
+    }
+
+    /// The amount of the asset that the account's offers are waiting to sell.
+    pub fn selling_liabilities(&self) -> Amount {
+        self.selling_liabilities
+    }
+
+    /// Returns true if this is the balance of lumens.
+    pub fn is_native(&self) -> bool {
+        self.asset_identifier.is_native()
+    }
+}
+
+/// A key that can sign on behalf of an account, and the weight its signature
+/// carries.
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Signer {
+    key: String,
+    weight: u8,
+    #[serde(rename = "type")]
+    signer_type: SignerType,
+}
+
+impl Signer {
+    /// The key of the signer encoded as a strkey.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The weight of the signer's signature.
+    pub fn weight(&self) -> u8 {
+        self.weight
+    }
+
+    /// The kind of key of the signer.
+    pub fn signer_type(&self) -> SignerType {
+        self.signer_type
+    }
+}
+
+/// The kinds of keys that can sign for an account.
+#[derive(Deserialize, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SignerType {
+    /// An ed25519 public key, which signs like any keypair.
+    #[serde(rename = "ed25519_public_key")]
+    Ed25519PublicKey,
+    /// The hash of a transaction that is authorized ahead of time.
+    #[serde(rename = "preauth_tx")]
+    PreAuthTx,
+    /// The SHA-256 hash of a value that is revealed to sign.
+    #[serde(rename = "sha256_hash")]
+    Sha256Hash,
+}
+
+#[cfg(test)]
+mod account_tests {
+    use super::*;
+    use serde_json;
+
+    fn account_json() -> &'static str {
+        include_str!("../../fixtures/account.json")
+    }
+
+    #[test]
+    fn it_parses_an_account_from_json() {
+        let account: Account = serde_json::from_str(&account_json()).unwrap();
+        assert_eq!(
+            account.account_id(),
+            "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3"
+        );
+        assert_eq!(account.sequence(), 2394452857640034);
+        assert_eq!(account.subentry_count(), 4);
+        assert_eq!(
+            account.inflation_destination(),
+            Some("GABQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQGAYDAMBQHGPC")
+        );
+        assert_eq!(account.home_domain(), Some("stellar.org"));
+        assert_eq!(account.thresholds().low(), 1);
+        assert_eq!(account.thresholds().med(), 2);
+        assert_eq!(account.thresholds().high(), 3);
+        assert!(!account.flags().is_auth_required());
+        assert!(account.flags().is_auth_revocable());
+        assert_eq!(account.data().len(), 1);
+    }
+
+    #[test]
+    fn it_parses_the_balances() {
+        let account: Account = serde_json::from_str(&account_json()).unwrap();
+        assert_eq!(account.balances().len(), 2);
+
+        let usd = &account.balances()[0];
+        assert_eq!(usd.asset_identifier().code(), "USD");
+        assert_eq!(usd.balance(), Amount::new(1_000_000_000));
+        assert_eq!(usd.limit(), Some(Amount::new(::std::i64::MAX)));
+        assert_eq!(usd.selling_liabilities(), Amount::new(255_000_000));
+        assert!(!usd.is_native());
+
+        let native = account.balance(&AssetIdentifier::native()).unwrap();
+        assert_eq!(native.balance(), Amount::new(99_999_999_900));
+        assert_eq!(native.limit(), None);
+        assert_eq!(native.buying_liabilities(), Amount::new(120_000_000));
+        assert_eq!(native.selling_liabilities(), Amount::new(15_000_000));
+    }
+
+    #[test]
+    fn it_computes_the_minimum_balance() {
+        let account: Account = serde_json::from_str(&account_json()).unwrap();
+        // Two base reserves for the account and four for its subentries.
+        let base_reserve = Amount::new(5_000_000);
+        assert_eq!(
+            account.minimum_balance(base_reserve),
+            Amount::new(30_000_000)
+        );
+        assert_eq!(account.minimum_balance(Amount::new(0)), Amount::new(0));
+    }
+
+    #[test]
+    fn it_computes_the_spendable_balance() {
+        let account: Account = serde_json::from_str(&account_json()).unwrap();
+        // The balance, less the minimum balance and the selling liabilities.
+        assert_eq!(
+            account.spendable_balance(Amount::new(5_000_000)),
+            Amount::new(99_954_999_900)
+        );
+        assert_eq!(
+            account.spendable_balance(Amount::new(50_000_000_000)),
+            Amount::new(0)
+        );
+        assert_eq!(
+            account.spendable_balance(Amount::new(::std::i64::MAX)),
+            Amount::new(0)
+        );
+    }
+
+    #[test]
+    fn it_parses_the_signers() {
+        let account: Account = serde_json::from_str(&account_json()).unwrap();
+        let signers = account.signers();
+        assert_eq!(signers.len(), 3);
+        assert_eq!(
+            signers[0].key(),
+            "GABAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEAQCAIBAEJXA"
+        );
+        assert_eq!(signers[0].weight(), 2);
+        assert_eq!(signers[0].signer_type(), SignerType::Ed25519PublicKey);
+        assert_eq!(signers[1].signer_type(), SignerType::PreAuthTx);
+    }
+
+    #[test]
+    fn it_defaults_liabilities_from_older_horizons() {
+        let json = r#"{"balance": "1.0000000", "asset_type": "native"}"#;
+        let balance: Balance = serde_json::from_str(json).unwrap();
+        assert_eq!(balance.buying_liabilities(), Amount::new(0));
+        assert_eq!(balance.selling_liabilities(), Amount::new(0));
+    }
+}
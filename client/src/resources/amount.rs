@@ -1,8 +1,9 @@
+use resources::PriceRatio;
 use serde::de;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
 use std::num;
-use std::ops::{Add, Sub};
+use std::ops::{Add, Neg, Sub};
 use std::str::FromStr;
 
 /// Amounts are used in several resources in the stellar ecosystem. There
@@ -20,6 +21,294 @@ impl Amount {
         assert!(amount >= 0);
         Amount(amount)
     }
+
+    /// The amount in stroops, the smallest unit of an asset. This is the value
+    /// that appears in xdr.
+    pub fn stroops(&self) -> i64 {
+        self.0
+    }
+
+    fn from_stroops(stroops: i64) -> Option<Amount> {
+        if stroops >= 0 {
+            Some(Amount(stroops))
+        } else {
+            None
+        }
+    }
+
+    /// Adds the amounts, returning `None` if the sum overflows.
+    pub fn checked_add(self, other: Amount) -> Option<Amount> {
+        self.0.checked_add(other.0).map(Amount)
+    }
+
+    /// Subtracts the other amount, returning `None` if the result would be
+    /// negative.
+    pub fn checked_sub(self, other: Amount) -> Option<Amount> {
+        self.0.checked_sub(other.0).and_then(Amount::from_stroops)
+    }
+
+    /// Multiplies the amount by a whole number, returning `None` if the result
+    /// is negative or overflows.
+    pub fn checked_mul(self, factor: i64) -> Option<Amount> {
+        self.0.checked_mul(factor).and_then(Amount::from_stroops)
+    }
+
+    /// Converts the amount at the price, such as the amount of buying asset an
+    /// offer of this much selling asset would receive. Returns `None` if the
+    /// price has a zero denominator or the result overflows.
+    ///
+    /// ## Example
+    /// ```
+    /// use stellar_client::resources::{Amount, PriceRatio, Rounding};
+    ///
+    /// let amount = Amount::new(10);
+    /// let price = PriceRatio::new(1, 3);
+    /// assert_eq!(amount.checked_mul_price(price, Rounding::Down), Some(Amount::new(3)));
+    /// assert_eq!(amount.checked_mul_price(price, Rounding::Up), Some(Amount::new(4)));
+    /// ```
+    pub fn checked_mul_price(self, price: PriceRatio, rounding: Rounding) -> Option<Amount> {
+        big_divide(self.0, price.numerator(), price.denominator(), rounding)
+    }
+
+    /// Converts the amount at the inverse of the price, such as the amount of
+    /// selling asset an offer needs to receive this much buying asset. Returns
+    /// `None` if the price has a zero numerator or the result overflows.
+    pub fn checked_div_price(self, price: PriceRatio, rounding: Rounding) -> Option<Amount> {
+        big_divide(self.0, price.denominator(), price.numerator(), rounding)
+    }
+
+    /// The change from a previous amount to this one.
+    pub fn delta_from(self, previous: Amount) -> AmountDelta {
+        // Both amounts are positive so the difference can't overflow.
+        AmountDelta(self.0 - previous.0)
+    }
+
+    /// Applies a change to the amount, returning `None` if the result would be
+    /// negative or overflows.
+    pub fn checked_add_delta(self, delta: AmountDelta) -> Option<Amount> {
+        self.0.checked_add(delta.0).and_then(Amount::from_stroops)
+    }
+}
+
+/// How to round a calculation that doesn't come out to a whole stroop.
+#[derive(Debug, Eq, PartialEq, Copy, Clone)]
+pub enum Rounding {
+    /// Round towards zero, as when computing what an offer receives.
+    Down,
+    /// Round away from zero, as when computing what an offer must pay.
+    Up,
+}
+
+/// Computes `amount * numerator / denominator` without overflowing the
+/// intermediate product.
+fn big_divide(amount: i64, numerator: u64, denominator: u64, rounding: Rounding) -> Option<Amount> {
+    if denominator == 0 {
+        return None;
+    }
+    let product = amount as u128 * u128::from(numerator);
+    let denominator = u128::from(denominator);
+    let mut quotient = product / denominator;
+    if rounding == Rounding::Up && product % denominator != 0 {
+        quotient += 1;
+    }
+    if quotient > ::std::i64::MAX as u128 {
+        return None;
+    }
+    Some(Amount(quotient as i64))
+}
+
+#[cfg(test)]
+mod checked_amount_tests {
+    use super::*;
+    #[test]
+    fn it_adds_without_overflowing() {
+        assert_eq!(
+            Amount::new(12).checked_add(Amount::new(6)),
+            Some(Amount(18))
+        );
+        assert_eq!(
+            Amount::new(::std::i64::MAX).checked_add(Amount::new(1)),
+            None
+        );
+    }
+    #[test]
+    fn it_subtracts_without_going_negative() {
+        assert_eq!(Amount::new(12).checked_sub(Amount::new(6)), Some(Amount(6)));
+        assert_eq!(Amount::new(1).checked_sub(Amount::new(2)), None);
+    }
+    #[test]
+    fn it_multiplies_without_overflowing() {
+        assert_eq!(Amount::new(12).checked_mul(3), Some(Amount(36)));
+        assert_eq!(Amount::new(12).checked_mul(-1), None);
+        assert_eq!(Amount::new(::std::i64::MAX).checked_mul(2), None);
+    }
+    #[test]
+    fn it_applies_deltas() {
+        let delta = Amount::new(2).delta_from(Amount::new(5));
+        assert_eq!(delta, AmountDelta::new(-3));
+        assert_eq!(Amount::new(5).checked_add_delta(delta), Some(Amount(2)));
+        assert_eq!(Amount::new(2).checked_add_delta(delta), None);
+    }
+}
+
+#[cfg(test)]
+mod price_amount_tests {
+    use super::*;
+    #[test]
+    fn it_multiplies_by_a_price_with_rounding() {
+        let price = PriceRatio::new(2, 3);
+        let amount = Amount::new(10);
+        assert_eq!(
+            amount.checked_mul_price(price, Rounding::Down),
+            Some(Amount(6))
+        );
+        assert_eq!(
+            amount.checked_mul_price(price, Rounding::Up),
+            Some(Amount(7))
+        );
+        assert_eq!(
+            Amount::new(9).checked_mul_price(price, Rounding::Up),
+            Some(Amount(6))
+        );
+    }
+    #[test]
+    fn it_divides_by_a_price_with_rounding() {
+        let price = PriceRatio::new(2, 3);
+        let amount = Amount::new(10);
+        assert_eq!(
+            amount.checked_div_price(price, Rounding::Down),
+            Some(Amount(15))
+        );
+        assert_eq!(
+            Amount::new(11).checked_div_price(price, Rounding::Down),
+            Some(Amount(16))
+        );
+        assert_eq!(
+            Amount::new(11).checked_div_price(price, Rounding::Up),
+            Some(Amount(17))
+        );
+    }
+    #[test]
+    fn it_does_not_overflow_the_intermediate_product() {
+        let max = Amount::new(::std::i64::MAX);
+        let price = PriceRatio::new(::std::i32::MAX as u64, ::std::i32::MAX as u64);
+        assert_eq!(max.checked_mul_price(price, Rounding::Down), Some(max));
+        assert_eq!(
+            max.checked_mul_price(PriceRatio::new(2, 1), Rounding::Down),
+            None
+        );
+    }
+    #[test]
+    fn it_rejects_zero_prices() {
+        let amount = Amount::new(10);
+        assert_eq!(
+            amount.checked_mul_price(PriceRatio::new(1, 0), Rounding::Down),
+            None
+        );
+        assert_eq!(
+            amount.checked_div_price(PriceRatio::new(0, 1), Rounding::Down),
+            None
+        );
+    }
+}
+
+/// A signed change in an amount, such as the change in a balance. Unlike an
+/// `Amount` it may be negative.
+#[derive(Debug, Eq, PartialEq, Ord, PartialOrd, Copy, Clone, Hash)]
+pub struct AmountDelta(i64);
+
+impl AmountDelta {
+    /// Create a delta of the given number of stroops.
+    pub fn new(stroops: i64) -> AmountDelta {
+        AmountDelta(stroops)
+    }
+
+    /// The change in stroops.
+    pub fn stroops(&self) -> i64 {
+        self.0
+    }
+
+    /// Returns true if the change is a decrease.
+    pub fn is_negative(&self) -> bool {
+        self.0 < 0
+    }
+
+    /// The size of the change as an amount, regardless of direction.
+    pub fn magnitude(&self) -> Option<Amount> {
+        self.0.checked_abs().map(Amount)
+    }
+
+    /// Adds the changes, returning `None` if the sum overflows.
+    pub fn checked_add(self, other: AmountDelta) -> Option<AmountDelta> {
+        self.0.checked_add(other.0).map(AmountDelta)
+    }
+
+    /// Reverses the direction of the change, returning `None` for the largest
+    /// decrease, which has no increase of the same size.
+    pub fn checked_neg(self) -> Option<AmountDelta> {
+        self.0.checked_neg().map(AmountDelta)
+    }
+}
+
+/// Negating saturates, so the largest decrease becomes the largest increase.
+impl Neg for AmountDelta {
+    type Output = AmountDelta;
+
+    fn neg(self) -> AmountDelta {
+        self.checked_neg()
+            .unwrap_or_else(|| AmountDelta(::std::i64::MAX))
+    }
+}
+
+impl From<Amount> for AmountDelta {
+    fn from(amount: Amount) -> AmountDelta {
+        AmountDelta(amount.0)
+    }
+}
+
+/// Deltas are displayed like amounts, with a leading `-` when negative.
+impl fmt::Display for AmountDelta {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sign = if self.is_negative() { "-" } else { "" };
+        let magnitude = (self.0 as i128).abs();
+        write!(
+            f,
+            "{}{}.{:07}",
+            sign,
+            magnitude / 10_000_000,
+            magnitude % 10_000_000
+        )
+    }
+}
+
+#[cfg(test)]
+mod amount_delta_tests {
+    use super::*;
+    #[test]
+    fn it_displays_signed_deltas() {
+        assert_eq!(format!("{}", AmountDelta::new(-10)), "-0.0000010");
+        assert_eq!(format!("{}", AmountDelta::new(123456789)), "12.3456789");
+        assert_eq!(
+            format!("{}", AmountDelta::new(::std::i64::MIN)),
+            "-922337203685.4775808"
+        );
+    }
+    #[test]
+    fn it_negates_and_measures_deltas() {
+        let delta = -AmountDelta::from(Amount::new(12));
+        assert!(delta.is_negative());
+        assert_eq!(delta.magnitude(), Some(Amount(12)));
+        assert_eq!(AmountDelta::new(::std::i64::MIN).magnitude(), None);
+        assert_eq!(AmountDelta::new(::std::i64::MIN).checked_neg(), None);
+        assert_eq!(
+            -AmountDelta::new(::std::i64::MIN),
+            AmountDelta::new(::std::i64::MAX)
+        );
+        assert_eq!(
+            delta.checked_add(AmountDelta::new(20)),
+            Some(AmountDelta::new(8))
+        );
+    }
 }
 
 #[cfg(test)]
@@ -30,6 +319,10 @@ mod amount_tests {
         assert_eq!(Amount::new(12), Amount(12));
     }
     #[test]
+    fn it_returns_the_stroops() {
+        assert_eq!(Amount::new(12).stroops(), 12);
+    }
+    #[test]
     fn it_orders_amounts() {
         assert!(Amount::new(12) > Amount(3));
     }
@@ -44,7 +337,8 @@ impl<'a> Add for &'a Amount {
     type Output = Amount;
 
     /// Adding two amounts returns a new amount with the value
-    /// being the sum of the two input values
+    /// being the sum of the two input values. Use `checked_add` when
+    /// the sum may overflow.
     fn add(self, other: &Amount) -> Amount {
         Amount::new(self.0 + other.0)
     }
@@ -54,7 +348,9 @@ impl<'a> Sub for &'a Amount {
     type Output = Amount;
 
     /// Subtracting two amounts returns a new amount with the value
-    /// being the difference of the two values.
+    /// being the difference of the two values. This panics if the
+    /// difference is negative, so use `checked_sub` or `delta_from`
+    /// when it may be.
     fn sub(self, other: &Amount) -> Amount {
         Amount::new(self.0 - other.0)
     }
@@ -148,7 +148,8 @@ impl AssetIdentifier {
         AssetIdentifier::Native
     }
 
-    /// A type safe way of creating an alphanum4 asset AssetIdentifier
+    /// A type safe way of creating an alphanum4 asset AssetIdentifier. The issuer is
+    /// checked as an account id when the asset is sent to horizon.
     pub fn alphanum4(code: &str, issuer: &str) -> AssetIdentifier {
         AssetIdentifier::CreditAlphanum4(AssetId {
             code: code.to_string(),
@@ -156,7 +157,8 @@ impl AssetIdentifier {
         })
     }
 
-    /// A type safe way of creating an alphanum12 asset AssetIdentifier
+    /// A type safe way of creating an alphanum12 asset AssetIdentifier. The issuer is
+    /// checked as an account id when the asset is sent to horizon.
     pub fn alphanum12(code: &str, issuer: &str) -> AssetIdentifier {
         AssetIdentifier::CreditAlphanum12(AssetId {
             code: code.to_string(),
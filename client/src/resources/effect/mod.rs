@@ -1,5 +1,6 @@
 use resources::{asset::Flags, Amount, AssetIdentifier};
 use serde::{de, Deserialize, Deserializer};
+use serde_json::Value;
 
 pub mod account;
 pub mod data;
@@ -34,6 +35,16 @@ pub enum EffectKind {
     Trade(trade::Kind),
     /// An effect representing data being managed.
     Data(data::Kind),
+    /// An effect type that this client does not know how to parse, such as one added to
+    /// horizon after this release. The json is kept so that it can still be inspected.
+    Unknown {
+        /// The numeric type of the effect as given by horizon.
+        type_i: u32,
+        /// The name of the effect type as given by horizon, e.g. `sequence_bumped`.
+        type_name: String,
+        /// The effect as it was returned by horizon.
+        raw: Value,
+    },
     // The stellar api docs list other operations for offers, but as of this writing those
     // endpoints do not yet exist in horizon https://github.com/stellar/go/issues/166
 }
@@ -81,6 +92,7 @@ impl Effect {
                 data::Kind::Updated(_) => 42,
             },
             Kind::Trade(_) => 33,
+            Kind::Unknown { type_i, .. } => type_i,
         }
     }
 
@@ -119,6 +131,7 @@ impl Effect {
                 data::Kind::Removed(_) => "Data removed",
                 data::Kind::Updated(_) => "Data updated",
             },
+            Kind::Unknown { ref type_name, .. } => type_name,
         }
     }
 
@@ -273,6 +286,49 @@ impl Effect {
             _ => false,
         }
     }
+
+    /// Returns true if the effect is of a type this client does not know how to parse
+    pub fn is_unknown(&self) -> bool {
+        match self.kind {
+            Kind::Unknown { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+/// The effect types that are parsed into their own kind. Any other type is kept as
+/// `EffectKind::Unknown`.
+const KNOWN_KINDS: [&str; 19] = [
+    "account_created",
+    "account_removed",
+    "account_credited",
+    "account_debited",
+    "account_thresholds_updated",
+    "account_home_domain_updated",
+    "account_flags_updated",
+    "signer_created",
+    "signer_removed",
+    "signer_updated",
+    "trustline_created",
+    "trustline_removed",
+    "trustline_updated",
+    "trustline_authorized",
+    "trustline_deauthorized",
+    "trade",
+    "data_created",
+    "data_removed",
+    "data_updated",
+];
+
+/// The fields common to every effect, which are all that can be parsed from one of an
+/// unknown type.
+#[derive(Debug, Deserialize, Clone)]
+struct Header {
+    id: String,
+    paging_token: String,
+    #[serde(rename = "type")]
+    kind: String,
+    type_i: u32,
 }
 
 /// Represents the actual structure of the json api. This allows us to parse
@@ -316,7 +372,20 @@ impl<'de> Deserialize<'de> for Effect {
     where
         D: Deserializer<'de>,
     {
-        let rep = Intermediate::deserialize(d)?;
+        let raw = Value::deserialize(d)?;
+        let header = Header::deserialize(&raw).map_err(de::Error::custom)?;
+        if !KNOWN_KINDS.contains(&header.kind.as_str()) {
+            return Ok(Effect {
+                id: header.id,
+                paging_token: header.paging_token,
+                kind: Kind::Unknown {
+                    type_i: header.type_i,
+                    type_name: header.kind,
+                    raw,
+                },
+            });
+        }
+        let rep = Intermediate::deserialize(raw).map_err(de::Error::custom)?;
 
         let kind: Kind = match rep.kind.as_str() {
             "account_created" => match rep {
@@ -1,3 +1,4 @@
+use endpoint::Records;
 use resources::{
     asset::Flags,
     effect::{
@@ -443,3 +444,33 @@ mod errors_on_missing_fields_for_effect_types {
     assert_err_on_missing_fields!(data_removed, 41);
     assert_err_on_missing_fields!(data_updated, 42);
 }
+
+fn sequence_bumped_json() -> &'static str {
+    include_str!("../../../fixtures/effects/sequence_bumped.json")
+}
+
+#[test]
+fn it_parses_an_unknown_effect_from_json() {
+    let effect: Effect = serde_json::from_str(&sequence_bumped_json()).unwrap();
+    assert!(effect.is_unknown());
+    assert_eq!(effect.id(), "0074277063196418049-0000000001");
+    assert_eq!(effect.type_i(), 43);
+    assert_eq!(effect.kind_name(), "sequence_bumped");
+    if let &EffectKind::Unknown { ref raw, .. } = effect.kind() {
+        assert_eq!(raw["new_seq"], "74277063196418100");
+    } else {
+        panic!("Did not generate unknown kind");
+    }
+}
+
+#[test]
+fn it_parses_a_page_with_unknown_effects() {
+    let json = format!(
+        r#"{{"_embedded": {{"records": [{}, {}]}}}}"#,
+        sequence_bumped_json(),
+        account_created_json()
+    );
+    let records: Records<Effect> = serde_json::from_str(&json).unwrap();
+    assert!(records.records()[0].is_unknown());
+    assert!(records.records()[1].is_account_created());
+}
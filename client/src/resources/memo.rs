@@ -0,0 +1,269 @@
+use base64;
+use serde::{de, Deserialize, Deserializer};
+use std::error::Error;
+use std::fmt;
+use xdr::{self, Hash};
+
+/// The longest a text memo may be, in bytes.
+pub const MAX_TEXT_LENGTH: usize = 28;
+
+/// Memos are a useful source for adding meta data to a transaction.
+/// A consists of a type and content (unless memo type is none).
+///
+/// To learn more about the concept of memos in the Stellar network, take a look at the Stellar memo description here:
+/// <https://www.stellar.org/developers/guides/concepts/transactions.html#memo>
+///
+/// ## Example
+/// ```
+/// use stellar_client::resources::Memo;
+///
+/// let memo = Memo::text("deposit 1234").unwrap();
+/// assert!(Memo::text("this memo is far too long to fit").is_err());
+///
+/// // Exchanges use id memos above the range of an i64.
+/// let memo = Memo::Id(18_446_744_073_709_551_615);
+/// assert!(memo.to_xdr().is_ok());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Memo {
+    ///  A string encoded using either ASCII or UTF-8, up to 28-bytes long.
+    /// Horizon replaces bytes that aren't UTF-8 with U+FFFD, so text it
+    /// returns may be longer.
+    Text(String),
+    /// A 64 bit unsigned integer.
+    Id(u64),
+    /// A 32 byte hash.
+    Hash(Hash),
+    /// A 32 byte hash intended to be interpreted as the hash of the transaction the sender is refunding.
+    Return(Hash),
+    /// The most common scenario where there is no memo included on the transaction.
+    None,
+}
+
+impl Memo {
+    /// A text memo, which must be at most 28 bytes.
+    pub fn text(text: &str) -> Result<Memo, MemoError> {
+        if text.len() > MAX_TEXT_LENGTH {
+            return Err(MemoError::TextTooLong);
+        }
+        Ok(Memo::Text(text.to_string()))
+    }
+
+    /// The memo as it is written in a transaction's xdr. This fails only for
+    /// text memos over 28 bytes, which `Memo::text` won't create.
+    pub fn to_xdr(&self) -> Result<xdr::Memo, MemoError> {
+        Ok(match *self {
+            Memo::Text(ref text) if text.len() <= MAX_TEXT_LENGTH => {
+                xdr::Memo::Text(text.as_bytes().to_vec())
+            }
+            Memo::Text(_) => return Err(MemoError::TextTooLong),
+            Memo::Id(id) => xdr::Memo::Id(id),
+            Memo::Hash(hash) => xdr::Memo::Hash(hash),
+            Memo::Return(hash) => xdr::Memo::Return(hash),
+            Memo::None => xdr::Memo::None,
+        })
+    }
+}
+
+impl From<xdr::Memo> for Memo {
+    fn from(memo: xdr::Memo) -> Memo {
+        match memo {
+            xdr::Memo::Text(text) => Memo::Text(String::from_utf8_lossy(&text).into_owned()),
+            xdr::Memo::Id(id) => Memo::Id(id),
+            xdr::Memo::Hash(hash) => Memo::Hash(hash),
+            xdr::Memo::Return(hash) => Memo::Return(hash),
+            xdr::Memo::None => Memo::None,
+        }
+    }
+}
+
+/// Errors that occur when a memo is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MemoError {
+    /// The text was over 28 bytes.
+    TextTooLong,
+    /// The text was not a string.
+    InvalidText,
+    /// The id was not an unsigned 64 bit integer.
+    InvalidId,
+    /// The hash was not 32 bytes encoded as base64 or hex.
+    InvalidHash,
+    /// The memo type was not one that stellar defines.
+    InvalidType,
+}
+
+impl Error for MemoError {
+    fn description(&self) -> &str {
+        match *self {
+            MemoError::TextTooLong => "The memo text is over 28 bytes",
+            MemoError::InvalidText => "The memo text is not a string",
+            MemoError::InvalidId => "The memo id is not an unsigned 64 bit integer",
+            MemoError::InvalidHash => "The memo hash is not 32 bytes of base64 or hex",
+            MemoError::InvalidType => "The memo type is not text, id, hash, return or none",
+        }
+    }
+}
+
+impl fmt::Display for MemoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Horizon writes ids as strings, so that they aren't rounded by javascript,
+/// but older responses wrote them as numbers.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum MemoValue {
+    Number(u64),
+    String(String),
+}
+
+#[derive(Deserialize, Debug)]
+struct IntermediateMemo {
+    memo_type: String,
+    #[serde(default)]
+    memo: Option<MemoValue>,
+}
+
+impl<'de> Deserialize<'de> for Memo {
+    fn deserialize<D>(d: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let rep = IntermediateMemo::deserialize(d)?;
+        let memo = match (rep.memo_type.as_str(), rep.memo) {
+            ("none", _) => Ok(Memo::None),
+            ("text", Some(MemoValue::String(text))) => Ok(Memo::Text(text)),
+            ("text", None) => Ok(Memo::Text(String::new())),
+            ("id", Some(MemoValue::Number(id))) => Ok(Memo::Id(id)),
+            ("id", Some(MemoValue::String(id))) => {
+                id.parse().map(Memo::Id).map_err(|_| MemoError::InvalidId)
+            }
+            ("hash", Some(MemoValue::String(hash))) => decode_hash(&hash).map(Memo::Hash),
+            ("return", Some(MemoValue::String(hash))) => decode_hash(&hash).map(Memo::Return),
+            ("text", _) => Err(MemoError::InvalidText),
+            ("id", _) => Err(MemoError::InvalidId),
+            ("hash", _) | ("return", _) => Err(MemoError::InvalidHash),
+            _ => Err(MemoError::InvalidType),
+        };
+        memo.map_err(de::Error::custom)
+    }
+}
+
+/// Horizon encodes memo hashes in base64, but they are often written in hex.
+/// Either is accepted.
+fn decode_hash(encoded: &str) -> Result<Hash, MemoError> {
+    let bytes = if encoded.len() == 64 {
+        encoded
+            .as_bytes()
+            .chunks(2)
+            .map(|pair| Some((hex_digit(pair[0])? << 4) | hex_digit(pair[1])?))
+            .collect::<Option<Vec<u8>>>()
+            .ok_or(MemoError::InvalidHash)?
+    } else {
+        base64::decode(encoded).map_err(|_| MemoError::InvalidHash)?
+    };
+    if bytes.len() != 32 {
+        return Err(MemoError::InvalidHash);
+    }
+    let mut hash = [0; 32];
+    hash.copy_from_slice(&bytes);
+    Ok(hash)
+}
+
+/// The value of an ascii hex digit of either case.
+fn hex_digit(byte: u8) -> Option<u8> {
+    (byte as char).to_digit(16).map(|digit| digit as u8)
+}
+
+#[cfg(test)]
+mod memo_tests {
+    use super::*;
+    use serde_json;
+
+    static HEX: &str = "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824";
+
+    fn parse(json: &str) -> Result<Memo, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+
+    #[test]
+    fn it_validates_text_length() {
+        assert_eq!(
+            Memo::text("a".repeat(28).as_str()),
+            Ok(Memo::Text("a".repeat(28)))
+        );
+        assert_eq!(Memo::text(&"a".repeat(29)), Err(MemoError::TextTooLong));
+    }
+
+    #[test]
+    fn it_parses_text_that_horizon_escaped_past_28_bytes() {
+        // Ten bytes that aren't utf-8, which horizon replaces with U+FFFD.
+        let escaped = "\u{fffd}".repeat(10);
+        let json = format!(r#"{{"memo_type": "text", "memo": "{}"}}"#, escaped);
+        let memo = parse(&json).unwrap();
+        assert_eq!(memo, Memo::Text(escaped));
+        assert_eq!(memo.to_xdr(), Err(MemoError::TextTooLong));
+        assert!(parse(r#"{"memo_type": "text", "memo": 12}"#).is_err());
+    }
+
+    #[test]
+    fn it_parses_ids_above_the_range_of_an_i64() {
+        let max = ::std::u64::MAX;
+        let json = format!(r#"{{"memo_type": "id", "memo": "{}"}}"#, max);
+        assert_eq!(parse(&json).unwrap(), Memo::Id(max));
+        let json = format!(r#"{{"memo_type": "id", "memo": {}}}"#, max);
+        assert_eq!(parse(&json).unwrap(), Memo::Id(max));
+        assert!(parse(r#"{"memo_type": "id", "memo": "-1"}"#).is_err());
+    }
+
+    #[test]
+    fn it_decodes_hashes_from_base64_and_hex() {
+        let hash = decode_hash(HEX).unwrap();
+        assert_eq!(&hash[..2], &[0x2c, 0xf2]);
+        let json = format!(
+            r#"{{"memo_type": "hash", "memo": "{}"}}"#,
+            base64::encode(&hash)
+        );
+        assert_eq!(parse(&json).unwrap(), Memo::Hash(hash));
+        let json = format!(r#"{{"memo_type": "return", "memo": "{}"}}"#, HEX);
+        assert_eq!(parse(&json).unwrap(), Memo::Return(hash));
+        assert_eq!(decode_hash("abc"), Err(MemoError::InvalidHash));
+    }
+
+    #[test]
+    fn it_rejects_hex_hashes_with_other_characters() {
+        assert_eq!(decode_hash(&"+f".repeat(32)), Err(MemoError::InvalidHash));
+        let straddling = format!("a\u{e9}{}", "a".repeat(61));
+        assert_eq!(straddling.len(), 64);
+        assert_eq!(decode_hash(&straddling), Err(MemoError::InvalidHash));
+        let json = format!(r#"{{"memo_type": "hash", "memo": "{}"}}"#, straddling);
+        assert!(parse(&json).is_err());
+    }
+
+    #[test]
+    fn it_round_trips_through_xdr() {
+        let memos = vec![
+            Memo::None,
+            Memo::text("hello").unwrap(),
+            Memo::Id(::std::u64::MAX),
+            Memo::Hash([1; 32]),
+            Memo::Return([2; 32]),
+        ];
+        for memo in memos {
+            let encoded = xdr::to_base64(&memo.to_xdr().unwrap());
+            let decoded: xdr::Memo = xdr::from_base64(&encoded).unwrap();
+            assert_eq!(Memo::from(decoded), memo);
+        }
+        assert_eq!(
+            Memo::Text("a".repeat(29)).to_xdr(),
+            Err(MemoError::TextTooLong)
+        );
+    }
+
+    #[test]
+    fn it_rejects_unknown_memo_types() {
+        assert!(parse(r#"{"memo_type": "emoji", "memo": "x"}"#).is_err());
+    }
+}
@@ -11,6 +11,7 @@ mod deserialize;
 /// An effect represents specific changes that occur in the ledger resulting from operations.
 pub mod effect;
 mod ledger;
+mod memo;
 mod offer;
 /// An operation is an individual command that mutates the ledger.
 pub mod operation;
@@ -25,16 +26,16 @@ mod transaction;
 /// A collection of data types and resources used within the stellar api.
 /// All the derives for XDR and JSON are implemented for the resources so that
 /// they can be used with a client. Either for reading or for writing.
-pub use self::account::Account;
-pub use self::amount::{Amount, ParseAmountError};
+pub use self::account::{Account, Balance, Signer, SignerType, Thresholds};
+pub use self::amount::{Amount, AmountDelta, ParseAmountError, Rounding};
 pub use self::asset::{Asset, AssetIdentifier, Flags, ParseAssetIdentifierError};
 pub use self::datum::Datum;
 pub use self::effect::Effect;
 pub use self::ledger::Ledger;
-pub use self::offer::Offer;
+pub use self::memo::{Memo, MemoError};
+pub use self::offer::{Offer, ParsePriceError, PriceRatio};
 pub use self::operation::{Operation, OperationKind};
 pub use self::orderbook::Orderbook;
 pub use self::payment_path::PaymentPath;
 pub use self::trade::{Seller as TradeSeller, Trade, TradeAggregation};
-pub use self::transaction::Memo;
-pub use self::transaction::Transaction;
+pub use self::transaction::{Transaction, TransactionSubmission};
@@ -1,8 +1,27 @@
 use resources::{Amount, AssetIdentifier};
+use std::cmp::Ordering;
+use std::error::Error;
 use std::fmt;
+use std::str::FromStr;
 
-/// The ratio between the asking and selling price
-#[derive(Serialize, Deserialize, Debug, Eq, PartialEq, Copy, Clone)]
+/// The largest numerator or denominator stellar-core accepts in a price.
+const MAX_PRICE_TERM: u128 = ::std::i32::MAX as u128;
+
+/// The ratio between the asking and selling price.
+///
+/// Ratios are compared by value, so `1/2` equals `2/4`. A ratio with a zero
+/// denominator isn't a number and is only equal to itself.
+///
+/// ## Example
+/// ```
+/// use stellar_client::resources::PriceRatio;
+///
+/// let price: PriceRatio = "0.1234567".parse().unwrap();
+/// assert_eq!(price, PriceRatio::new(1234567, 10000000));
+/// assert_eq!(price.to_decimal_string(), Some("0.1234567".to_string()));
+/// assert!(price < price.invert());
+/// ```
+#[derive(Serialize, Deserialize, Debug, Copy, Clone)]
 pub struct PriceRatio {
     #[serde(rename = "n")]
     numerator: u64,
@@ -34,6 +53,288 @@ impl PriceRatio {
     pub fn denominator(&self) -> u64 {
         self.denominator
     }
+
+    /// Finds the closest ratio to a decimal price whose numerator and
+    /// denominator both fit in an `i32`, as stellar-core requires. Prices
+    /// that can be represented exactly, such as `"0.1234567"`, are.
+    ///
+    /// The approximation is found with continued fractions, stopping before
+    /// either term grows too large.
+    pub fn approximate(price: &str) -> Result<PriceRatio, ParsePriceError> {
+        let (mut numerator, mut denominator) = parse_decimal(price)?;
+        // The convergents of the continued fraction, starting from the
+        // conventional 0/1 and 1/0.
+        let (mut h0, mut k0, mut h1, mut k1) = (0u128, 1u128, 1u128, 0u128);
+        while denominator != 0 {
+            let whole = numerator / denominator;
+            let h = whole * h1 + h0;
+            let k = whole * k1 + k0;
+            if whole > MAX_PRICE_TERM || h > MAX_PRICE_TERM || k > MAX_PRICE_TERM {
+                break;
+            }
+            h0 = h1;
+            k0 = k1;
+            h1 = h;
+            k1 = k;
+            let remainder = numerator % denominator;
+            numerator = denominator;
+            denominator = remainder;
+        }
+        if h1 == 0 || k1 == 0 {
+            return Err(ParsePriceError::OutOfRange);
+        }
+        Ok(PriceRatio::new(h1 as u64, k1 as u64))
+    }
+
+    /// The ratio in lowest terms.
+    pub fn reduce(&self) -> PriceRatio {
+        let divisor = gcd(self.numerator, self.denominator).max(1);
+        PriceRatio::new(self.numerator / divisor, self.denominator / divisor)
+    }
+
+    /// The inverse of the ratio. An offer's price is given in terms of the
+    /// asset being bought, so this swaps the base and counter assets.
+    pub fn invert(&self) -> PriceRatio {
+        PriceRatio::new(self.denominator, self.numerator)
+    }
+
+    /// Multiplies the ratios, such as the prices of two hops in a path.
+    /// The product is reduced to lowest terms, and is `None` if it still
+    /// overflows.
+    pub fn checked_mul(&self, other: PriceRatio) -> Option<PriceRatio> {
+        let numerator = u128::from(self.numerator) * u128::from(other.numerator);
+        let denominator = u128::from(self.denominator) * u128::from(other.denominator);
+        let divisor = gcd_u128(numerator, denominator).max(1);
+        let (numerator, denominator) = (numerator / divisor, denominator / divisor);
+        if numerator > u128::from(::std::u64::MAX) || denominator > u128::from(::std::u64::MAX) {
+            return None;
+        }
+        Some(PriceRatio::new(numerator as u64, denominator as u64))
+    }
+
+    /// The exact decimal value of the ratio, such as `"0.25"` for `1/4`.
+    /// Ratios whose decimal value repeats, like `1/3`, have no exact decimal
+    /// form and return `None`, as do ratios with a zero denominator.
+    pub fn to_decimal_string(&self) -> Option<String> {
+        let reduced = self.reduce();
+        if reduced.denominator == 0 {
+            return None;
+        }
+        // The decimal terminates only when the denominator has no prime
+        // factors other than two and five.
+        let mut rest = reduced.denominator;
+        let (mut twos, mut fives) = (0, 0);
+        while rest % 2 == 0 {
+            rest /= 2;
+            twos += 1;
+        }
+        while rest % 5 == 0 {
+            rest /= 5;
+            fives += 1;
+        }
+        if rest != 1 {
+            return None;
+        }
+        let places = ::std::cmp::max(twos, fives);
+        let whole = reduced.numerator / reduced.denominator;
+        let mut remainder = u128::from(reduced.numerator % reduced.denominator);
+        let denominator = u128::from(reduced.denominator);
+        let mut decimal = whole.to_string();
+        if places > 0 {
+            decimal.push('.');
+            for _ in 0..places {
+                remainder *= 10;
+                decimal.push_str(&(remainder / denominator).to_string());
+                remainder %= denominator;
+            }
+        }
+        Some(decimal)
+    }
+}
+
+impl PartialEq for PriceRatio {
+    fn eq(&self, other: &PriceRatio) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl Eq for PriceRatio {}
+
+impl PartialOrd for PriceRatio {
+    fn partial_cmp(&self, other: &PriceRatio) -> Option<Ordering> {
+        if self.denominator == 0 || other.denominator == 0 {
+            let identical =
+                self.numerator == other.numerator && self.denominator == other.denominator;
+            return if identical {
+                Some(Ordering::Equal)
+            } else {
+                None
+            };
+        }
+        let left = u128::from(self.numerator) * u128::from(other.denominator);
+        let right = u128::from(other.numerator) * u128::from(self.denominator);
+        Some(left.cmp(&right))
+    }
+}
+
+impl FromStr for PriceRatio {
+    type Err = ParsePriceError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PriceRatio::approximate(s)
+    }
+}
+
+/// Errors that occur when parsing a decimal price.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParsePriceError {
+    /// The price was not a positive decimal number.
+    Invalid,
+    /// The price was zero, or too large or too small to approximate within
+    /// the bounds of a price.
+    OutOfRange,
+}
+
+impl Error for ParsePriceError {
+    fn description(&self) -> &str {
+        match *self {
+            ParsePriceError::Invalid => "The price is not a positive decimal number",
+            ParsePriceError::OutOfRange => "The price can not be represented within i32 bounds",
+        }
+    }
+}
+
+impl fmt::Display for ParsePriceError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Parses a decimal string into an exact fraction.
+fn parse_decimal(price: &str) -> Result<(u128, u128), ParsePriceError> {
+    let (whole, fraction) = match price.find('.') {
+        Some(index) => (&price[..index], &price[index + 1..]),
+        None => (price, ""),
+    };
+    let digits = || whole.chars().chain(fraction.chars());
+    if whole.is_empty() && fraction.is_empty() || !digits().all(|c| c.is_ascii_digit()) {
+        return Err(ParsePriceError::Invalid);
+    }
+    let mut numerator: u128 = 0;
+    let mut denominator: u128 = 1;
+    for digit in digits() {
+        numerator = numerator
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(u128::from(digit.to_digit(10).unwrap())))
+            .ok_or(ParsePriceError::OutOfRange)?;
+    }
+    for _ in fraction.chars() {
+        denominator = denominator
+            .checked_mul(10)
+            .ok_or(ParsePriceError::OutOfRange)?;
+    }
+    Ok((numerator, denominator))
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+fn gcd_u128(a: u128, b: u128) -> u128 {
+    if b == 0 {
+        a
+    } else {
+        gcd_u128(b, a % b)
+    }
+}
+
+#[cfg(test)]
+mod price_ratio_tests {
+    use super::*;
+
+    #[test]
+    fn it_compares_by_value() {
+        assert_eq!(PriceRatio::new(1, 2), PriceRatio::new(2, 4));
+        assert!(PriceRatio::new(1, 3) < PriceRatio::new(1, 2));
+        assert!(PriceRatio::new(3, 2) > PriceRatio::new(1, 1));
+        assert_ne!(PriceRatio::new(1, 0), PriceRatio::new(2, 0));
+        assert_eq!(PriceRatio::new(1, 0), PriceRatio::new(1, 0));
+        assert_eq!(
+            PriceRatio::new(1, 0).partial_cmp(&PriceRatio::new(1, 2)),
+            None
+        );
+    }
+
+    #[test]
+    fn it_inverts_and_reduces() {
+        let price = PriceRatio::new(6, 4);
+        assert_eq!(price.invert().numerator(), 4);
+        assert_eq!(price.invert().denominator(), 6);
+        let reduced = price.reduce();
+        assert_eq!((reduced.numerator(), reduced.denominator()), (3, 2));
+    }
+
+    #[test]
+    fn it_multiplies_ratios() {
+        let product = PriceRatio::new(2, 3)
+            .checked_mul(PriceRatio::new(9, 4))
+            .unwrap();
+        assert_eq!((product.numerator(), product.denominator()), (3, 2));
+        let max = PriceRatio::new(::std::u64::MAX, 1);
+        assert_eq!(max.checked_mul(PriceRatio::new(2, 1)), None);
+        assert_eq!(max.checked_mul(PriceRatio::new(2, 2)), Some(max));
+    }
+
+    #[test]
+    fn it_converts_to_an_exact_decimal() {
+        let decimal = |n, d| PriceRatio::new(n, d).to_decimal_string();
+        assert_eq!(decimal(1, 4), Some("0.25".to_string()));
+        assert_eq!(decimal(10, 2), Some("5".to_string()));
+        assert_eq!(decimal(1234567, 10000000), Some("0.1234567".to_string()));
+        assert_eq!(
+            decimal(7, 1 << 20),
+            Some("0.00000667572021484375".to_string())
+        );
+        assert_eq!(decimal(1, 3), None);
+        assert_eq!(decimal(1, 0), None);
+    }
+
+    #[test]
+    fn it_approximates_decimal_prices() {
+        let parse = |price: &str| {
+            let ratio = PriceRatio::approximate(price).unwrap();
+            (ratio.numerator(), ratio.denominator())
+        };
+        assert_eq!(parse("0.1234567"), (1234567, 10000000));
+        assert_eq!(parse("1.25"), (5, 4));
+        assert_eq!(parse("2"), (2, 1));
+        assert_eq!(parse("0.333333333333333333"), (1, 3));
+        assert_eq!(parse("3.14159265358979323846"), (1068966896, 340262731));
+        assert_eq!(parse("2147483647"), (2147483647, 1));
+    }
+
+    #[test]
+    fn it_rejects_prices_it_can_not_approximate() {
+        assert_eq!(
+            PriceRatio::approximate("0"),
+            Err(ParsePriceError::OutOfRange)
+        );
+        assert_eq!(
+            PriceRatio::approximate("2147483648"),
+            Err(ParsePriceError::OutOfRange)
+        );
+        assert_eq!(
+            PriceRatio::approximate("0.0000000001"),
+            Err(ParsePriceError::OutOfRange)
+        );
+        assert_eq!("-1".parse::<PriceRatio>(), Err(ParsePriceError::Invalid));
+        assert_eq!("1.2.3".parse::<PriceRatio>(), Err(ParsePriceError::Invalid));
+        assert_eq!(".".parse::<PriceRatio>(), Err(ParsePriceError::Invalid));
+    }
 }
 
 /// Summary of an offer to be shown in an orderbook
@@ -1,6 +1,7 @@
 use super::deserialize;
 use resources::{asset::Flags, offer::PriceRatio, Amount, AssetIdentifier};
 use serde::{de, Deserialize, Deserializer};
+use serde_json::Value;
 mod account_merge;
 mod allow_trust;
 mod change_trust;
@@ -90,6 +91,16 @@ pub enum OperationKind {
     Inflation,
     /// Set, modify or delete a Data Entry (name/value pair) for an account.
     ManageData(ManageData),
+    /// An operation type that this client does not know how to parse, such as one added to
+    /// horizon after this release. The json is kept so that it can still be inspected.
+    Unknown {
+        /// The numeric type of the operation as given by horizon.
+        type_i: u32,
+        /// The name of the operation type as given by horizon, e.g. `bump_sequence`.
+        type_name: String,
+        /// The operation as it was returned by horizon.
+        raw: Value,
+    },
 }
 // Use inside file to be brief
 use self::OperationKind as Kind;
@@ -125,6 +136,7 @@ impl Operation {
             Kind::AccountMerge(_) => 8,
             Kind::Inflation => 9,
             Kind::ManageData(_) => 10,
+            Kind::Unknown { type_i, .. } => type_i,
         }
     }
 
@@ -147,6 +159,7 @@ impl Operation {
             Kind::AccountMerge(_) => "Account Merge",
             Kind::Inflation => "Inflation",
             Kind::ManageData(_) => "Manage Data",
+            Kind::Unknown { ref type_name, .. } => type_name,
         }
     }
 
@@ -237,6 +250,43 @@ impl Operation {
             _ => false,
         }
     }
+
+    /// Returns true if the operation is of a type this client does not know how to parse
+    pub fn is_unknown(&self) -> bool {
+        match self.kind {
+            Kind::Unknown { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+/// The operation types that are parsed into their own kind. Any other type is kept as
+/// `OperationKind::Unknown`.
+const KNOWN_KINDS: [&str; 11] = [
+    "create_account",
+    "payment",
+    "path_payment",
+    "manage_offer",
+    "create_passive_offer",
+    "set_options",
+    "change_trust",
+    "allow_trust",
+    "account_merge",
+    "inflation",
+    "manage_data",
+];
+
+/// The fields common to every operation, which are all that can be parsed from one of
+/// an unknown type.
+#[derive(Debug, Deserialize, Clone)]
+struct Header {
+    #[serde(deserialize_with = "deserialize::from_str")]
+    id: i64,
+    paging_token: String,
+    transaction_hash: String,
+    #[serde(rename = "type")]
+    kind: String,
+    type_i: u32,
 }
 
 /// Represents the actual structure of the json api. This allows us to parse
@@ -298,7 +348,21 @@ impl<'de> Deserialize<'de> for Operation {
     where
         D: Deserializer<'de>,
     {
-        let rep = Intermediate::deserialize(d)?;
+        let raw = Value::deserialize(d)?;
+        let header = Header::deserialize(&raw).map_err(de::Error::custom)?;
+        if !KNOWN_KINDS.contains(&header.kind.as_str()) {
+            return Ok(Operation {
+                id: header.id,
+                paging_token: header.paging_token,
+                transaction_hash: header.transaction_hash,
+                kind: Kind::Unknown {
+                    type_i: header.type_i,
+                    type_name: header.kind,
+                    raw,
+                },
+            });
+        }
+        let rep = Intermediate::deserialize(raw).map_err(de::Error::custom)?;
 
         let kind: Kind = match rep.kind.as_str() {
             "create_account" => match rep {
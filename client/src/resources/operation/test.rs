@@ -1,3 +1,4 @@
+use endpoint::Records;
 use resources::{asset::Flags, Amount, Operation, OperationKind};
 use serde_json;
 
@@ -300,3 +301,34 @@ fn it_parses_a_set_options_from_json() {
         panic!("Did not generate set options kind");
     }
 }
+
+fn bump_sequence_json() -> &'static str {
+    include_str!("../../../fixtures/operations/bump_sequence.json")
+}
+
+#[test]
+fn it_parses_an_unknown_operation_from_json() {
+    let operation: Operation = serde_json::from_str(&bump_sequence_json()).unwrap();
+    assert!(operation.is_unknown());
+    assert_eq!(operation.id(), 74277063196418049);
+    assert_eq!(operation.paging_token(), "74277063196418049");
+    assert_eq!(operation.type_i(), 11);
+    assert_eq!(operation.kind_name(), "bump_sequence");
+    if let &OperationKind::Unknown { ref raw, .. } = operation.kind() {
+        assert_eq!(raw["bump_to"], "74277063196418100");
+    } else {
+        panic!("Did not generate unknown kind");
+    }
+}
+
+#[test]
+fn it_parses_a_page_with_unknown_operations() {
+    let json = format!(
+        r#"{{"_embedded": {{"records": [{}, {}]}}}}"#,
+        bump_sequence_json(),
+        payment_json()
+    );
+    let records: Records<Operation> = serde_json::from_str(&json).unwrap();
+    assert!(records.records()[0].is_unknown());
+    assert!(records.records()[1].is_payment());
+}
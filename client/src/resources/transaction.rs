@@ -1,29 +1,6 @@
-use super::{amount::Amount, deserialize};
+use super::{amount::Amount, deserialize, memo::Memo};
 use chrono::prelude::*;
-
-/// Memos are a useful source for adding meta data to a transaction.
-/// A consists of a type and content (unless memo type is none).
-///
-/// To learn more about the concept of memos in the Stellar network, take a look at the Stellar memo description here:
-/// <https://www.stellar.org/developers/guides/concepts/transactions.html#memo>
-#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
-#[serde(
-    rename_all = "lowercase",
-    tag = "memo_type",
-    content = "memo"
-)]
-pub enum Memo {
-    ///  A string encoded using either ASCII or UTF-8, up to 28-bytes long.
-    Text(String),
-    /// A 64 bit unsigned integer.
-    Id(i64),
-    /// A 32 byte hash.
-    Hash(String),
-    /// A 32 byte hash intended to be interpreted as the hash of the transaction the sender is refunding.
-    Return(String),
-    /// The most common scenario where there is no memo included on the transaction.
-    None,
-}
+use xdr::{self, TransactionEnvelope, TransactionMeta, TransactionResult};
 
 /// Transactions are the basic unit of change in the Stellar Network.
 /// A transaction is a grouping of operations.
@@ -110,16 +87,34 @@ impl Transaction {
         &self.envelope_xdr
     }
 
-    /// A base64 encoded string of the raw TransactionResultPair xdr struct for this transaction
+    /// Decodes the envelope xdr into the transaction that was submitted, including its
+    /// operations, time bounds and memo, along with the signatures that authorized it.
+    pub fn envelope(&self) -> xdr::Result<TransactionEnvelope> {
+        xdr::from_base64(&self.envelope_xdr)
+    }
+
+    /// A base64 encoded string of the raw TransactionResult xdr struct for this transaction
     pub fn result_xdr(&self) -> &String {
         &self.result_xdr
     }
 
+    /// Decodes the result xdr into the fee charged, the overall result code of the
+    /// transaction and the result of each of its operations.
+    pub fn result(&self) -> xdr::Result<TransactionResult> {
+        xdr::from_base64(&self.result_xdr)
+    }
+
     /// A base64 encoded string of the raw TransactionMeta xdr struct for this transaction
     pub fn result_meta_xdr(&self) -> &String {
         &self.result_meta_xdr
     }
 
+    /// Decodes the result meta xdr into the ledger entries each operation created, updated
+    /// or removed. Updates and removals are preceded by the state of the entry beforehand.
+    pub fn result_meta(&self) -> xdr::Result<TransactionMeta> {
+        xdr::from_base64(&self.result_meta_xdr)
+    }
+
     /// A base64 encoded string of the raw LedgerEntryChanges xdr struct produced by taking fees
     /// for this transaction.
     pub fn fee_meta_xdr(&self) -> &String {
@@ -132,6 +127,72 @@ impl Transaction {
     }
 }
 
+/// The result of successfully submitting a transaction to horizon. The
+/// transaction has been applied to the ledger by the time this is returned.
+///
+/// <https://www.stellar.org/developers/horizon/reference/endpoints/transactions-create.html>
+#[derive(Deserialize, Debug, Clone)]
+pub struct TransactionSubmission {
+    hash: String,
+    ledger: u32,
+    envelope_xdr: String,
+    result_xdr: String,
+    result_meta_xdr: String,
+}
+
+impl TransactionSubmission {
+    /// A hex-encoded SHA-256 hash of the transaction’s XDR-encoded form.
+    pub fn hash(&self) -> &String {
+        &self.hash
+    }
+
+    /// Sequence number of the ledger in which this transaction was applied.
+    pub fn ledger(&self) -> u32 {
+        self.ledger
+    }
+
+    /// A base64 encoded string of the raw TransactionEnvelope xdr struct for this transaction
+    pub fn envelope_xdr(&self) -> &String {
+        &self.envelope_xdr
+    }
+
+    /// A base64 encoded string of the raw TransactionResult xdr struct for this transaction
+    pub fn result_xdr(&self) -> &String {
+        &self.result_xdr
+    }
+
+    /// A base64 encoded string of the raw TransactionMeta xdr struct for this transaction
+    pub fn result_meta_xdr(&self) -> &String {
+        &self.result_meta_xdr
+    }
+}
+
+#[cfg(test)]
+mod transaction_submission_tests {
+    use super::*;
+    use serde_json;
+
+    fn submission_json() -> &'static str {
+        include_str!("../../fixtures/transactions/submission.json")
+    }
+
+    #[test]
+    fn it_parses_a_submission() {
+        let submission: TransactionSubmission = serde_json::from_str(&submission_json()).unwrap();
+        assert_eq!(
+            submission.hash(),
+            "648da0d47aa3b3b20afd4499a68f89b6d10ead8b1f38858e99b1d94b6fef6e69"
+        );
+        assert_eq!(submission.ledger(), 16751283);
+        assert_eq!(
+            submission.result_xdr(),
+            "AAAAAAAAAGQAAAAAAAAAAQAAAAAAAAABAAAAAAAAAAA="
+        );
+        assert!(submission.envelope_xdr().starts_with("AAAAAH2Hmt1J"));
+        assert!(submission.result_meta_xdr().starts_with("AAAAAAAAAAEA"));
+    }
+}
+
 #[cfg(test)]
 mod transaction_tests {
     use super::*;
@@ -221,6 +282,89 @@ mod transaction_tests {
         }
     }
 
+    #[test]
+    fn it_decodes_the_envelope() {
+        let transaction: Transaction = serde_json::from_str(&transaction_json()).unwrap();
+        let envelope = transaction.envelope().unwrap();
+        assert_eq!(envelope.tx.fee, 100);
+        assert_eq!(
+            envelope.tx.seq_num as u64,
+            transaction.source_account_sequence()
+        );
+        assert_eq!(envelope.tx.time_bounds, None);
+        assert_eq!(
+            envelope.tx.operations.len() as u32,
+            transaction.operation_count()
+        );
+        assert_eq!(envelope.signatures.len(), 1);
+        assert_eq!(envelope.signatures[0].hint, [0xef, 0xef, 0xc4, 0x06]);
+    }
+
+    #[test]
+    fn it_fails_to_decode_an_invalid_envelope() {
+        let mut transaction: Transaction = serde_json::from_str(&transaction_json()).unwrap();
+        transaction.envelope_xdr = "AAAA".to_string();
+        assert!(transaction.envelope().is_err());
+    }
+
+    #[test]
+    fn it_decodes_the_result() {
+        let transaction: Transaction = serde_json::from_str(&transaction_json()).unwrap();
+        let result = transaction.result().unwrap();
+        assert_eq!(result.fee_charged, transaction.fee_paid());
+        assert_eq!(result.code, xdr::TransactionResultCode::Success);
+        assert_eq!(
+            result.results,
+            vec![xdr::OperationResult::Payment(xdr::PaymentResult::Success)]
+        );
+    }
+
+    #[test]
+    fn it_decodes_the_result_of_a_failed_transaction() {
+        let mut transaction: Transaction = serde_json::from_str(&transaction_json()).unwrap();
+        transaction.result_xdr = "AAAAAAAAAGT/////AAAAAQAAAAAAAAAB/////gAAAAA=".to_string();
+        let result = transaction.result().unwrap();
+        assert_eq!(result.code, xdr::TransactionResultCode::Failed);
+        assert_eq!(
+            result.results,
+            vec![xdr::OperationResult::Payment(
+                xdr::PaymentResult::Underfunded
+            )]
+        );
+    }
+
+    #[test]
+    fn it_decodes_the_result_meta() {
+        use xdr::{LedgerEntryChange, LedgerEntryData};
+
+        let transaction: Transaction = serde_json::from_str(&transaction_json()).unwrap();
+        let meta = transaction.result_meta().unwrap();
+        assert_eq!(meta.operations().len(), 1);
+
+        let balances: Vec<_> = meta.operations()[0]
+            .changes
+            .iter()
+            .map(|change| match *change {
+                LedgerEntryChange::State(ref entry) | LedgerEntryChange::Updated(ref entry) => {
+                    match entry.data {
+                        LedgerEntryData::Account(ref account) => account.balance,
+                        ref data => panic!("Expected an account: {:?}", data),
+                    }
+                }
+                ref change => panic!("Expected a state or update: {:?}", change),
+            })
+            .collect();
+        assert_eq!(
+            balances,
+            vec![
+                12_019_657_489_797_950,
+                12_019_656_489_897_950,
+                14_446_885,
+                1_014_346_885,
+            ]
+        );
+    }
+
     #[test]
     fn it_parses_memo_text() {
         let transaction: Transaction = serde_json::from_str(&transaction_json_memo_text()).unwrap();
@@ -235,10 +379,8 @@ mod transaction_tests {
     fn it_parses_memo_hash() {
         let transaction: Transaction = serde_json::from_str(&transaction_json_memo_hash()).unwrap();
         if let &Memo::Hash(ref value) = transaction.memo() {
-            assert_eq!(
-                value,
-                "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824"
-            );
+            assert_eq!(&value[..4], &[0x2c, 0xf2, 0x4d, 0xba]);
+            assert_eq!(value[31], 0x24);
         } else {
             panic!("Can't parse memo hash.");
         }
@@ -260,10 +402,8 @@ mod transaction_tests {
             serde_json::from_str(&transaction_json_memo_return()).unwrap();
 
         if let &Memo::Return(ref value) = transaction.memo() {
-            assert_eq!(
-                value,
-                "2CF24DBA5FB0A30E26E83B2AC5B9E29E1B161E5C1FA7425E73043362938B9824"
-            );
+            assert_eq!(&value[..4], &[0x2c, 0xf2, 0x4d, 0xba]);
+            assert_eq!(value[31], 0x24);
         } else {
             panic!("Can't parse memo return.");
         }
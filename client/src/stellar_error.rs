@@ -13,6 +13,7 @@ pub struct StellarError {
     status: u16,
     detail: String,
     instance: Option<String>,
+    extras: Option<Extras>,
 }
 
 #[derive(Deserialize)]
@@ -24,6 +25,41 @@ struct Intermediate {
     status: u16,
     detail: String,
     instance: Option<String>,
+    extras: Option<Extras>,
+}
+
+/// Additional details horizon includes with some errors. Only the fields
+/// relevant to the kind of error are present.
+#[derive(Deserialize, Debug)]
+struct Extras {
+    envelope_xdr: Option<String>,
+    result_xdr: Option<String>,
+    result_codes: Option<ResultCodes>,
+}
+
+/// The result codes returned when a transaction fails to be applied to the ledger.
+/// There is a code for the transaction as a whole and one for each operation
+/// within it, in the order they appear in the transaction.
+///
+/// <https://www.stellar.org/developers/guides/concepts/list-of-operations.html>
+#[derive(Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ResultCodes {
+    transaction: String,
+    #[serde(default)]
+    operations: Vec<String>,
+}
+
+impl ResultCodes {
+    /// The result code of the transaction, such as `tx_failed` or `tx_bad_seq`.
+    pub fn transaction(&self) -> &str {
+        &self.transaction
+    }
+
+    /// The result codes of each operation, such as `op_underfunded`. This will be
+    /// empty when the transaction failed before any operations were applied.
+    pub fn operations(&self) -> &[String] {
+        &self.operations
+    }
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, Copy)]
@@ -69,6 +105,7 @@ impl<'de> Deserialize<'de> for StellarError {
             status: int.status,
             detail: int.detail,
             instance: int.instance,
+            extras: int.extras,
         })
     }
 }
@@ -219,6 +256,30 @@ impl StellarError {
     pub fn is_unknown_error(&self) -> bool {
         self.kind == Kind::UnknownError
     }
+
+    /// The result codes of a transaction that failed. Only present on
+    /// `transaction_failed` errors.
+    pub fn result_codes(&self) -> Option<&ResultCodes> {
+        self.extras.as_ref().and_then(|e| e.result_codes.as_ref())
+    }
+
+    /// A base64 encoded string of the TransactionEnvelope xdr struct that was
+    /// submitted. Present when a transaction submission fails.
+    pub fn envelope_xdr(&self) -> Option<&str> {
+        self.extras
+            .as_ref()
+            .and_then(|e| e.envelope_xdr.as_ref())
+            .map(|s| &**s)
+    }
+
+    /// A base64 encoded string of the TransactionResult xdr struct that the
+    /// network produced. Present when a transaction submission fails.
+    pub fn result_xdr(&self) -> Option<&str> {
+        self.extras
+            .as_ref()
+            .and_then(|e| e.result_xdr.as_ref())
+            .map(|s| &**s)
+    }
 }
 
 impl Error for StellarError {
@@ -242,6 +303,10 @@ mod asset_identifier_tests {
         include_str!("../fixtures/before_history_error.json")
     }
 
+    fn transaction_failed_json() -> &'static str {
+        include_str!("../fixtures/transaction_failed_error.json")
+    }
+
     #[test]
     fn it_parses_stellar_errors_from_json() {
         let before_history: StellarError = serde_json::from_str(&before_history_json()).unwrap();
@@ -260,6 +325,27 @@ mod asset_identifier_tests {
         );
     }
 
+    #[test]
+    fn it_parses_the_result_codes_of_failed_transactions() {
+        let error: StellarError = serde_json::from_str(&transaction_failed_json()).unwrap();
+        assert!(error.is_transaction_failed());
+        let codes = error.result_codes().unwrap();
+        assert_eq!(codes.transaction(), "tx_failed");
+        assert_eq!(codes.operations(), &["op_underfunded".to_string()]);
+        assert_eq!(
+            error.result_xdr(),
+            Some("AAAAAAAAAGT/////AAAAAQAAAAAAAAAB/////gAAAAA=")
+        );
+        assert!(error.envelope_xdr().is_some());
+    }
+
+    #[test]
+    fn it_has_no_result_codes_for_other_errors() {
+        let before_history: StellarError = serde_json::from_str(&before_history_json()).unwrap();
+        assert_eq!(before_history.result_codes(), None);
+        assert_eq!(before_history.envelope_xdr(), None);
+    }
+
     #[test]
     fn it_will_deserialize_unknown_errors() {
         let kind: Kind = serde_json::from_str("\"bad type\"").unwrap();
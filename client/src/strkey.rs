@@ -0,0 +1,338 @@
+//! Encodes and decodes the string form of stellar keys.
+//!
+//! Keys are written as a version byte, the 32 byte key and a CRC16-XModem checksum
+//! encoded in base32. The version byte determines the first character of the key:
+//! `G` for public keys, `S` for secret seeds, `T` for pre-authorized transactions and
+//! `X` for sha256 hashes.
+//!
+//! <https://www.stellar.org/developers/guides/concepts/accounts.html#account-id>
+//!
+//! ## Example
+//! ```
+//! use stellar_client::strkey::{self, Version};
+//!
+//! let account_id = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+//! let key = strkey::decode(Version::PublicKey, account_id).unwrap();
+//! assert_eq!(strkey::encode(Version::PublicKey, &key), account_id);
+//!
+//! // A single character out of place fails the checksum.
+//! let typo = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF4";
+//! assert!(strkey::decode(Version::PublicKey, typo).is_err());
+//! ```
+use std::error::Error as StdError;
+use std::fmt;
+use std::str::FromStr;
+use xdr::{PublicKey, SignerKey};
+
+static ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// The length of an encoded key: one version byte, 32 bytes of key and two bytes of
+/// checksum make 35 bytes, or 56 characters of base32.
+const ENCODED_LEN: usize = 56;
+
+/// The kind of key that is encoded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Version {
+    /// An ed25519 public key, which starts with a `G`.
+    PublicKey,
+    /// An ed25519 secret seed, which starts with an `S`.
+    Seed,
+    /// The hash of a pre-authorized transaction, which starts with a `T`.
+    PreAuthTx,
+    /// A sha256 hash whose preimage is a signer, which starts with an `X`.
+    HashX,
+}
+
+impl Version {
+    fn byte(self) -> u8 {
+        match self {
+            Version::PublicKey => 6 << 3,
+            Version::Seed => 18 << 3,
+            Version::PreAuthTx => 19 << 3,
+            Version::HashX => 23 << 3,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Option<Version> {
+        [
+            Version::PublicKey,
+            Version::Seed,
+            Version::PreAuthTx,
+            Version::HashX,
+        ]
+        .iter()
+        .cloned()
+        .find(|version| version.byte() == byte)
+    }
+}
+
+/// Errors that occur when decoding a key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The key was not 56 characters long.
+    InvalidLength,
+    /// The key contained a character outside of the base32 alphabet.
+    InvalidCharacter,
+    /// The key was a different kind of key than expected.
+    InvalidVersion,
+    /// The checksum did not match the key.
+    InvalidChecksum,
+}
+
+/// A result including strkey errors.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::InvalidLength => "The key is not 56 characters long",
+            Error::InvalidCharacter => "The key contains a character that is not base32",
+            Error::InvalidVersion => "The key is not the expected kind of key",
+            Error::InvalidChecksum => "The key does not match its checksum",
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+/// Encodes a key of the given kind.
+pub fn encode(version: Version, key: &[u8; 32]) -> String {
+    let mut bytes = Vec::with_capacity(35);
+    bytes.push(version.byte());
+    bytes.extend_from_slice(key);
+    let checksum = crc16(&bytes);
+    bytes.push(checksum as u8);
+    bytes.push((checksum >> 8) as u8);
+    base32_encode(&bytes)
+}
+
+/// Decodes a key, checking that it is the given kind of key and that it matches its
+/// checksum.
+pub fn decode(version: Version, encoded: &str) -> Result<[u8; 32]> {
+    let (found, key) = decode_any(encoded)?;
+    if found != version {
+        return Err(Error::InvalidVersion);
+    }
+    Ok(key)
+}
+
+/// Decodes a key of any kind, returning the kind of key along with it.
+pub fn decode_any(encoded: &str) -> Result<(Version, [u8; 32])> {
+    if encoded.len() != ENCODED_LEN {
+        return Err(Error::InvalidLength);
+    }
+    let bytes = base32_decode(encoded)?;
+    let (payload, checksum) = bytes.split_at(33);
+    let expected = crc16(payload);
+    if checksum != [expected as u8, (expected >> 8) as u8] {
+        return Err(Error::InvalidChecksum);
+    }
+    let version = Version::from_byte(payload[0]).ok_or(Error::InvalidVersion)?;
+    let mut key = [0; 32];
+    key.copy_from_slice(&payload[1..]);
+    Ok((version, key))
+}
+
+/// Returns true if the string is a valid public key, such as an account id.
+pub fn is_valid_account_id(encoded: &str) -> bool {
+    decode(Version::PublicKey, encoded).is_ok()
+}
+
+impl FromStr for PublicKey {
+    type Err = Error;
+
+    fn from_str(encoded: &str) -> Result<Self> {
+        decode(Version::PublicKey, encoded).map(PublicKey::Ed25519)
+    }
+}
+
+impl fmt::Display for PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            PublicKey::Ed25519(ref key) => f.write_str(&encode(Version::PublicKey, key)),
+        }
+    }
+}
+
+impl FromStr for SignerKey {
+    type Err = Error;
+
+    fn from_str(encoded: &str) -> Result<Self> {
+        match decode_any(encoded)? {
+            (Version::PublicKey, key) => Ok(SignerKey::Ed25519(key)),
+            (Version::PreAuthTx, hash) => Ok(SignerKey::PreAuthTx(hash)),
+            (Version::HashX, hash) => Ok(SignerKey::HashX(hash)),
+            (Version::Seed, _) => Err(Error::InvalidVersion),
+        }
+    }
+}
+
+impl fmt::Display for SignerKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let encoded = match *self {
+            SignerKey::Ed25519(ref key) => encode(Version::PublicKey, key),
+            SignerKey::PreAuthTx(ref hash) => encode(Version::PreAuthTx, hash),
+            SignerKey::HashX(ref hash) => encode(Version::HashX, hash),
+        };
+        f.write_str(&encoded)
+    }
+}
+
+/// The CRC16-XModem checksum of the bytes.
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0;
+    for byte in bytes {
+        crc ^= u16::from(*byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Encodes bytes as unpadded base32. Keys are always 35 bytes, which is a whole number
+/// of 5 byte groups, so padding is never needed.
+fn base32_encode(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() * 8 + 4) / 5);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for byte in bytes {
+        buffer = (buffer << 8) | u16::from(*byte);
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        encoded.push(ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+fn base32_decode(encoded: &str) -> Result<Vec<u8>> {
+    let mut bytes = Vec::with_capacity(encoded.len() * 5 / 8);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for character in encoded.bytes() {
+        let value = ALPHABET
+            .iter()
+            .position(|c| *c == character)
+            .ok_or(Error::InvalidCharacter)?;
+        buffer = (buffer << 5) | value as u16;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod strkey_tests {
+    use super::*;
+
+    static ACCOUNT_ID: &str = "GB6YPGW5JFMMP2QB2USQ33EUWTXVL4ZT5ITUNCY3YKVWOJPP57CANOF3";
+    static SEED: &str = "SBMVFTVKOTJWEROPLCAG4OBSXYC56U5LAFKXA636MO2DJRSFU6BBBXUV";
+
+    #[test]
+    fn it_computes_the_xmodem_checksum() {
+        assert_eq!(crc16(b"123456789"), 0x31c3);
+    }
+
+    #[test]
+    fn it_round_trips_base32() {
+        let bytes: Vec<u8> = (0..35).collect();
+        assert_eq!(base32_decode(&base32_encode(&bytes)).unwrap(), bytes);
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+    }
+
+    #[test]
+    fn it_decodes_an_account_id() {
+        let key = decode(Version::PublicKey, ACCOUNT_ID).unwrap();
+        assert_eq!(&key[..4], &[0x7d, 0x87, 0x9a, 0xdd]);
+        assert_eq!(encode(Version::PublicKey, &key), ACCOUNT_ID);
+        assert!(is_valid_account_id(ACCOUNT_ID));
+    }
+
+    #[test]
+    fn it_decodes_a_seed() {
+        let key = decode(Version::Seed, SEED).unwrap();
+        assert_eq!(encode(Version::Seed, &key), SEED);
+    }
+
+    #[test]
+    fn it_round_trips_each_version() {
+        let versions = [
+            (Version::PublicKey, 'G'),
+            (Version::Seed, 'S'),
+            (Version::PreAuthTx, 'T'),
+            (Version::HashX, 'X'),
+        ];
+        for &(version, prefix) in &versions {
+            let encoded = encode(version, &[7; 32]);
+            assert!(encoded.starts_with(prefix));
+            assert_eq!(decode_any(&encoded).unwrap(), (version, [7; 32]));
+        }
+    }
+
+    #[test]
+    fn it_rejects_the_wrong_version() {
+        assert_eq!(
+            decode(Version::Seed, ACCOUNT_ID),
+            Err(Error::InvalidVersion)
+        );
+        assert!(!is_valid_account_id(SEED));
+    }
+
+    #[test]
+    fn it_rejects_a_bad_checksum() {
+        let typo = ACCOUNT_ID.replace("NOF3", "NOF4");
+        assert_eq!(
+            decode(Version::PublicKey, &typo),
+            Err(Error::InvalidChecksum)
+        );
+    }
+
+    #[test]
+    fn it_rejects_malformed_keys() {
+        assert_eq!(
+            decode(Version::PublicKey, "abc123"),
+            Err(Error::InvalidLength)
+        );
+        let lowercase = ACCOUNT_ID.to_lowercase();
+        assert_eq!(
+            decode(Version::PublicKey, &lowercase),
+            Err(Error::InvalidCharacter)
+        );
+    }
+
+    #[test]
+    fn it_parses_and_displays_xdr_keys() {
+        let key: PublicKey = ACCOUNT_ID.parse().unwrap();
+        assert_eq!(key.to_string(), ACCOUNT_ID);
+
+        let signer: SignerKey = ACCOUNT_ID.parse().unwrap();
+        assert_eq!(
+            signer,
+            SignerKey::Ed25519(match key {
+                PublicKey::Ed25519(bytes) => bytes,
+            })
+        );
+        let hash_x = encode(Version::HashX, &[1; 32]);
+        assert_eq!(
+            hash_x.parse::<SignerKey>().unwrap(),
+            SignerKey::HashX([1; 32])
+        );
+        assert!(SEED.parse::<SignerKey>().is_err());
+    }
+}
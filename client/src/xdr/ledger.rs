@@ -0,0 +1,630 @@
+use super::{
+    AccountId, Asset, Error, FromXdr, Liabilities, Price, Reader, Result, Signer, Thresholds,
+    ToXdr, Writer, UNBOUNDED,
+};
+use std::borrow::Cow;
+
+/// Reads the extension that newer entries use to carry their liabilities.
+fn read_liabilities(reader: &mut Reader) -> Result<Option<Liabilities>> {
+    match reader.read()? {
+        0 => Ok(None),
+        1 => {
+            let liabilities = reader.read()?;
+            reader.read_reserved()?;
+            Ok(Some(liabilities))
+        }
+        discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+    }
+}
+
+fn write_liabilities(writer: &mut Writer, liabilities: &Option<Liabilities>) {
+    match *liabilities {
+        Some(ref liabilities) => {
+            writer.write(&1i32);
+            writer.write(liabilities);
+            writer.write_reserved();
+        }
+        None => writer.write_reserved(),
+    }
+}
+
+/// An account as it is stored in the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountEntry {
+    /// The id of the account.
+    pub account_id: AccountId,
+    /// The balance, in stroops, of lumens held by the account.
+    pub balance: i64,
+    /// The last sequence number used by the account.
+    pub seq_num: i64,
+    /// The number of trustlines, offers, signers and data entries.
+    pub num_sub_entries: u32,
+    /// The account that inflation votes go to.
+    pub inflation_dest: Option<AccountId>,
+    /// The authorization flags of the account.
+    pub flags: u32,
+    /// The domain that hosts the account's stellar.toml, as the bytes stored
+    /// in the ledger.
+    pub home_domain: Vec<u8>,
+    /// The master weight and thresholds of the account.
+    pub thresholds: Thresholds,
+    /// The additional signers on the account.
+    pub signers: Vec<Signer>,
+    /// The lumens committed to offers, if the ledger records it.
+    pub liabilities: Option<Liabilities>,
+}
+
+impl AccountEntry {
+    /// The home domain as text, with any bytes that aren't utf-8 replaced by
+    /// U+FFFD.
+    pub fn home_domain_text(&self) -> Cow<str> {
+        String::from_utf8_lossy(&self.home_domain)
+    }
+}
+
+impl FromXdr for AccountEntry {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(AccountEntry {
+            account_id: reader.read()?,
+            balance: reader.read()?,
+            seq_num: reader.read()?,
+            num_sub_entries: reader.read()?,
+            inflation_dest: reader.read()?,
+            flags: reader.read()?,
+            home_domain: reader.read_opaque(32)?,
+            thresholds: reader.read()?,
+            signers: reader.read_array(20)?,
+            liabilities: read_liabilities(reader)?,
+        })
+    }
+}
+
+impl ToXdr for AccountEntry {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.account_id);
+        writer.write(&self.balance);
+        writer.write(&self.seq_num);
+        writer.write(&self.num_sub_entries);
+        writer.write(&self.inflation_dest);
+        writer.write(&self.flags);
+        writer.write_opaque(&self.home_domain);
+        writer.write(&self.thresholds);
+        writer.write_array(&self.signers);
+        write_liabilities(writer, &self.liabilities);
+    }
+}
+
+/// A trustline as it is stored in the ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TrustLineEntry {
+    /// The account that holds the trustline.
+    pub account_id: AccountId,
+    /// The asset that is trusted.
+    pub asset: Asset,
+    /// The balance, in stroops, of the asset held.
+    pub balance: i64,
+    /// The most, in stroops, the account is willing to hold.
+    pub limit: i64,
+    /// The authorization flags of the trustline.
+    pub flags: u32,
+    /// The amounts committed to offers, if the ledger records it.
+    pub liabilities: Option<Liabilities>,
+}
+
+impl FromXdr for TrustLineEntry {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(TrustLineEntry {
+            account_id: reader.read()?,
+            asset: reader.read()?,
+            balance: reader.read()?,
+            limit: reader.read()?,
+            flags: reader.read()?,
+            liabilities: read_liabilities(reader)?,
+        })
+    }
+}
+
+impl ToXdr for TrustLineEntry {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.account_id);
+        writer.write(&self.asset);
+        writer.write(&self.balance);
+        writer.write(&self.limit);
+        writer.write(&self.flags);
+        write_liabilities(writer, &self.liabilities);
+    }
+}
+
+/// An offer as it is stored in the ledger.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OfferEntry {
+    /// The account that made the offer.
+    pub seller_id: AccountId,
+    /// The id of the offer.
+    pub offer_id: u64,
+    /// The asset being sold.
+    pub selling: Asset,
+    /// The asset being bought.
+    pub buying: Asset,
+    /// The amount, in stroops, of the selling asset left to sell.
+    pub amount: i64,
+    /// The price of one unit of selling in terms of buying.
+    pub price: Price,
+    /// The flags of the offer.
+    pub flags: u32,
+}
+
+impl FromXdr for OfferEntry {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        let entry = OfferEntry {
+            seller_id: reader.read()?,
+            offer_id: reader.read()?,
+            selling: reader.read()?,
+            buying: reader.read()?,
+            amount: reader.read()?,
+            price: reader.read()?,
+            flags: reader.read()?,
+        };
+        reader.read_reserved()?;
+        Ok(entry)
+    }
+}
+
+impl ToXdr for OfferEntry {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.seller_id);
+        writer.write(&self.offer_id);
+        writer.write(&self.selling);
+        writer.write(&self.buying);
+        writer.write(&self.amount);
+        writer.write(&self.price);
+        writer.write(&self.flags);
+        writer.write_reserved();
+    }
+}
+
+/// A data entry as it is stored in the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataEntry {
+    /// The account the data is attached to.
+    pub account_id: AccountId,
+    /// The name of the entry, as the bytes stored in the ledger.
+    pub data_name: Vec<u8>,
+    /// The value of the entry.
+    pub data_value: Vec<u8>,
+}
+
+impl DataEntry {
+    /// The name as text, with any bytes that aren't utf-8 replaced by U+FFFD.
+    pub fn data_name_text(&self) -> Cow<str> {
+        String::from_utf8_lossy(&self.data_name)
+    }
+}
+
+impl FromXdr for DataEntry {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        let entry = DataEntry {
+            account_id: reader.read()?,
+            data_name: reader.read_opaque(64)?,
+            data_value: reader.read_opaque(64)?,
+        };
+        reader.read_reserved()?;
+        Ok(entry)
+    }
+}
+
+impl ToXdr for DataEntry {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.account_id);
+        writer.write_opaque(&self.data_name);
+        writer.write_opaque(&self.data_value);
+        writer.write_reserved();
+    }
+}
+
+/// The data held by a ledger entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerEntryData {
+    /// An account.
+    Account(AccountEntry),
+    /// A trustline.
+    TrustLine(TrustLineEntry),
+    /// An offer.
+    Offer(OfferEntry),
+    /// A data entry.
+    Data(DataEntry),
+}
+
+impl FromXdr for LedgerEntryData {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(LedgerEntryData::Account(reader.read()?)),
+            1 => Ok(LedgerEntryData::TrustLine(reader.read()?)),
+            2 => Ok(LedgerEntryData::Offer(reader.read()?)),
+            3 => Ok(LedgerEntryData::Data(reader.read()?)),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for LedgerEntryData {
+    fn to_xdr(&self, writer: &mut Writer) {
+        let (kind, entry): (i32, &ToXdr) = match *self {
+            LedgerEntryData::Account(ref entry) => (0, entry),
+            LedgerEntryData::TrustLine(ref entry) => (1, entry),
+            LedgerEntryData::Offer(ref entry) => (2, entry),
+            LedgerEntryData::Data(ref entry) => (3, entry),
+        };
+        writer.write(&kind);
+        writer.write(entry);
+    }
+}
+
+/// An entry in the ledger and the ledger in which it last changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LedgerEntry {
+    /// The sequence of the ledger in which the entry last changed.
+    pub last_modified_ledger_seq: u32,
+    /// The data of the entry.
+    pub data: LedgerEntryData,
+}
+
+impl FromXdr for LedgerEntry {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        let entry = LedgerEntry {
+            last_modified_ledger_seq: reader.read()?,
+            data: reader.read()?,
+        };
+        reader.read_reserved()?;
+        Ok(entry)
+    }
+}
+
+impl ToXdr for LedgerEntry {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.last_modified_ledger_seq);
+        writer.write(&self.data);
+        writer.write_reserved();
+    }
+}
+
+/// The key that identifies an entry in the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerKey {
+    /// An account.
+    Account {
+        /// The id of the account.
+        account_id: AccountId,
+    },
+    /// A trustline.
+    TrustLine {
+        /// The account that holds the trustline.
+        account_id: AccountId,
+        /// The asset that is trusted.
+        asset: Asset,
+    },
+    /// An offer.
+    Offer {
+        /// The account that made the offer.
+        seller_id: AccountId,
+        /// The id of the offer.
+        offer_id: u64,
+    },
+    /// A data entry.
+    Data {
+        /// The account the data is attached to.
+        account_id: AccountId,
+        /// The name of the entry, as its exact bytes.
+        data_name: Vec<u8>,
+    },
+}
+
+impl FromXdr for LedgerKey {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(LedgerKey::Account {
+                account_id: reader.read()?,
+            }),
+            1 => Ok(LedgerKey::TrustLine {
+                account_id: reader.read()?,
+                asset: reader.read()?,
+            }),
+            2 => Ok(LedgerKey::Offer {
+                seller_id: reader.read()?,
+                offer_id: reader.read()?,
+            }),
+            3 => Ok(LedgerKey::Data {
+                account_id: reader.read()?,
+                data_name: reader.read_opaque(64)?,
+            }),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for LedgerKey {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            LedgerKey::Account { ref account_id } => {
+                writer.write(&0i32);
+                writer.write(account_id);
+            }
+            LedgerKey::TrustLine {
+                ref account_id,
+                ref asset,
+            } => {
+                writer.write(&1i32);
+                writer.write(account_id);
+                writer.write(asset);
+            }
+            LedgerKey::Offer {
+                ref seller_id,
+                offer_id,
+            } => {
+                writer.write(&2i32);
+                writer.write(seller_id);
+                writer.write(&offer_id);
+            }
+            LedgerKey::Data {
+                ref account_id,
+                ref data_name,
+            } => {
+                writer.write(&3i32);
+                writer.write(account_id);
+                writer.write_opaque(data_name);
+            }
+        }
+    }
+}
+
+/// A change made to an entry in the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LedgerEntryChange {
+    /// The entry was created.
+    Created(LedgerEntry),
+    /// The entry was updated, and this is its new state.
+    Updated(LedgerEntry),
+    /// The entry was removed.
+    Removed(LedgerKey),
+    /// The state of the entry before it was updated or removed.
+    State(LedgerEntry),
+}
+
+impl FromXdr for LedgerEntryChange {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(LedgerEntryChange::Created(reader.read()?)),
+            1 => Ok(LedgerEntryChange::Updated(reader.read()?)),
+            2 => Ok(LedgerEntryChange::Removed(reader.read()?)),
+            3 => Ok(LedgerEntryChange::State(reader.read()?)),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for LedgerEntryChange {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            LedgerEntryChange::Created(ref entry) => {
+                writer.write(&0i32);
+                writer.write(entry);
+            }
+            LedgerEntryChange::Updated(ref entry) => {
+                writer.write(&1i32);
+                writer.write(entry);
+            }
+            LedgerEntryChange::Removed(ref key) => {
+                writer.write(&2i32);
+                writer.write(key);
+            }
+            LedgerEntryChange::State(ref entry) => {
+                writer.write(&3i32);
+                writer.write(entry);
+            }
+        }
+    }
+}
+
+/// The changes an operation made to the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OperationMeta {
+    /// The changes, in the order they were made.
+    pub changes: Vec<LedgerEntryChange>,
+}
+
+impl FromXdr for OperationMeta {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(OperationMeta {
+            changes: reader.read_array(UNBOUNDED)?,
+        })
+    }
+}
+
+impl ToXdr for OperationMeta {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write_array(&self.changes);
+    }
+}
+
+/// The changes a transaction made to the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransactionMeta {
+    /// The changes made by each operation.
+    V0(Vec<OperationMeta>),
+    /// The changes made by the transaction itself, such as consuming the
+    /// sequence number, followed by the changes made by each operation.
+    V1 {
+        /// The changes made by the transaction itself.
+        tx_changes: Vec<LedgerEntryChange>,
+        /// The changes made by each operation.
+        operations: Vec<OperationMeta>,
+    },
+}
+
+impl TransactionMeta {
+    /// The changes made by each operation, in the order of the operations.
+    pub fn operations(&self) -> &[OperationMeta] {
+        match *self {
+            TransactionMeta::V0(ref operations) => operations,
+            TransactionMeta::V1 { ref operations, .. } => operations,
+        }
+    }
+}
+
+impl FromXdr for TransactionMeta {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(TransactionMeta::V0(reader.read_array(UNBOUNDED)?)),
+            1 => Ok(TransactionMeta::V1 {
+                tx_changes: reader.read_array(UNBOUNDED)?,
+                operations: reader.read_array(UNBOUNDED)?,
+            }),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for TransactionMeta {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            TransactionMeta::V0(ref operations) => {
+                writer.write(&0i32);
+                writer.write_array(operations);
+            }
+            TransactionMeta::V1 {
+                ref tx_changes,
+                ref operations,
+            } => {
+                writer.write(&1i32);
+                writer.write_array(tx_changes);
+                writer.write_array(operations);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod ledger_xdr_tests {
+    use super::super::{from_base64, to_base64, PublicKey};
+    use super::*;
+    use serde_json;
+
+    fn horizon_meta() -> String {
+        let json: serde_json::Value = serde_json::from_str(include_str!(
+            "../../fixtures/transactions/transaction_memo_text.json"
+        ))
+        .unwrap();
+        json["result_meta_xdr"].as_str().unwrap().to_string()
+    }
+
+    fn all_entries() -> &'static str {
+        include_str!("../../fixtures/xdr/meta_all_entries.xdr").trim()
+    }
+
+    #[test]
+    fn it_decodes_meta_from_horizon() {
+        let xdr = horizon_meta();
+        let meta: TransactionMeta = from_base64(&xdr).unwrap();
+        assert_eq!(meta.operations().len(), 1);
+        let changes = &meta.operations()[0].changes;
+        assert_eq!(changes.len(), 4);
+        match changes[1] {
+            LedgerEntryChange::Updated(ref entry) => {
+                assert_eq!(entry.last_modified_ledger_seq, 16_751_283);
+                match entry.data {
+                    LedgerEntryData::Account(ref account) => {
+                        assert_eq!(account.balance, 12_019_656_489_897_950);
+                        assert_eq!(account.thresholds, [1, 0, 0, 0]);
+                        assert_eq!(account.liabilities, None);
+                    }
+                    ref data => panic!("Expected an account: {:?}", data),
+                }
+            }
+            ref change => panic!("Expected an update: {:?}", change),
+        }
+        assert_eq!(to_base64(&meta), xdr);
+    }
+
+    #[test]
+    fn it_round_trips_every_kind_of_entry_and_change() {
+        let meta: TransactionMeta = from_base64(all_entries()).unwrap();
+        let (tx_changes, operations) = match meta {
+            TransactionMeta::V1 {
+                ref tx_changes,
+                ref operations,
+            } => (tx_changes, operations),
+            ref meta => panic!("Expected v1 meta: {:?}", meta),
+        };
+        assert_eq!(tx_changes.len(), 2);
+        assert_eq!(operations.len(), 2);
+
+        let changes = &operations[0].changes;
+        match changes[0] {
+            LedgerEntryChange::Created(LedgerEntry {
+                data: LedgerEntryData::Account(ref account),
+                ..
+            }) => {
+                assert_eq!(account.inflation_dest, Some(PublicKey::Ed25519([4; 32])));
+                assert_eq!(account.home_domain_text(), "stellar.org");
+                assert_eq!(account.signers.len(), 1);
+                assert_eq!(
+                    account.liabilities,
+                    Some(Liabilities {
+                        buying: 100,
+                        selling: 200,
+                    })
+                );
+            }
+            ref change => panic!("Expected a created account: {:?}", change),
+        }
+        match changes[1] {
+            LedgerEntryChange::Created(LedgerEntry {
+                data: LedgerEntryData::TrustLine(ref line),
+                ..
+            }) => {
+                assert_eq!(line.limit, 9_223_372_036_854_775_807);
+                assert_eq!(line.liabilities, None);
+            }
+            ref change => panic!("Expected a created trustline: {:?}", change),
+        }
+        match changes[2] {
+            LedgerEntryChange::Updated(LedgerEntry {
+                data: LedgerEntryData::Offer(ref offer),
+                ..
+            }) => {
+                assert_eq!(offer.offer_id, 99);
+                assert_eq!(offer.price, Price { n: 1, d: 2 });
+            }
+            ref change => panic!("Expected an updated offer: {:?}", change),
+        }
+        match changes[3] {
+            LedgerEntryChange::State(LedgerEntry {
+                data: LedgerEntryData::Data(ref data),
+                ..
+            }) => {
+                assert_eq!(data.data_name_text(), "name");
+                assert_eq!(data.data_value, b"value".to_vec());
+            }
+            ref change => panic!("Expected data state: {:?}", change),
+        }
+
+        let removed: Vec<_> = operations[1]
+            .changes
+            .iter()
+            .filter_map(|change| match *change {
+                LedgerEntryChange::Removed(ref key) => Some(key.clone()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(removed.len(), 4);
+        assert_eq!(
+            removed[3],
+            LedgerKey::Data {
+                account_id: PublicKey::Ed25519([1; 32]),
+                data_name: b"name".to_vec(),
+            }
+        );
+
+        assert_eq!(to_base64(&meta), all_entries());
+    }
+}
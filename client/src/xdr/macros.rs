@@ -0,0 +1,34 @@
+/// Declares an xdr enum, one that is encoded as its discriminant alone. The
+/// variants must be given explicit discriminants that match the schema.
+macro_rules! xdr_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $($(#[$variant_meta:meta])* $variant:ident = $value:expr,)+
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant = $value,)+
+        }
+
+        impl $crate::xdr::FromXdr for $name {
+            fn from_xdr(reader: &mut $crate::xdr::Reader) -> $crate::xdr::Result<Self> {
+                let discriminant: i32 = reader.read()?;
+                $(
+                    if discriminant == $name::$variant as i32 {
+                        return Ok($name::$variant);
+                    }
+                )+
+                Err($crate::xdr::Error::InvalidDiscriminant(discriminant))
+            }
+        }
+
+        impl $crate::xdr::ToXdr for $name {
+            fn to_xdr(&self, writer: &mut $crate::xdr::Writer) {
+                writer.write(&(*self as i32));
+            }
+        }
+    };
+}
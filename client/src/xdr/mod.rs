@@ -0,0 +1,447 @@
+//! Encoding and decoding of the XDR structures used by the stellar network.
+//! Horizon returns several fields, such as a transaction's `envelope_xdr`, as
+//! base64 encoded XDR. This module provides rust types for those structures
+//! so that they can be inspected, and built, without a round trip to horizon.
+//!
+//! Types implement `FromXdr` and `ToXdr`, and every value encodes back to
+//! exactly the bytes it was decoded from.
+//!
+//! <https://www.stellar.org/developers/guides/concepts/xdr.html>
+//!
+//! ## Example
+//!
+//! ```
+//! use stellar_client::xdr::{self, TransactionResult, TransactionResultCode};
+//!
+//! let result_xdr = "AAAAAAAAAGQAAAAAAAAAAQAAAAAAAAABAAAAAAAAAAA=";
+//! let result: TransactionResult = xdr::from_base64(result_xdr).unwrap();
+//! assert_eq!(result.fee_charged, 100);
+//! assert_eq!(result.code, TransactionResultCode::Success);
+//! assert_eq!(xdr::to_base64(&result), result_xdr);
+//! ```
+use base64;
+use std::error::Error as StdError;
+use std::fmt;
+
+#[macro_use]
+mod macros;
+
+mod ledger;
+mod result;
+mod transaction;
+mod types;
+
+pub use self::ledger::{
+    AccountEntry, DataEntry, LedgerEntry, LedgerEntryChange, LedgerEntryData, LedgerKey,
+    OfferEntry, OperationMeta, TransactionMeta, TrustLineEntry,
+};
+pub use self::result::{
+    AccountMergeResult, AllowTrustResult, BumpSequenceResult, ChangeTrustResult, ClaimOfferAtom,
+    CreateAccountResult, InflationPayout, InflationResult, ManageDataResult, ManageOfferEffect,
+    ManageOfferResult, ManageOfferSuccess, OperationResult, PathPaymentResult, PathPaymentSuccess,
+    PaymentResult, SetOptionsResult, SimplePaymentResult, TransactionResult, TransactionResultCode,
+};
+pub use self::transaction::{
+    AllowTrustAsset, AllowTrustOp, BumpSequenceOp, ChangeTrustOp, CreateAccountOp,
+    CreatePassiveOfferOp, DecoratedSignature, ManageDataOp, ManageOfferOp, Memo, Operation,
+    OperationBody, PathPaymentOp, PaymentOp, SetOptionsOp, TimeBounds, Transaction,
+    TransactionEnvelope,
+};
+pub use self::types::{
+    AccountId, Asset, Hash, Liabilities, Price, PublicKey, Signer, SignerKey, Thresholds,
+};
+
+/// The maximum length of a variable length value that the schema leaves unbounded.
+const UNBOUNDED: u32 = ::std::u32::MAX;
+
+/// Errors that occur when decoding xdr.
+#[derive(Debug)]
+pub enum Error {
+    /// The input ended before the value was completely decoded.
+    UnexpectedEof,
+    /// There were bytes left over after the value was decoded.
+    TrailingBytes,
+    /// An enum or union had a discriminant that is not part of the schema.
+    InvalidDiscriminant(i32),
+    /// A variable length value was longer than the schema allows.
+    LengthExceeded,
+    /// A boolean was encoded as something other than zero or one.
+    InvalidBool,
+    /// The padding following an opaque value or string was not zeroed.
+    InvalidPadding,
+    /// A string was not valid utf-8.
+    InvalidUtf8,
+    /// The base64 surrounding the xdr could not be decoded.
+    Base64(base64::DecodeError),
+}
+
+/// A result of decoding xdr.
+pub type Result<T> = ::std::result::Result<T, Error>;
+
+impl StdError for Error {
+    fn description(&self) -> &str {
+        match *self {
+            Error::UnexpectedEof => "The xdr ended before the value was decoded",
+            Error::TrailingBytes => "The xdr has bytes left over after the value was decoded",
+            Error::InvalidDiscriminant(_) => "The xdr has a discriminant outside of the schema",
+            Error::LengthExceeded => "The xdr has a value longer than the schema allows",
+            Error::InvalidBool => "The xdr has a boolean that isn't zero or one",
+            Error::InvalidPadding => "The xdr has padding that isn't zeroed",
+            Error::InvalidUtf8 => "The xdr has a string that isn't valid utf-8",
+            Error::Base64(ref inner) => inner.description(),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.description())
+    }
+}
+
+impl From<base64::DecodeError> for Error {
+    fn from(inner: base64::DecodeError) -> Self {
+        Error::Base64(inner)
+    }
+}
+
+/// Declares that a type can be decoded from xdr.
+pub trait FromXdr: Sized {
+    /// Decodes the value from the reader, consuming exactly the bytes of the value.
+    fn from_xdr(reader: &mut Reader) -> Result<Self>;
+}
+
+/// Declares that a type can be encoded as xdr.
+pub trait ToXdr {
+    /// Encodes the value onto the end of the writer.
+    fn to_xdr(&self, writer: &mut Writer);
+}
+
+/// Decodes a value from xdr. All of the bytes must be used by the value.
+pub fn from_bytes<T: FromXdr>(bytes: &[u8]) -> Result<T> {
+    let mut reader = Reader::new(bytes);
+    let value = reader.read()?;
+    if reader.remaining() > 0 {
+        return Err(Error::TrailingBytes);
+    }
+    Ok(value)
+}
+
+/// Decodes a value from base64 encoded xdr, the form horizon uses.
+pub fn from_base64<T: FromXdr>(encoded: &str) -> Result<T> {
+    from_bytes(&base64::decode(encoded)?)
+}
+
+/// Encodes a value as xdr.
+pub fn to_bytes<T: ToXdr>(value: &T) -> Vec<u8> {
+    let mut writer = Writer::new();
+    writer.write(value);
+    writer.into_bytes()
+}
+
+/// Encodes a value as base64 encoded xdr, the form horizon accepts.
+pub fn to_base64<T: ToXdr>(value: &T) -> String {
+    base64::encode(&to_bytes(value))
+}
+
+/// Reads xdr values from a slice of bytes.
+#[derive(Debug)]
+pub struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Creates a reader over the bytes.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Reader { bytes }
+    }
+
+    /// The number of bytes that have not been read.
+    pub fn remaining(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Decodes the next value.
+    pub fn read<T: FromXdr>(&mut self) -> Result<T> {
+        T::from_xdr(self)
+    }
+
+    /// Reads fixed length opaque data into the buffer, which determines the length.
+    pub fn read_fixed_opaque(&mut self, buffer: &mut [u8]) -> Result<()> {
+        let bytes = self.take(buffer.len())?;
+        buffer.copy_from_slice(bytes);
+        self.skip_padding(buffer.len())
+    }
+
+    /// Reads variable length opaque data of at most `max` bytes.
+    pub fn read_opaque(&mut self, max: u32) -> Result<Vec<u8>> {
+        let len = self.read_len(max)?;
+        let bytes = self.take(len)?.to_vec();
+        self.skip_padding(len)?;
+        Ok(bytes)
+    }
+
+    /// Reads a string of at most `max` bytes.
+    pub fn read_string(&mut self, max: u32) -> Result<String> {
+        String::from_utf8(self.read_opaque(max)?).map_err(|_| Error::InvalidUtf8)
+    }
+
+    /// Reads a variable length array of at most `max` values.
+    pub fn read_array<T: FromXdr>(&mut self, max: u32) -> Result<Vec<T>> {
+        let len = self.read_len(max)?;
+        // Every value takes at least four bytes, so don't trust a length
+        // that couldn't possibly fit in what's left.
+        if len > self.remaining() / 4 {
+            return Err(Error::UnexpectedEof);
+        }
+        (0..len).map(|_| self.read()).collect()
+    }
+
+    /// Reads the discriminant of a reserved extension point, which must be zero.
+    pub(crate) fn read_reserved(&mut self) -> Result<()> {
+        match self.read()? {
+            0 => Ok(()),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+
+    fn read_len(&mut self, max: u32) -> Result<usize> {
+        let len: u32 = self.read()?;
+        if len > max {
+            return Err(Error::LengthExceeded);
+        }
+        Ok(len as usize)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if len > self.bytes.len() {
+            return Err(Error::UnexpectedEof);
+        }
+        let (taken, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn skip_padding(&mut self, len: usize) -> Result<()> {
+        let padding = (4 - len % 4) % 4;
+        if self.take(padding)?.iter().any(|&b| b != 0) {
+            return Err(Error::InvalidPadding);
+        }
+        Ok(())
+    }
+}
+
+/// Writes xdr values into a buffer of bytes.
+#[derive(Debug, Default)]
+pub struct Writer {
+    bytes: Vec<u8>,
+}
+
+impl Writer {
+    /// Creates an empty writer.
+    pub fn new() -> Self {
+        Writer::default()
+    }
+
+    /// Returns the bytes that have been written.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Encodes the value.
+    pub fn write<T: ToXdr + ?Sized>(&mut self, value: &T) {
+        value.to_xdr(self)
+    }
+
+    /// Writes fixed length opaque data. The length is not written.
+    pub fn write_fixed_opaque(&mut self, bytes: &[u8]) {
+        self.bytes.extend_from_slice(bytes);
+        self.write_padding(bytes.len());
+    }
+
+    /// Writes variable length opaque data, prefixed by its length.
+    pub fn write_opaque(&mut self, bytes: &[u8]) {
+        self.write(&(bytes.len() as u32));
+        self.write_fixed_opaque(bytes);
+    }
+
+    /// Writes a string, prefixed by its length.
+    pub fn write_string(&mut self, value: &str) {
+        self.write_opaque(value.as_bytes());
+    }
+
+    /// Writes a variable length array, prefixed by its length.
+    pub fn write_array<T: ToXdr>(&mut self, values: &[T]) {
+        self.write(&(values.len() as u32));
+        for value in values {
+            self.write(value);
+        }
+    }
+
+    /// Writes the discriminant of a reserved extension point.
+    pub(crate) fn write_reserved(&mut self) {
+        self.write(&0i32);
+    }
+
+    fn write_padding(&mut self, len: usize) {
+        let padding = (4 - len % 4) % 4;
+        self.bytes.extend_from_slice(&[0; 3][..padding]);
+    }
+}
+
+macro_rules! impl_integer {
+    ($ty:ty, $len:expr) => {
+        impl FromXdr for $ty {
+            fn from_xdr(reader: &mut Reader) -> Result<Self> {
+                let bytes = reader.take($len)?;
+                Ok(bytes.iter().fold(0, |acc, &b| (acc << 8) | b as $ty))
+            }
+        }
+
+        impl ToXdr for $ty {
+            fn to_xdr(&self, writer: &mut Writer) {
+                for shift in (0..$len).rev() {
+                    writer.bytes.push((*self >> (shift * 8)) as u8);
+                }
+            }
+        }
+    };
+}
+
+impl_integer!(i32, 4);
+impl_integer!(u32, 4);
+impl_integer!(i64, 8);
+impl_integer!(u64, 8);
+
+impl FromXdr for bool {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read::<i32>()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::InvalidBool),
+        }
+    }
+}
+
+impl ToXdr for bool {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&(*self as i32));
+    }
+}
+
+/// Optional values are encoded as a boolean followed by the value, if present.
+impl<T: FromXdr> FromXdr for Option<T> {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        if reader.read()? {
+            Ok(Some(reader.read()?))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+impl<T: ToXdr> ToXdr for Option<T> {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            Some(ref value) => {
+                writer.write(&true);
+                writer.write(value);
+            }
+            None => writer.write(&false),
+        }
+    }
+}
+
+macro_rules! impl_fixed_opaque {
+    ($len:expr) => {
+        impl FromXdr for [u8; $len] {
+            fn from_xdr(reader: &mut Reader) -> Result<Self> {
+                let mut bytes = [0; $len];
+                reader.read_fixed_opaque(&mut bytes)?;
+                Ok(bytes)
+            }
+        }
+
+        impl ToXdr for [u8; $len] {
+            fn to_xdr(&self, writer: &mut Writer) {
+                writer.write_fixed_opaque(self);
+            }
+        }
+    };
+}
+
+impl_fixed_opaque!(4);
+impl_fixed_opaque!(12);
+impl_fixed_opaque!(32);
+
+#[cfg(test)]
+mod codec_tests {
+    use super::*;
+
+    #[test]
+    fn it_encodes_integers_big_endian() {
+        assert_eq!(to_bytes(&1u32), vec![0, 0, 0, 1]);
+        assert_eq!(to_bytes(&-2i32), vec![0xff, 0xff, 0xff, 0xfe]);
+        assert_eq!(
+            to_bytes(&0x0102_0304_0506_0708u64),
+            vec![1, 2, 3, 4, 5, 6, 7, 8]
+        );
+        assert_eq!(from_bytes::<i64>(&[0xff; 8]).unwrap(), -1);
+    }
+
+    #[test]
+    fn it_pads_opaque_data_to_four_bytes() {
+        let mut writer = Writer::new();
+        writer.write_opaque(&[1, 2, 3, 4, 5]);
+        let bytes = writer.into_bytes();
+        assert_eq!(bytes, vec![0, 0, 0, 5, 1, 2, 3, 4, 5, 0, 0, 0]);
+
+        let mut reader = Reader::new(&bytes);
+        assert_eq!(reader.read_opaque(8).unwrap(), vec![1, 2, 3, 4, 5]);
+        assert_eq!(reader.remaining(), 0);
+    }
+
+    #[test]
+    fn it_rejects_values_longer_than_the_schema_allows() {
+        let mut writer = Writer::new();
+        writer.write_string("hello");
+        let bytes = writer.into_bytes();
+        let mut reader = Reader::new(&bytes);
+        match reader.read_string(4) {
+            Err(Error::LengthExceeded) => {}
+            other => panic!("Expected the length to be exceeded: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_rejects_non_zero_padding() {
+        let mut reader = Reader::new(&[0, 0, 0, 1, 9, 0, 0, 1]);
+        match reader.read_opaque(4) {
+            Err(Error::InvalidPadding) => {}
+            other => panic!("Expected invalid padding: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn it_encodes_optional_values() {
+        assert_eq!(to_bytes(&Some(7u32)), vec![0, 0, 0, 1, 0, 0, 0, 7]);
+        assert_eq!(to_bytes(&None::<u32>), vec![0, 0, 0, 0]);
+        assert_eq!(
+            from_bytes::<Option<u32>>(&[0, 0, 0, 1, 0, 0, 0, 7]).unwrap(),
+            Some(7)
+        );
+        assert!(from_bytes::<Option<u32>>(&[0, 0, 0, 2, 0, 0, 0, 7]).is_err());
+    }
+
+    #[test]
+    fn it_errors_on_trailing_and_missing_bytes() {
+        match from_bytes::<u32>(&[0, 0, 0, 1, 0]) {
+            Err(Error::TrailingBytes) => {}
+            other => panic!("Expected trailing bytes: {:?}", other),
+        }
+        match from_bytes::<u64>(&[0, 0, 0, 1]) {
+            Err(Error::UnexpectedEof) => {}
+            other => panic!("Expected an unexpected eof: {:?}", other),
+        }
+    }
+}
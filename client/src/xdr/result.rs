@@ -0,0 +1,814 @@
+use super::{
+    AccountId, Asset, Error, FromXdr, OfferEntry, Reader, Result, ToXdr, Writer, UNBOUNDED,
+};
+
+xdr_enum! {
+    /// The overall result of applying a transaction.
+    pub enum TransactionResultCode {
+        /// All operations succeeded.
+        Success = 0,
+        /// One or more operations failed.
+        Failed = -1,
+        /// The ledger closed before the transaction's min time.
+        TooEarly = -2,
+        /// The ledger closed after the transaction's max time.
+        TooLate = -3,
+        /// The transaction had no operations.
+        MissingOperation = -4,
+        /// The sequence number did not match the source account.
+        BadSeq = -5,
+        /// There were too few valid signatures, or the network was wrong.
+        BadAuth = -6,
+        /// The fee would bring the account below the reserve.
+        InsufficientBalance = -7,
+        /// The source account was not found.
+        NoAccount = -8,
+        /// The fee was too small.
+        InsufficientFee = -9,
+        /// There were unused signatures attached to the transaction.
+        BadAuthExtra = -10,
+        /// An unknown error occurred.
+        InternalError = -11,
+    }
+}
+
+xdr_enum! {
+    /// The result of a create account operation.
+    pub enum CreateAccountResult {
+        /// The account was created.
+        Success = 0,
+        /// The destination was invalid.
+        Malformed = -1,
+        /// The source did not have enough funds.
+        Underfunded = -2,
+        /// The starting balance would not meet the reserve.
+        LowReserve = -3,
+        /// The account already exists.
+        AlreadyExist = -4,
+    }
+}
+
+xdr_enum! {
+    /// The result of a payment operation.
+    pub enum PaymentResult {
+        /// The payment was sent.
+        Success = 0,
+        /// The amount or asset was invalid.
+        Malformed = -1,
+        /// The source did not have enough funds.
+        Underfunded = -2,
+        /// The source does not trust the asset.
+        SrcNoTrust = -3,
+        /// The source is not authorized to send the asset.
+        SrcNotAuthorized = -4,
+        /// The destination account does not exist.
+        NoDestination = -5,
+        /// The destination does not trust the asset.
+        NoTrust = -6,
+        /// The destination is not authorized to hold the asset.
+        NotAuthorized = -7,
+        /// The destination would exceed the limit of its trustline.
+        LineFull = -8,
+        /// The issuer of the asset does not exist.
+        NoIssuer = -9,
+    }
+}
+
+/// The final payment made by a successful path payment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimplePaymentResult {
+    /// The account that received the payment.
+    pub destination: AccountId,
+    /// The asset received.
+    pub asset: Asset,
+    /// The amount, in stroops, received.
+    pub amount: i64,
+}
+
+impl FromXdr for SimplePaymentResult {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(SimplePaymentResult {
+            destination: reader.read()?,
+            asset: reader.read()?,
+            amount: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for SimplePaymentResult {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.destination);
+        writer.write(&self.asset);
+        writer.write(&self.amount);
+    }
+}
+
+/// An offer that was taken, in whole or in part, by an operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimOfferAtom {
+    /// The account that made the offer.
+    pub seller_id: AccountId,
+    /// The id of the offer.
+    pub offer_id: u64,
+    /// The asset the seller sold.
+    pub asset_sold: Asset,
+    /// The amount, in stroops, the seller sold.
+    pub amount_sold: i64,
+    /// The asset the seller bought.
+    pub asset_bought: Asset,
+    /// The amount, in stroops, the seller bought.
+    pub amount_bought: i64,
+}
+
+impl FromXdr for ClaimOfferAtom {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(ClaimOfferAtom {
+            seller_id: reader.read()?,
+            offer_id: reader.read()?,
+            asset_sold: reader.read()?,
+            amount_sold: reader.read()?,
+            asset_bought: reader.read()?,
+            amount_bought: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for ClaimOfferAtom {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.seller_id);
+        writer.write(&self.offer_id);
+        writer.write(&self.asset_sold);
+        writer.write(&self.amount_sold);
+        writer.write(&self.asset_bought);
+        writer.write(&self.amount_bought);
+    }
+}
+
+/// The offers taken by a successful path payment and the payment it made.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPaymentSuccess {
+    /// The offers that were taken along the path.
+    pub offers: Vec<ClaimOfferAtom>,
+    /// The payment made to the destination.
+    pub last: SimplePaymentResult,
+}
+
+impl FromXdr for PathPaymentSuccess {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(PathPaymentSuccess {
+            offers: reader.read_array(UNBOUNDED)?,
+            last: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for PathPaymentSuccess {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write_array(&self.offers);
+        writer.write(&self.last);
+    }
+}
+
+/// The result of a path payment operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathPaymentResult {
+    /// The payment was sent.
+    Success(PathPaymentSuccess),
+    /// The amounts or assets were invalid.
+    Malformed,
+    /// The source did not have enough funds.
+    Underfunded,
+    /// The source does not trust the asset.
+    SrcNoTrust,
+    /// The source is not authorized to send the asset.
+    SrcNotAuthorized,
+    /// The destination account does not exist.
+    NoDestination,
+    /// The destination does not trust the asset.
+    NoTrust,
+    /// The destination is not authorized to hold the asset.
+    NotAuthorized,
+    /// The destination would exceed the limit of its trustline.
+    LineFull,
+    /// The issuer of the asset does not exist.
+    NoIssuer(Asset),
+    /// There were not enough offers along the path.
+    TooFewOffers,
+    /// The path would take an offer made by the source.
+    OfferCrossSelf,
+    /// The payment would cost more than the send max.
+    OverSendmax,
+}
+
+impl FromXdr for PathPaymentResult {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(match reader.read()? {
+            0 => PathPaymentResult::Success(reader.read()?),
+            -1 => PathPaymentResult::Malformed,
+            -2 => PathPaymentResult::Underfunded,
+            -3 => PathPaymentResult::SrcNoTrust,
+            -4 => PathPaymentResult::SrcNotAuthorized,
+            -5 => PathPaymentResult::NoDestination,
+            -6 => PathPaymentResult::NoTrust,
+            -7 => PathPaymentResult::NotAuthorized,
+            -8 => PathPaymentResult::LineFull,
+            -9 => PathPaymentResult::NoIssuer(reader.read()?),
+            -10 => PathPaymentResult::TooFewOffers,
+            -11 => PathPaymentResult::OfferCrossSelf,
+            -12 => PathPaymentResult::OverSendmax,
+            discriminant => return Err(Error::InvalidDiscriminant(discriminant)),
+        })
+    }
+}
+
+impl ToXdr for PathPaymentResult {
+    fn to_xdr(&self, writer: &mut Writer) {
+        let code: i32 = match *self {
+            PathPaymentResult::Success(_) => 0,
+            PathPaymentResult::Malformed => -1,
+            PathPaymentResult::Underfunded => -2,
+            PathPaymentResult::SrcNoTrust => -3,
+            PathPaymentResult::SrcNotAuthorized => -4,
+            PathPaymentResult::NoDestination => -5,
+            PathPaymentResult::NoTrust => -6,
+            PathPaymentResult::NotAuthorized => -7,
+            PathPaymentResult::LineFull => -8,
+            PathPaymentResult::NoIssuer(_) => -9,
+            PathPaymentResult::TooFewOffers => -10,
+            PathPaymentResult::OfferCrossSelf => -11,
+            PathPaymentResult::OverSendmax => -12,
+        };
+        writer.write(&code);
+        match *self {
+            PathPaymentResult::Success(ref success) => writer.write(success),
+            PathPaymentResult::NoIssuer(ref asset) => writer.write(asset),
+            _ => {}
+        }
+    }
+}
+
+/// What happened to the offer of a successful manage offer operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManageOfferEffect {
+    /// A new offer was left on the books.
+    Created(OfferEntry),
+    /// The existing offer was updated.
+    Updated(OfferEntry),
+    /// The offer was deleted, or was completely filled.
+    Deleted,
+}
+
+impl FromXdr for ManageOfferEffect {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(ManageOfferEffect::Created(reader.read()?)),
+            1 => Ok(ManageOfferEffect::Updated(reader.read()?)),
+            2 => Ok(ManageOfferEffect::Deleted),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for ManageOfferEffect {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            ManageOfferEffect::Created(ref offer) => {
+                writer.write(&0i32);
+                writer.write(offer);
+            }
+            ManageOfferEffect::Updated(ref offer) => {
+                writer.write(&1i32);
+                writer.write(offer);
+            }
+            ManageOfferEffect::Deleted => writer.write(&2i32),
+        }
+    }
+}
+
+/// The offers taken by a successful manage offer operation and what became of
+/// the offer itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManageOfferSuccess {
+    /// The offers that were taken.
+    pub offers_claimed: Vec<ClaimOfferAtom>,
+    /// What happened to the offer.
+    pub offer: ManageOfferEffect,
+}
+
+impl FromXdr for ManageOfferSuccess {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(ManageOfferSuccess {
+            offers_claimed: reader.read_array(UNBOUNDED)?,
+            offer: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for ManageOfferSuccess {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write_array(&self.offers_claimed);
+        writer.write(&self.offer);
+    }
+}
+
+/// The result of a manage offer or create passive offer operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ManageOfferResult {
+    /// The offer was managed.
+    Success(ManageOfferSuccess),
+    /// The offer was invalid.
+    Malformed,
+    /// The source does not trust the selling asset.
+    SellNoTrust,
+    /// The source does not trust the buying asset.
+    BuyNoTrust,
+    /// The source is not authorized to sell the asset.
+    SellNotAuthorized,
+    /// The source is not authorized to buy the asset.
+    BuyNotAuthorized,
+    /// The source can't receive more of the buying asset.
+    LineFull,
+    /// The source does not have enough of the selling asset.
+    Underfunded,
+    /// The offer would take an offer made by the source.
+    CrossSelf,
+    /// The issuer of the selling asset does not exist.
+    SellNoIssuer,
+    /// The issuer of the buying asset does not exist.
+    BuyNoIssuer,
+    /// The offer to update was not found.
+    NotFound,
+    /// The offer would bring the source below the reserve.
+    LowReserve,
+}
+
+impl FromXdr for ManageOfferResult {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(match reader.read()? {
+            0 => ManageOfferResult::Success(reader.read()?),
+            -1 => ManageOfferResult::Malformed,
+            -2 => ManageOfferResult::SellNoTrust,
+            -3 => ManageOfferResult::BuyNoTrust,
+            -4 => ManageOfferResult::SellNotAuthorized,
+            -5 => ManageOfferResult::BuyNotAuthorized,
+            -6 => ManageOfferResult::LineFull,
+            -7 => ManageOfferResult::Underfunded,
+            -8 => ManageOfferResult::CrossSelf,
+            -9 => ManageOfferResult::SellNoIssuer,
+            -10 => ManageOfferResult::BuyNoIssuer,
+            -11 => ManageOfferResult::NotFound,
+            -12 => ManageOfferResult::LowReserve,
+            discriminant => return Err(Error::InvalidDiscriminant(discriminant)),
+        })
+    }
+}
+
+impl ToXdr for ManageOfferResult {
+    fn to_xdr(&self, writer: &mut Writer) {
+        let code: i32 = match *self {
+            ManageOfferResult::Success(_) => 0,
+            ManageOfferResult::Malformed => -1,
+            ManageOfferResult::SellNoTrust => -2,
+            ManageOfferResult::BuyNoTrust => -3,
+            ManageOfferResult::SellNotAuthorized => -4,
+            ManageOfferResult::BuyNotAuthorized => -5,
+            ManageOfferResult::LineFull => -6,
+            ManageOfferResult::Underfunded => -7,
+            ManageOfferResult::CrossSelf => -8,
+            ManageOfferResult::SellNoIssuer => -9,
+            ManageOfferResult::BuyNoIssuer => -10,
+            ManageOfferResult::NotFound => -11,
+            ManageOfferResult::LowReserve => -12,
+        };
+        writer.write(&code);
+        if let ManageOfferResult::Success(ref success) = *self {
+            writer.write(success);
+        }
+    }
+}
+
+xdr_enum! {
+    /// The result of a set options operation.
+    pub enum SetOptionsResult {
+        /// The options were set.
+        Success = 0,
+        /// Adding a signer would bring the account below the reserve.
+        LowReserve = -1,
+        /// The account already has the most signers allowed.
+        TooManySigners = -2,
+        /// The same flag was both set and cleared.
+        BadFlags = -3,
+        /// The inflation destination does not exist.
+        InvalidInflation = -4,
+        /// The flags can't be changed because the account is immutable.
+        CantChange = -5,
+        /// A flag that doesn't exist was set.
+        UnknownFlag = -6,
+        /// A weight or threshold was above 255.
+        ThresholdOutOfRange = -7,
+        /// The signer was the account's master key.
+        BadSigner = -8,
+        /// The home domain was malformed.
+        InvalidHomeDomain = -9,
+    }
+}
+
+xdr_enum! {
+    /// The result of a change trust operation.
+    pub enum ChangeTrustResult {
+        /// The trustline was changed.
+        Success = 0,
+        /// The asset or limit was invalid.
+        Malformed = -1,
+        /// The issuer of the asset does not exist.
+        NoIssuer = -2,
+        /// The limit is below the current balance.
+        InvalidLimit = -3,
+        /// A new trustline would bring the account below the reserve.
+        LowReserve = -4,
+        /// The account can't trust itself.
+        SelfNotAllowed = -5,
+    }
+}
+
+xdr_enum! {
+    /// The result of an allow trust operation.
+    pub enum AllowTrustResult {
+        /// The trustline was authorized, or revoked.
+        Success = 0,
+        /// The asset was invalid.
+        Malformed = -1,
+        /// The trustor does not have a trustline to the asset.
+        NoTrustLine = -2,
+        /// The source does not require authorization.
+        TrustNotRequired = -3,
+        /// The source can't revoke authorization.
+        CantRevoke = -4,
+        /// The source can't authorize itself.
+        SelfNotAllowed = -5,
+    }
+}
+
+/// The result of an account merge operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccountMergeResult {
+    /// The account was merged and its balance, in stroops, was transferred.
+    Success(i64),
+    /// The account can't be merged into itself.
+    Malformed,
+    /// The destination account does not exist.
+    NoAccount,
+    /// The source account has the immutable flag set.
+    ImmutableSet,
+    /// The source account still has trustlines or offers.
+    HasSubEntries,
+    /// The source account's sequence number is too far in the future.
+    SeqnumTooFar,
+    /// The destination can't receive the balance.
+    DestFull,
+}
+
+impl FromXdr for AccountMergeResult {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(match reader.read()? {
+            0 => AccountMergeResult::Success(reader.read()?),
+            -1 => AccountMergeResult::Malformed,
+            -2 => AccountMergeResult::NoAccount,
+            -3 => AccountMergeResult::ImmutableSet,
+            -4 => AccountMergeResult::HasSubEntries,
+            -5 => AccountMergeResult::SeqnumTooFar,
+            -6 => AccountMergeResult::DestFull,
+            discriminant => return Err(Error::InvalidDiscriminant(discriminant)),
+        })
+    }
+}
+
+impl ToXdr for AccountMergeResult {
+    fn to_xdr(&self, writer: &mut Writer) {
+        let code: i32 = match *self {
+            AccountMergeResult::Success(_) => 0,
+            AccountMergeResult::Malformed => -1,
+            AccountMergeResult::NoAccount => -2,
+            AccountMergeResult::ImmutableSet => -3,
+            AccountMergeResult::HasSubEntries => -4,
+            AccountMergeResult::SeqnumTooFar => -5,
+            AccountMergeResult::DestFull => -6,
+        };
+        writer.write(&code);
+        if let AccountMergeResult::Success(balance) = *self {
+            writer.write(&balance);
+        }
+    }
+}
+
+/// A payout made by inflation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InflationPayout {
+    /// The account that was paid.
+    pub destination: AccountId,
+    /// The amount, in stroops, paid.
+    pub amount: i64,
+}
+
+impl FromXdr for InflationPayout {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(InflationPayout {
+            destination: reader.read()?,
+            amount: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for InflationPayout {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.destination);
+        writer.write(&self.amount);
+    }
+}
+
+/// The result of an inflation operation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InflationResult {
+    /// Inflation ran and made the payouts.
+    Success(Vec<InflationPayout>),
+    /// It is not yet time for inflation to run.
+    NotTime,
+}
+
+impl FromXdr for InflationResult {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(InflationResult::Success(reader.read_array(UNBOUNDED)?)),
+            -1 => Ok(InflationResult::NotTime),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for InflationResult {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            InflationResult::Success(ref payouts) => {
+                writer.write(&0i32);
+                writer.write_array(payouts);
+            }
+            InflationResult::NotTime => writer.write(&-1i32),
+        }
+    }
+}
+
+xdr_enum! {
+    /// The result of a manage data operation.
+    pub enum ManageDataResult {
+        /// The entry was set, or deleted.
+        Success = 0,
+        /// The network does not support data entries yet.
+        NotSupportedYet = -1,
+        /// The entry to delete does not exist.
+        NameNotFound = -2,
+        /// A new entry would bring the account below the reserve.
+        LowReserve = -3,
+        /// The name was invalid.
+        InvalidName = -4,
+    }
+}
+
+xdr_enum! {
+    /// The result of a bump sequence operation.
+    pub enum BumpSequenceResult {
+        /// The sequence number was bumped.
+        Success = 0,
+        /// The sequence number to bump to was invalid.
+        BadSeq = -1,
+    }
+}
+
+/// The result of an operation. If the operation could not be attempted at all
+/// the result is one of the variants without a value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperationResult {
+    /// The result of a create account operation.
+    CreateAccount(CreateAccountResult),
+    /// The result of a payment operation.
+    Payment(PaymentResult),
+    /// The result of a path payment operation.
+    PathPayment(PathPaymentResult),
+    /// The result of a manage offer operation.
+    ManageOffer(ManageOfferResult),
+    /// The result of a create passive offer operation.
+    CreatePassiveOffer(ManageOfferResult),
+    /// The result of a set options operation.
+    SetOptions(SetOptionsResult),
+    /// The result of a change trust operation.
+    ChangeTrust(ChangeTrustResult),
+    /// The result of an allow trust operation.
+    AllowTrust(AllowTrustResult),
+    /// The result of an account merge operation.
+    AccountMerge(AccountMergeResult),
+    /// The result of an inflation operation.
+    Inflation(InflationResult),
+    /// The result of a manage data operation.
+    ManageData(ManageDataResult),
+    /// The result of a bump sequence operation.
+    BumpSequence(BumpSequenceResult),
+    /// There were too few valid signatures for the operation.
+    BadAuth,
+    /// The source account of the operation was not found.
+    NoAccount,
+    /// The operation is not supported by the network.
+    NotSupported,
+}
+
+impl FromXdr for OperationResult {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => {}
+            -1 => return Ok(OperationResult::BadAuth),
+            -2 => return Ok(OperationResult::NoAccount),
+            -3 => return Ok(OperationResult::NotSupported),
+            discriminant => return Err(Error::InvalidDiscriminant(discriminant)),
+        }
+        Ok(match reader.read()? {
+            0 => OperationResult::CreateAccount(reader.read()?),
+            1 => OperationResult::Payment(reader.read()?),
+            2 => OperationResult::PathPayment(reader.read()?),
+            3 => OperationResult::ManageOffer(reader.read()?),
+            4 => OperationResult::CreatePassiveOffer(reader.read()?),
+            5 => OperationResult::SetOptions(reader.read()?),
+            6 => OperationResult::ChangeTrust(reader.read()?),
+            7 => OperationResult::AllowTrust(reader.read()?),
+            8 => OperationResult::AccountMerge(reader.read()?),
+            9 => OperationResult::Inflation(reader.read()?),
+            10 => OperationResult::ManageData(reader.read()?),
+            11 => OperationResult::BumpSequence(reader.read()?),
+            discriminant => return Err(Error::InvalidDiscriminant(discriminant)),
+        })
+    }
+}
+
+impl ToXdr for OperationResult {
+    fn to_xdr(&self, writer: &mut Writer) {
+        let (kind, result): (i32, &ToXdr) = match *self {
+            OperationResult::CreateAccount(ref result) => (0, result),
+            OperationResult::Payment(ref result) => (1, result),
+            OperationResult::PathPayment(ref result) => (2, result),
+            OperationResult::ManageOffer(ref result) => (3, result),
+            OperationResult::CreatePassiveOffer(ref result) => (4, result),
+            OperationResult::SetOptions(ref result) => (5, result),
+            OperationResult::ChangeTrust(ref result) => (6, result),
+            OperationResult::AllowTrust(ref result) => (7, result),
+            OperationResult::AccountMerge(ref result) => (8, result),
+            OperationResult::Inflation(ref result) => (9, result),
+            OperationResult::ManageData(ref result) => (10, result),
+            OperationResult::BumpSequence(ref result) => (11, result),
+            OperationResult::BadAuth => return writer.write(&-1i32),
+            OperationResult::NoAccount => return writer.write(&-2i32),
+            OperationResult::NotSupported => return writer.write(&-3i32),
+        };
+        writer.write(&0i32);
+        writer.write(&kind);
+        writer.write(result);
+    }
+}
+
+/// The result of applying a transaction to the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionResult {
+    /// The fee, in stroops, charged to the source account.
+    pub fee_charged: i64,
+    /// The overall result of the transaction.
+    pub code: TransactionResultCode,
+    /// The result of each operation. Only present when the code is `Success`
+    /// or `Failed`, since otherwise the operations were never attempted.
+    pub results: Vec<OperationResult>,
+}
+
+impl FromXdr for TransactionResult {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        let fee_charged = reader.read()?;
+        let code = reader.read()?;
+        let results = match code {
+            TransactionResultCode::Success | TransactionResultCode::Failed => {
+                reader.read_array(UNBOUNDED)?
+            }
+            _ => Vec::new(),
+        };
+        reader.read_reserved()?;
+        Ok(TransactionResult {
+            fee_charged,
+            code,
+            results,
+        })
+    }
+}
+
+impl ToXdr for TransactionResult {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.fee_charged);
+        writer.write(&self.code);
+        match self.code {
+            TransactionResultCode::Success | TransactionResultCode::Failed => {
+                writer.write_array(&self.results)
+            }
+            _ => {}
+        }
+        writer.write_reserved();
+    }
+}
+
+#[cfg(test)]
+mod result_xdr_tests {
+    use super::super::{from_base64, to_base64, PublicKey};
+    use super::*;
+
+    fn fixture(name: &str) -> &'static str {
+        match name {
+            "path_payment" => include_str!("../../fixtures/xdr/result_path_payment.xdr"),
+            "manage_offer" => include_str!("../../fixtures/xdr/result_manage_offer.xdr"),
+            "all_operations" => include_str!("../../fixtures/xdr/result_all_operations.xdr"),
+            _ => unreachable!(),
+        }
+        .trim()
+    }
+
+    #[test]
+    fn it_decodes_a_successful_result_from_horizon() {
+        let xdr = "AAAAAAAAAGQAAAAAAAAAAQAAAAAAAAABAAAAAAAAAAA=";
+        let result: TransactionResult = from_base64(xdr).unwrap();
+        assert_eq!(result.fee_charged, 100);
+        assert_eq!(result.code, TransactionResultCode::Success);
+        assert_eq!(
+            result.results,
+            vec![OperationResult::Payment(PaymentResult::Success)]
+        );
+        assert_eq!(to_base64(&result), xdr);
+    }
+
+    #[test]
+    fn it_decodes_a_failed_result_from_horizon() {
+        let xdr = "AAAAAAAAAGT/////AAAAAQAAAAAAAAAB/////gAAAAA=";
+        let result: TransactionResult = from_base64(xdr).unwrap();
+        assert_eq!(result.code, TransactionResultCode::Failed);
+        assert_eq!(
+            result.results,
+            vec![OperationResult::Payment(PaymentResult::Underfunded)]
+        );
+        assert_eq!(to_base64(&result), xdr);
+    }
+
+    #[test]
+    fn it_has_no_operation_results_when_the_transaction_was_not_attempted() {
+        let result = TransactionResult {
+            fee_charged: 100,
+            code: TransactionResultCode::BadSeq,
+            results: Vec::new(),
+        };
+        let xdr = to_base64(&result);
+        assert_eq!(xdr, "AAAAAAAAAGT////7AAAAAA==");
+        assert_eq!(from_base64::<TransactionResult>(&xdr).unwrap(), result);
+    }
+
+    #[test]
+    fn it_decodes_a_path_payment_result() {
+        let result: TransactionResult = from_base64(fixture("path_payment")).unwrap();
+        match result.results[0] {
+            OperationResult::PathPayment(PathPaymentResult::Success(ref success)) => {
+                assert_eq!(success.offers.len(), 2);
+                assert_eq!(success.offers[1].offer_id, 43);
+                assert_eq!(success.last.destination, PublicKey::Ed25519([2; 32]));
+                assert_eq!(success.last.amount, 10_000_000);
+            }
+            ref result => panic!("Expected a path payment: {:?}", result),
+        }
+        assert_eq!(to_base64(&result), fixture("path_payment"));
+    }
+
+    #[test]
+    fn it_decodes_a_manage_offer_result() {
+        let result: TransactionResult = from_base64(fixture("manage_offer")).unwrap();
+        match result.results[0] {
+            OperationResult::ManageOffer(ManageOfferResult::Success(ref success)) => {
+                assert_eq!(success.offers_claimed.len(), 1);
+                match success.offer {
+                    ManageOfferEffect::Created(ref offer) => assert_eq!(offer.offer_id, 99),
+                    ref effect => panic!("Expected a created offer: {:?}", effect),
+                }
+            }
+            ref result => panic!("Expected a manage offer: {:?}", result),
+        }
+        assert_eq!(to_base64(&result), fixture("manage_offer"));
+    }
+
+    #[test]
+    fn it_round_trips_every_kind_of_operation_result() {
+        let result: TransactionResult = from_base64(fixture("all_operations")).unwrap();
+        assert_eq!(result.code, TransactionResultCode::Failed);
+        assert_eq!(result.results.len(), 15);
+        assert_eq!(
+            result.results[8],
+            OperationResult::AccountMerge(AccountMergeResult::Success(1_000_000_000))
+        );
+        assert_eq!(result.results[12], OperationResult::BadAuth);
+        assert_eq!(to_base64(&result), fixture("all_operations"));
+    }
+}
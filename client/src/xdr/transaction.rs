@@ -0,0 +1,856 @@
+use super::{AccountId, Asset, Error, FromXdr, Hash, Price, Reader, Result, Signer, ToXdr, Writer};
+use std::borrow::Cow;
+
+/// Meta data attached to a transaction.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Memo {
+    /// The transaction has no memo.
+    None,
+    /// Up to 28 bytes, usually but not necessarily utf-8 text.
+    Text(Vec<u8>),
+    /// A 64 bit unsigned integer.
+    Id(u64),
+    /// A 32 byte hash.
+    Hash(Hash),
+    /// A 32 byte hash of the transaction the sender is refunding.
+    Return(Hash),
+}
+
+impl FromXdr for Memo {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(Memo::None),
+            1 => Ok(Memo::Text(reader.read_opaque(28)?)),
+            2 => Ok(Memo::Id(reader.read()?)),
+            3 => Ok(Memo::Hash(reader.read()?)),
+            4 => Ok(Memo::Return(reader.read()?)),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for Memo {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            Memo::None => writer.write(&0i32),
+            Memo::Text(ref text) => {
+                writer.write(&1i32);
+                writer.write_opaque(text);
+            }
+            Memo::Id(id) => {
+                writer.write(&2i32);
+                writer.write(&id);
+            }
+            Memo::Hash(ref hash) => {
+                writer.write(&3i32);
+                writer.write(hash);
+            }
+            Memo::Return(ref hash) => {
+                writer.write(&4i32);
+                writer.write(hash);
+            }
+        }
+    }
+}
+
+/// The window of time, in unix seconds, in which a transaction is valid. A
+/// max time of zero means the transaction does not expire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TimeBounds {
+    /// The earliest time the transaction can be applied.
+    pub min_time: u64,
+    /// The latest time the transaction can be applied.
+    pub max_time: u64,
+}
+
+impl FromXdr for TimeBounds {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(TimeBounds {
+            min_time: reader.read()?,
+            max_time: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for TimeBounds {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.min_time);
+        writer.write(&self.max_time);
+    }
+}
+
+/// A signature on a transaction along with a hint of which key signed it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DecoratedSignature {
+    /// The last four bytes of the public key that signed.
+    pub hint: [u8; 4],
+    /// The signature itself, at most 64 bytes.
+    pub signature: Vec<u8>,
+}
+
+impl FromXdr for DecoratedSignature {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(DecoratedSignature {
+            hint: reader.read()?,
+            signature: reader.read_opaque(64)?,
+        })
+    }
+}
+
+impl ToXdr for DecoratedSignature {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.hint);
+        writer.write_opaque(&self.signature);
+    }
+}
+
+/// Creates and funds a new account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreateAccountOp {
+    /// The account to create.
+    pub destination: AccountId,
+    /// The amount of lumens, in stroops, to fund the account with.
+    pub starting_balance: i64,
+}
+
+impl FromXdr for CreateAccountOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(CreateAccountOp {
+            destination: reader.read()?,
+            starting_balance: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for CreateAccountOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.destination);
+        writer.write(&self.starting_balance);
+    }
+}
+
+/// Sends an amount of an asset to an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PaymentOp {
+    /// The account receiving the payment.
+    pub destination: AccountId,
+    /// The asset being sent.
+    pub asset: Asset,
+    /// The amount, in stroops, being sent.
+    pub amount: i64,
+}
+
+impl FromXdr for PaymentOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(PaymentOp {
+            destination: reader.read()?,
+            asset: reader.read()?,
+            amount: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for PaymentOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.destination);
+        writer.write(&self.asset);
+        writer.write(&self.amount);
+    }
+}
+
+/// Sends an amount of an asset to an account, converting it from another asset
+/// through the order books along the way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathPaymentOp {
+    /// The asset being sent.
+    pub send_asset: Asset,
+    /// The most, in stroops, of the send asset that may be spent.
+    pub send_max: i64,
+    /// The account receiving the payment.
+    pub destination: AccountId,
+    /// The asset the destination receives.
+    pub dest_asset: Asset,
+    /// The amount, in stroops, the destination receives.
+    pub dest_amount: i64,
+    /// The intermediate assets to convert through, at most five.
+    pub path: Vec<Asset>,
+}
+
+impl FromXdr for PathPaymentOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(PathPaymentOp {
+            send_asset: reader.read()?,
+            send_max: reader.read()?,
+            destination: reader.read()?,
+            dest_asset: reader.read()?,
+            dest_amount: reader.read()?,
+            path: reader.read_array(5)?,
+        })
+    }
+}
+
+impl ToXdr for PathPaymentOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.send_asset);
+        writer.write(&self.send_max);
+        writer.write(&self.destination);
+        writer.write(&self.dest_asset);
+        writer.write(&self.dest_amount);
+        writer.write_array(&self.path);
+    }
+}
+
+/// Creates, updates or deletes an offer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManageOfferOp {
+    /// The asset being sold.
+    pub selling: Asset,
+    /// The asset being bought.
+    pub buying: Asset,
+    /// The amount, in stroops, being sold. Zero deletes the offer.
+    pub amount: i64,
+    /// The price of one unit of selling in terms of buying.
+    pub price: Price,
+    /// The offer to update, or zero to create a new one.
+    pub offer_id: u64,
+}
+
+impl FromXdr for ManageOfferOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(ManageOfferOp {
+            selling: reader.read()?,
+            buying: reader.read()?,
+            amount: reader.read()?,
+            price: reader.read()?,
+            offer_id: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for ManageOfferOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.selling);
+        writer.write(&self.buying);
+        writer.write(&self.amount);
+        writer.write(&self.price);
+        writer.write(&self.offer_id);
+    }
+}
+
+/// Creates an offer that does not take offers of the same price.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreatePassiveOfferOp {
+    /// The asset being sold.
+    pub selling: Asset,
+    /// The asset being bought.
+    pub buying: Asset,
+    /// The amount, in stroops, being sold.
+    pub amount: i64,
+    /// The price of one unit of selling in terms of buying.
+    pub price: Price,
+}
+
+impl FromXdr for CreatePassiveOfferOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(CreatePassiveOfferOp {
+            selling: reader.read()?,
+            buying: reader.read()?,
+            amount: reader.read()?,
+            price: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for CreatePassiveOfferOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.selling);
+        writer.write(&self.buying);
+        writer.write(&self.amount);
+        writer.write(&self.price);
+    }
+}
+
+/// Sets the options of an account. Only the options that are present change.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SetOptionsOp {
+    /// The account to send inflation votes to.
+    pub inflation_dest: Option<AccountId>,
+    /// The flags to clear.
+    pub clear_flags: Option<u32>,
+    /// The flags to set.
+    pub set_flags: Option<u32>,
+    /// The weight of the master key.
+    pub master_weight: Option<u32>,
+    /// The low threshold.
+    pub low_threshold: Option<u32>,
+    /// The medium threshold.
+    pub med_threshold: Option<u32>,
+    /// The high threshold.
+    pub high_threshold: Option<u32>,
+    /// The home domain, at most 32 bytes, which needn't be utf-8.
+    pub home_domain: Option<Vec<u8>>,
+    /// A signer to add, update, or remove when the weight is zero.
+    pub signer: Option<Signer>,
+}
+
+impl SetOptionsOp {
+    /// The home domain as text, with any bytes that aren't utf-8 replaced by
+    /// U+FFFD.
+    pub fn home_domain_text(&self) -> Option<Cow<str>> {
+        self.home_domain
+            .as_ref()
+            .map(|domain| String::from_utf8_lossy(domain))
+    }
+}
+
+impl FromXdr for SetOptionsOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(SetOptionsOp {
+            inflation_dest: reader.read()?,
+            clear_flags: reader.read()?,
+            set_flags: reader.read()?,
+            master_weight: reader.read()?,
+            low_threshold: reader.read()?,
+            med_threshold: reader.read()?,
+            high_threshold: reader.read()?,
+            home_domain: if reader.read()? {
+                Some(reader.read_opaque(32)?)
+            } else {
+                None
+            },
+            signer: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for SetOptionsOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.inflation_dest);
+        writer.write(&self.clear_flags);
+        writer.write(&self.set_flags);
+        writer.write(&self.master_weight);
+        writer.write(&self.low_threshold);
+        writer.write(&self.med_threshold);
+        writer.write(&self.high_threshold);
+        match self.home_domain {
+            Some(ref home_domain) => {
+                writer.write(&true);
+                writer.write_opaque(home_domain);
+            }
+            None => writer.write(&false),
+        }
+        writer.write(&self.signer);
+    }
+}
+
+/// Creates, updates or deletes a trustline.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangeTrustOp {
+    /// The asset to trust.
+    pub line: Asset,
+    /// The most, in stroops, the account will hold. Zero deletes the trustline.
+    pub limit: i64,
+}
+
+impl FromXdr for ChangeTrustOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(ChangeTrustOp {
+            line: reader.read()?,
+            limit: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for ChangeTrustOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.line);
+        writer.write(&self.limit);
+    }
+}
+
+/// The code of an asset issued by the source account of an allow trust
+/// operation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AllowTrustAsset {
+    /// A code of one to four characters.
+    AlphaNum4([u8; 4]),
+    /// A code of five to twelve characters.
+    AlphaNum12([u8; 12]),
+}
+
+impl FromXdr for AllowTrustAsset {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            1 => Ok(AllowTrustAsset::AlphaNum4(reader.read()?)),
+            2 => Ok(AllowTrustAsset::AlphaNum12(reader.read()?)),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for AllowTrustAsset {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            AllowTrustAsset::AlphaNum4(ref code) => {
+                writer.write(&1i32);
+                writer.write(code);
+            }
+            AllowTrustAsset::AlphaNum12(ref code) => {
+                writer.write(&2i32);
+                writer.write(code);
+            }
+        }
+    }
+}
+
+/// Authorizes, or revokes, another account's trustline to an asset.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AllowTrustOp {
+    /// The account holding the trustline.
+    pub trustor: AccountId,
+    /// The asset of the trustline.
+    pub asset: AllowTrustAsset,
+    /// Whether the trustline is authorized.
+    pub authorize: bool,
+}
+
+impl FromXdr for AllowTrustOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(AllowTrustOp {
+            trustor: reader.read()?,
+            asset: reader.read()?,
+            authorize: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for AllowTrustOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.trustor);
+        writer.write(&self.asset);
+        writer.write(&self.authorize);
+    }
+}
+
+/// Sets, or deletes, a key value pair on an account.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManageDataOp {
+    /// The key, at most 64 bytes, which needn't be utf-8.
+    pub data_name: Vec<u8>,
+    /// The value, at most 64 bytes. No value deletes the entry.
+    pub data_value: Option<Vec<u8>>,
+}
+
+impl ManageDataOp {
+    /// The key as text, with any bytes that aren't utf-8 replaced by U+FFFD.
+    pub fn data_name_text(&self) -> Cow<str> {
+        String::from_utf8_lossy(&self.data_name)
+    }
+}
+
+impl FromXdr for ManageDataOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(ManageDataOp {
+            data_name: reader.read_opaque(64)?,
+            data_value: if reader.read()? {
+                Some(reader.read_opaque(64)?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl ToXdr for ManageDataOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write_opaque(&self.data_name);
+        match self.data_value {
+            Some(ref value) => {
+                writer.write(&true);
+                writer.write_opaque(value);
+            }
+            None => writer.write(&false),
+        }
+    }
+}
+
+/// Bumps the sequence number of the source account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BumpSequenceOp {
+    /// The sequence number to bump to.
+    pub bump_to: i64,
+}
+
+impl FromXdr for BumpSequenceOp {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(BumpSequenceOp {
+            bump_to: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for BumpSequenceOp {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.bump_to);
+    }
+}
+
+/// The body of an operation, which determines what the operation does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperationBody {
+    /// Creates and funds a new account.
+    CreateAccount(CreateAccountOp),
+    /// Sends an amount of an asset to an account.
+    Payment(PaymentOp),
+    /// Sends an asset, converting it to another along the way.
+    PathPayment(PathPaymentOp),
+    /// Creates, updates or deletes an offer.
+    ManageOffer(ManageOfferOp),
+    /// Creates a passive offer.
+    CreatePassiveOffer(CreatePassiveOfferOp),
+    /// Sets the options of an account.
+    SetOptions(SetOptionsOp),
+    /// Creates, updates or deletes a trustline.
+    ChangeTrust(ChangeTrustOp),
+    /// Authorizes another account's trustline.
+    AllowTrust(AllowTrustOp),
+    /// Merges the source account into the destination account.
+    AccountMerge(AccountId),
+    /// Runs inflation.
+    Inflation,
+    /// Sets, or deletes, a key value pair on an account.
+    ManageData(ManageDataOp),
+    /// Bumps the sequence number of the source account.
+    BumpSequence(BumpSequenceOp),
+}
+
+impl FromXdr for OperationBody {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(match reader.read()? {
+            0 => OperationBody::CreateAccount(reader.read()?),
+            1 => OperationBody::Payment(reader.read()?),
+            2 => OperationBody::PathPayment(reader.read()?),
+            3 => OperationBody::ManageOffer(reader.read()?),
+            4 => OperationBody::CreatePassiveOffer(reader.read()?),
+            5 => OperationBody::SetOptions(reader.read()?),
+            6 => OperationBody::ChangeTrust(reader.read()?),
+            7 => OperationBody::AllowTrust(reader.read()?),
+            8 => OperationBody::AccountMerge(reader.read()?),
+            9 => OperationBody::Inflation,
+            10 => OperationBody::ManageData(reader.read()?),
+            11 => OperationBody::BumpSequence(reader.read()?),
+            discriminant => return Err(Error::InvalidDiscriminant(discriminant)),
+        })
+    }
+}
+
+impl ToXdr for OperationBody {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            OperationBody::CreateAccount(ref op) => {
+                writer.write(&0i32);
+                writer.write(op);
+            }
+            OperationBody::Payment(ref op) => {
+                writer.write(&1i32);
+                writer.write(op);
+            }
+            OperationBody::PathPayment(ref op) => {
+                writer.write(&2i32);
+                writer.write(op);
+            }
+            OperationBody::ManageOffer(ref op) => {
+                writer.write(&3i32);
+                writer.write(op);
+            }
+            OperationBody::CreatePassiveOffer(ref op) => {
+                writer.write(&4i32);
+                writer.write(op);
+            }
+            OperationBody::SetOptions(ref op) => {
+                writer.write(&5i32);
+                writer.write(op);
+            }
+            OperationBody::ChangeTrust(ref op) => {
+                writer.write(&6i32);
+                writer.write(op);
+            }
+            OperationBody::AllowTrust(ref op) => {
+                writer.write(&7i32);
+                writer.write(op);
+            }
+            OperationBody::AccountMerge(ref destination) => {
+                writer.write(&8i32);
+                writer.write(destination);
+            }
+            OperationBody::Inflation => writer.write(&9i32),
+            OperationBody::ManageData(ref op) => {
+                writer.write(&10i32);
+                writer.write(op);
+            }
+            OperationBody::BumpSequence(ref op) => {
+                writer.write(&11i32);
+                writer.write(op);
+            }
+        }
+    }
+}
+
+/// A single command that mutates the ledger.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Operation {
+    /// The account the operation applies to, if not the transaction's source.
+    pub source_account: Option<AccountId>,
+    /// What the operation does.
+    pub body: OperationBody,
+}
+
+impl FromXdr for Operation {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(Operation {
+            source_account: reader.read()?,
+            body: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for Operation {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.source_account);
+        writer.write(&self.body);
+    }
+}
+
+/// A group of operations that are applied to the ledger together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    /// The account that pays the fee and uses up a sequence number.
+    pub source_account: AccountId,
+    /// The fee, in stroops, that the source account is willing to pay.
+    pub fee: u32,
+    /// The sequence number of the transaction.
+    pub seq_num: i64,
+    /// The time window the transaction is valid in.
+    pub time_bounds: Option<TimeBounds>,
+    /// The memo attached to the transaction.
+    pub memo: Memo,
+    /// The operations of the transaction, at most one hundred.
+    pub operations: Vec<Operation>,
+}
+
+impl FromXdr for Transaction {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        let transaction = Transaction {
+            source_account: reader.read()?,
+            fee: reader.read()?,
+            seq_num: reader.read()?,
+            time_bounds: reader.read()?,
+            memo: reader.read()?,
+            operations: reader.read_array(100)?,
+        };
+        reader.read_reserved()?;
+        Ok(transaction)
+    }
+}
+
+impl ToXdr for Transaction {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.source_account);
+        writer.write(&self.fee);
+        writer.write(&self.seq_num);
+        writer.write(&self.time_bounds);
+        writer.write(&self.memo);
+        writer.write_array(&self.operations);
+        writer.write_reserved();
+    }
+}
+
+/// A transaction and the signatures that authorize it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionEnvelope {
+    /// The transaction.
+    pub tx: Transaction,
+    /// The signatures on the transaction, at most twenty.
+    pub signatures: Vec<DecoratedSignature>,
+}
+
+impl FromXdr for TransactionEnvelope {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(TransactionEnvelope {
+            tx: reader.read()?,
+            signatures: reader.read_array(20)?,
+        })
+    }
+}
+
+impl ToXdr for TransactionEnvelope {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.tx);
+        writer.write_array(&self.signatures);
+    }
+}
+
+#[cfg(test)]
+mod transaction_xdr_tests {
+    use super::super::{from_base64, to_base64, PublicKey};
+    use super::*;
+
+    fn envelope_xdr() -> &'static str {
+        include_str!("../../fixtures/xdr/envelope_all_operations.xdr").trim()
+    }
+
+    fn memo_bytes_envelope_xdr() -> &'static str {
+        include_str!("../../fixtures/xdr/envelope_memo_bytes.xdr").trim()
+    }
+
+    fn horizon_envelope_xdr() -> &'static str {
+        "AAAAAH2Hmt1JWMfqAdUlDeyUtO9V8zPqJ0aLG8KrZyXv78QGAAAAZAAIgb4AAtRiAAAAAAAAAAEAAAAAAAAA\
+         AQAAAAAAAAABAAAAAJZgy/0KAk+3JQwG8hPGBNTZVGew2Joi1TwkVBdwPn9QAAAAAAAAAAA7mUNgAAAAAAAAA\
+         AHv78QGAAAAQITCXzWfgHgAjF3djx1VK9JK08UypfpftzFoyNXv7A0Agau/ur/3/+ZZtQb8xSsao8yVAsTiV4\
+         ttiT/HqfvvlAk="
+    }
+
+    #[test]
+    fn it_decodes_an_envelope_from_horizon() {
+        let envelope: TransactionEnvelope = from_base64(horizon_envelope_xdr()).unwrap();
+        assert_eq!(envelope.tx.fee, 100);
+        assert_eq!(envelope.tx.seq_num, 2394452857640034);
+        assert_eq!(envelope.tx.time_bounds, None);
+        assert_eq!(envelope.tx.memo, Memo::Text(Vec::new()));
+        assert_eq!(envelope.tx.operations.len(), 1);
+        match envelope.tx.operations[0].body {
+            OperationBody::Payment(ref payment) => {
+                assert_eq!(payment.asset, Asset::Native);
+                assert_eq!(payment.amount, 999_900_000);
+            }
+            ref body => panic!("Expected a payment: {:?}", body),
+        }
+        assert_eq!(envelope.signatures.len(), 1);
+        assert_eq!(envelope.signatures[0].hint, [0xef, 0xef, 0xc4, 0x06]);
+        assert_eq!(envelope.signatures[0].signature.len(), 64);
+        assert_eq!(to_base64(&envelope), horizon_envelope_xdr());
+    }
+
+    #[test]
+    fn it_decodes_every_kind_of_operation() {
+        let envelope: TransactionEnvelope = from_base64(envelope_xdr()).unwrap();
+        let tx = &envelope.tx;
+        assert_eq!(tx.source_account, PublicKey::Ed25519([1; 32]));
+        assert_eq!(tx.fee, 1200);
+        assert_eq!(tx.seq_num, 123456789012);
+        assert_eq!(
+            tx.time_bounds,
+            Some(TimeBounds {
+                min_time: 1500000000,
+                max_time: 1600000000,
+            })
+        );
+        assert_eq!(tx.memo, Memo::Text(b"all the operations".to_vec()));
+        assert_eq!(tx.operations.len(), 12);
+        assert_eq!(
+            tx.operations[0].source_account,
+            Some(PublicKey::Ed25519([9; 32]))
+        );
+
+        match tx.operations[2].body {
+            OperationBody::PathPayment(ref op) => {
+                assert_eq!(op.send_max, 50_000_000);
+                assert_eq!(op.path.len(), 2);
+                assert_eq!(
+                    op.dest_asset,
+                    Asset::AlphaNum4 {
+                        code: *b"USD\0",
+                        issuer: PublicKey::Ed25519([3; 32]),
+                    }
+                );
+            }
+            ref body => panic!("Expected a path payment: {:?}", body),
+        }
+        match tx.operations[5].body {
+            OperationBody::SetOptions(ref op) => {
+                assert_eq!(op.inflation_dest, None);
+                assert_eq!(op.set_flags, Some(3));
+                assert_eq!(op.home_domain, Some(b"stellar.org".to_vec()));
+                assert_eq!(op.home_domain_text().unwrap(), "stellar.org");
+                assert_eq!(op.signer.unwrap().weight, 1);
+            }
+            ref body => panic!("Expected set options: {:?}", body),
+        }
+        match tx.operations[10].body {
+            OperationBody::ManageData(ref op) => {
+                assert_eq!(op.data_name, b"name".to_vec());
+                assert_eq!(op.data_name_text(), "name");
+                assert_eq!(op.data_value, Some(vec![1, 2, 3, 4, 5]));
+            }
+            ref body => panic!("Expected manage data: {:?}", body),
+        }
+        assert_eq!(tx.operations[9].body, OperationBody::Inflation);
+        assert_eq!(
+            tx.operations[11].body,
+            OperationBody::BumpSequence(BumpSequenceOp {
+                bump_to: 123456789100,
+            })
+        );
+        assert_eq!(envelope.signatures.len(), 2);
+    }
+
+    #[test]
+    fn it_round_trips_every_kind_of_operation() {
+        let envelope: TransactionEnvelope = from_base64(envelope_xdr()).unwrap();
+        assert_eq!(to_base64(&envelope), envelope_xdr());
+    }
+
+    #[test]
+    fn it_round_trips_each_kind_of_memo() {
+        let memos = vec![
+            Memo::None,
+            Memo::Text(b"hello".to_vec()),
+            Memo::Id(::std::u64::MAX),
+            Memo::Hash([7; 32]),
+            Memo::Return([8; 32]),
+        ];
+        for memo in memos {
+            let encoded = to_base64(&memo);
+            assert_eq!(from_base64::<Memo>(&encoded).unwrap(), memo);
+        }
+    }
+
+    #[test]
+    fn it_keeps_the_exact_bytes_of_text_memos() {
+        let envelope: TransactionEnvelope = from_base64(memo_bytes_envelope_xdr()).unwrap();
+        assert_eq!(envelope.tx.memo, Memo::Text(b"\xffinvoice\xfe".to_vec()));
+        assert_eq!(to_base64(&envelope), memo_bytes_envelope_xdr());
+    }
+
+    #[test]
+    fn it_keeps_the_exact_bytes_of_home_domains_and_data_names() {
+        let options = SetOptionsOp {
+            inflation_dest: None,
+            clear_flags: None,
+            set_flags: None,
+            master_weight: None,
+            low_threshold: None,
+            med_threshold: None,
+            high_threshold: None,
+            home_domain: Some(b"\xffstellar.org".to_vec()),
+            signer: None,
+        };
+        let decoded: SetOptionsOp = from_base64(&to_base64(&options)).unwrap();
+        assert_eq!(decoded, options);
+        assert_eq!(decoded.home_domain_text().unwrap(), "\u{fffd}stellar.org");
+
+        let data = ManageDataOp {
+            data_name: b"name\xfe".to_vec(),
+            data_value: None,
+        };
+        let decoded: ManageDataOp = from_base64(&to_base64(&data)).unwrap();
+        assert_eq!(decoded, data);
+        assert_eq!(decoded.data_name_text(), "name\u{fffd}");
+    }
+
+    #[test]
+    fn it_rejects_text_memos_over_28_bytes() {
+        let memo = Memo::Text(b"this memo is far too long to fit".to_vec());
+        assert!(from_base64::<Memo>(&to_base64(&memo)).is_err());
+    }
+}
@@ -0,0 +1,210 @@
+use super::{Error, FromXdr, Reader, Result, ToXdr, Writer};
+
+/// A SHA-256 hash.
+pub type Hash = [u8; 32];
+
+/// The weight of an account's master key followed by its low, medium and high
+/// thresholds.
+pub type Thresholds = [u8; 4];
+
+/// The public key of a keypair on the stellar network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PublicKey {
+    /// An ed25519 public key.
+    Ed25519([u8; 32]),
+}
+
+/// Accounts are identified by the public key of their master keypair.
+pub type AccountId = PublicKey;
+
+impl FromXdr for PublicKey {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(PublicKey::Ed25519(reader.read()?)),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for PublicKey {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            PublicKey::Ed25519(ref key) => {
+                writer.write(&0i32);
+                writer.write(key);
+            }
+        }
+    }
+}
+
+/// The key of an additional signer on an account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignerKey {
+    /// An ed25519 public key.
+    Ed25519([u8; 32]),
+    /// The hash of a transaction that is authorized ahead of time.
+    PreAuthTx(Hash),
+    /// The SHA-256 hash of a value whose preimage signs the transaction.
+    HashX(Hash),
+}
+
+impl FromXdr for SignerKey {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(SignerKey::Ed25519(reader.read()?)),
+            1 => Ok(SignerKey::PreAuthTx(reader.read()?)),
+            2 => Ok(SignerKey::HashX(reader.read()?)),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for SignerKey {
+    fn to_xdr(&self, writer: &mut Writer) {
+        let (discriminant, key) = match *self {
+            SignerKey::Ed25519(ref key) => (0i32, key),
+            SignerKey::PreAuthTx(ref hash) => (1, hash),
+            SignerKey::HashX(ref hash) => (2, hash),
+        };
+        writer.write(&discriminant);
+        writer.write(key);
+    }
+}
+
+/// A signer of an account and the weight its signature carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Signer {
+    /// The key of the signer.
+    pub key: SignerKey,
+    /// The weight of the signer's signature.
+    pub weight: u32,
+}
+
+impl FromXdr for Signer {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(Signer {
+            key: reader.read()?,
+            weight: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for Signer {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.key);
+        writer.write(&self.weight);
+    }
+}
+
+/// An asset on the stellar network. Asset codes are padded with zeros to
+/// fill the length of the code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Asset {
+    /// Lumens, the native asset of the network.
+    Native,
+    /// An asset with a code of one to four characters.
+    AlphaNum4 {
+        /// The asset code.
+        code: [u8; 4],
+        /// The account that issued the asset.
+        issuer: AccountId,
+    },
+    /// An asset with a code of five to twelve characters.
+    AlphaNum12 {
+        /// The asset code.
+        code: [u8; 12],
+        /// The account that issued the asset.
+        issuer: AccountId,
+    },
+}
+
+impl FromXdr for Asset {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        match reader.read()? {
+            0 => Ok(Asset::Native),
+            1 => Ok(Asset::AlphaNum4 {
+                code: reader.read()?,
+                issuer: reader.read()?,
+            }),
+            2 => Ok(Asset::AlphaNum12 {
+                code: reader.read()?,
+                issuer: reader.read()?,
+            }),
+            discriminant => Err(Error::InvalidDiscriminant(discriminant)),
+        }
+    }
+}
+
+impl ToXdr for Asset {
+    fn to_xdr(&self, writer: &mut Writer) {
+        match *self {
+            Asset::Native => writer.write(&0i32),
+            Asset::AlphaNum4 {
+                ref code,
+                ref issuer,
+            } => {
+                writer.write(&1i32);
+                writer.write(code);
+                writer.write(issuer);
+            }
+            Asset::AlphaNum12 {
+                ref code,
+                ref issuer,
+            } => {
+                writer.write(&2i32);
+                writer.write(code);
+                writer.write(issuer);
+            }
+        }
+    }
+}
+
+/// A price expressed as a fraction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Price {
+    /// The numerator.
+    pub n: i32,
+    /// The denominator.
+    pub d: i32,
+}
+
+impl FromXdr for Price {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(Price {
+            n: reader.read()?,
+            d: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for Price {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.n);
+        writer.write(&self.d);
+    }
+}
+
+/// The amounts of an asset that are committed to open offers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Liabilities {
+    /// The amount offers are waiting to buy.
+    pub buying: i64,
+    /// The amount offers are waiting to sell.
+    pub selling: i64,
+}
+
+impl FromXdr for Liabilities {
+    fn from_xdr(reader: &mut Reader) -> Result<Self> {
+        Ok(Liabilities {
+            buying: reader.read()?,
+            selling: reader.read()?,
+        })
+    }
+}
+
+impl ToXdr for Liabilities {
+    fn to_xdr(&self, writer: &mut Writer) {
+        writer.write(&self.buying);
+        writer.write(&self.selling);
+    }
+}